@@ -0,0 +1,21 @@
+-- Member Email Verification
+ALTER TABLE member ADD COLUMN email_verified_at TIMESTAMPTZ;
+
+-- Existing accounts are trusted.
+UPDATE member SET email_verified_at = created_at WHERE email IS NOT NULL;
+
+-- Email Verification and Password Reset Tokens
+CREATE TABLE member_token (
+    id            SERIAL PRIMARY KEY,
+
+    member_id     INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    type_of       SMALLINT NOT NULL,
+    token_hash    TEXT NOT NULL,
+    email         TEXT,
+
+    created_at    TIMESTAMPTZ NOT NULL,
+    expires_at    TIMESTAMPTZ NOT NULL,
+
+    UNIQUE(token_hash)
+);
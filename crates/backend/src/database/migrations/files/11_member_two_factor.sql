@@ -0,0 +1,27 @@
+-- Member TOTP Two-Factor Authentication
+CREATE TABLE member_totp (
+    member_id         INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    secret            TEXT NOT NULL,
+    -- Last accepted time step. Codes can't be reused.
+    last_used_step    BIGINT NOT NULL DEFAULT 0,
+
+    -- Set once the first code was verified.
+    enabled_at        TIMESTAMPTZ,
+    created_at        TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (member_id)
+);
+
+-- Single-use Two-Factor Recovery Codes
+CREATE TABLE member_recovery_code (
+    id            SERIAL PRIMARY KEY,
+
+    member_id     INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    code_hash     TEXT NOT NULL,
+
+    created_at    TIMESTAMPTZ NOT NULL,
+
+    UNIQUE(member_id, code_hash)
+);
@@ -0,0 +1,21 @@
+-- Server-side Sessions
+CREATE TABLE member_session (
+    id              SERIAL PRIMARY KEY,
+
+    key_hash        TEXT NOT NULL,
+    state           TEXT NOT NULL,
+
+    -- Set once logged in.
+    member_id       INT references member(id) ON DELETE CASCADE,
+    login_id        TEXT,
+    user_agent      TEXT,
+    ip_address      TEXT,
+
+    created_at      TIMESTAMPTZ NOT NULL,
+    last_seen_at    TIMESTAMPTZ NOT NULL,
+    expires_at      TIMESTAMPTZ NOT NULL,
+
+    UNIQUE(key_hash)
+);
+
+CREATE INDEX member_session_member_id_idx ON member_session (member_id);
@@ -0,0 +1,23 @@
+-- Audit Log
+CREATE TABLE audit_log (
+    id              SERIAL PRIMARY KEY,
+
+    -- Not referenced so entries outlive the member.
+    member_id       INT NOT NULL,
+
+    action          SMALLINT NOT NULL,
+    target_id       TEXT,
+
+    before          TEXT,
+    after           TEXT,
+
+    ip_address      TEXT,
+
+    created_at      TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX audit_log_member_id_idx ON audit_log (member_id);
+
+-- Append-only
+CREATE RULE audit_log_no_update AS ON UPDATE TO audit_log DO INSTEAD NOTHING;
+CREATE RULE audit_log_no_delete AS ON DELETE TO audit_log DO INSTEAD NOTHING;
@@ -0,0 +1,2 @@
+-- Server Link Last Seen
+ALTER TABLE server_link ADD COLUMN last_seen_at TIMESTAMPTZ;
@@ -0,0 +1,28 @@
+-- Signed Request Nonces
+CREATE TABLE server_link_nonce (
+    server_link_id  INT NOT NULL references server_link(id) ON DELETE CASCADE,
+    nonce           TEXT NOT NULL,
+
+    created_at      TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (server_link_id, nonce)
+);
+
+-- Server Link Rate Limits
+CREATE TABLE server_link_quota (
+    server_link_id      INT NOT NULL references server_link(id) ON DELETE CASCADE,
+
+    -- NULL uses the configured default.
+    requests_per_minute INT,
+    requests_per_day    INT,
+
+    minute_started_at   TIMESTAMPTZ NOT NULL,
+    minute_requests     INT NOT NULL,
+    day_started_at      TIMESTAMPTZ NOT NULL,
+    day_requests        INT NOT NULL,
+
+    total_requests      BIGINT NOT NULL,
+    throttled_requests  BIGINT NOT NULL,
+
+    PRIMARY KEY (server_link_id)
+);
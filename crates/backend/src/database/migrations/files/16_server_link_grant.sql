@@ -0,0 +1,18 @@
+-- Member Grants for Linked Servers
+CREATE TABLE server_link_grant (
+    id              SERIAL PRIMARY KEY,
+
+    server_link_id  INT NOT NULL references server_link(id) ON DELETE CASCADE,
+    member_id       INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    scopes          BIGINT NOT NULL,
+
+    created_at      TIMESTAMPTZ NOT NULL,
+    updated_at      TIMESTAMPTZ NOT NULL,
+    last_used_at    TIMESTAMPTZ,
+
+    UNIQUE(server_link_id, member_id)
+);
+
+-- Edits submitted through a linked server
+ALTER TABLE edit ADD COLUMN server_link_id INT references server_link(id) ON DELETE SET NULL;
@@ -0,0 +1,2 @@
+-- Threaded replies to Edit Comments
+ALTER TABLE edit_comment ADD COLUMN parent_id INT references edit_comment(id) ON DELETE CASCADE;
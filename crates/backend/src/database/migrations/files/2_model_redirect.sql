@@ -0,0 +1,11 @@
+-- Create Model Redirect Table
+CREATE TABLE model_redirect (
+    type_of     SMALLINT NOT NULL,
+
+    old_id      INT NOT NULL,
+    new_id      INT NOT NULL,
+
+    created_at  TIMESTAMPTZ NOT NULL,
+
+    UNIQUE(type_of, old_id)
+);
@@ -0,0 +1,2 @@
+-- Add Member Reputation
+ALTER TABLE member ADD COLUMN reputation INT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Link edits created by the same mass edit
+ALTER TABLE edit ADD COLUMN batch_id INT references edit(id) ON DELETE CASCADE;
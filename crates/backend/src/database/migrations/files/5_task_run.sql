@@ -0,0 +1,14 @@
+-- Task Run History
+CREATE TABLE task_run (
+    id          SERIAL PRIMARY KEY,
+
+    name        TEXT NOT NULL,
+    status      SMALLINT NOT NULL,
+
+    member_id   INT references member(id) ON DELETE SET NULL,
+
+    error       TEXT,
+
+    started_at  TIMESTAMPTZ NOT NULL,
+    ended_at    TIMESTAMPTZ
+);
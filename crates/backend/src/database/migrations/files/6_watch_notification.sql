@@ -0,0 +1,28 @@
+-- Watches
+CREATE TABLE watch (
+    member_id   INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    type_of     SMALLINT NOT NULL,
+    model_id    INT NOT NULL,
+
+    created_at  TIMESTAMPTZ NOT NULL,
+
+    UNIQUE(member_id, type_of, model_id)
+);
+
+-- Notifications
+CREATE TABLE notification (
+    id          SERIAL PRIMARY KEY,
+
+    member_id   INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    type_of     SMALLINT NOT NULL,
+
+    edit_id     INT NOT NULL references edit(id) ON DELETE CASCADE,
+    comment_id  INT references edit_comment(id) ON DELETE CASCADE,
+
+    is_read     BOOL NOT NULL DEFAULT false,
+    is_emailed  BOOL NOT NULL DEFAULT false,
+
+    created_at  TIMESTAMPTZ NOT NULL
+);
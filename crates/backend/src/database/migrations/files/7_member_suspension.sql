@@ -0,0 +1,3 @@
+-- Add Member Suspension
+ALTER TABLE member ADD COLUMN suspension_reason TEXT;
+ALTER TABLE member ADD COLUMN suspended_until TIMESTAMPTZ;
@@ -0,0 +1,16 @@
+-- API Tokens
+CREATE TABLE api_token (
+    id            SERIAL PRIMARY KEY,
+
+    member_id     INT NOT NULL references member(id) ON DELETE CASCADE,
+
+    name          TEXT NOT NULL,
+    token_hash    TEXT NOT NULL,
+    scopes        BIGINT NOT NULL,
+
+    last_used_at  TIMESTAMPTZ,
+    expires_at    TIMESTAMPTZ,
+    created_at    TIMESTAMPTZ NOT NULL,
+
+    UNIQUE(token_hash)
+);
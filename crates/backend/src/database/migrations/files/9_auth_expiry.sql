@@ -0,0 +1,7 @@
+-- Bind Passwordless Tokens to an Email and Expire them
+DELETE FROM auth;
+
+ALTER TABLE auth DROP COLUMN oauth_token_secret;
+ALTER TABLE auth ADD COLUMN email TEXT NOT NULL;
+ALTER TABLE auth ADD COLUMN ip_address TEXT;
+ALTER TABLE auth ADD COLUMN expires_at TIMESTAMPTZ NOT NULL;
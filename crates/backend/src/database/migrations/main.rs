@@ -121,6 +121,13 @@ pub async fn init(client: &Client) -> Result<()> {
             created_at     TIMESTAMPTZ NOT NULL,
             updated_at     TIMESTAMPTZ NOT NULL,
 
+            reputation     INT NOT NULL DEFAULT 0,
+
+            suspension_reason  TEXT,
+            suspended_until    TIMESTAMPTZ,
+
+            email_verified_at  TIMESTAMPTZ,
+
             UNIQUE(email)
         );"#,
             &[],
@@ -132,9 +139,12 @@ pub async fn init(client: &Client) -> Result<()> {
         .execute(
             r#"CREATE TABLE auth (
             oauth_token          TEXT NOT NULL,
-            oauth_token_secret   TEXT NOT NULL,
+
+            email                TEXT NOT NULL,
+            ip_address           TEXT,
 
             created_at           TIMESTAMPTZ NOT NULL,
+            expires_at           TIMESTAMPTZ NOT NULL,
 
             UNIQUE(oauth_token)
         );"#,
@@ -237,7 +247,9 @@ pub async fn init(client: &Client) -> Result<()> {
             ended_at     TIMESTAMPTZ,
             expires_at   TIMESTAMPTZ,
             created_at   TIMESTAMPTZ NOT NULL,
-            updated_at   TIMESTAMPTZ NOT NULL
+            updated_at   TIMESTAMPTZ NOT NULL,
+
+            batch_id     INT references edit(id) ON DELETE CASCADE
         );"#,
             &[],
         )
@@ -274,8 +286,9 @@ pub async fn init(client: &Client) -> Result<()> {
             text        TEXT NOT NULL,
             deleted     BOOL NOT NULL,
 
-            created_at  TIMESTAMPTZ NOT NULL
+            created_at  TIMESTAMPTZ NOT NULL,
 
+            parent_id   INT references edit_comment(id) ON DELETE CASCADE
         );"#,
             &[],
         )
@@ -298,6 +311,7 @@ pub async fn init(client: &Client) -> Result<()> {
 
             created_at         TIMESTAMPTZ NOT NULL,
             updated_at         TIMESTAMPTZ NOT NULL,
+            last_seen_at       TIMESTAMPTZ,
 
             UNIQUE("server_id")
         );"#,
@@ -399,6 +413,302 @@ pub async fn init(client: &Client) -> Result<()> {
         )
         .await?;
 
+    // Model Redirect
+    client
+        .execute(
+            r#"CREATE TABLE model_redirect (
+            type_of     SMALLINT NOT NULL,
+
+            old_id      INT NOT NULL,
+            new_id      INT NOT NULL,
+
+            created_at  TIMESTAMPTZ NOT NULL,
+
+            UNIQUE(type_of, old_id)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Task Run
+    client
+        .execute(
+            r#"CREATE TABLE task_run (
+            id          SERIAL PRIMARY KEY,
+
+            name        TEXT NOT NULL,
+            status      SMALLINT NOT NULL,
+
+            member_id   INT references member(id) ON DELETE SET NULL,
+
+            error       TEXT,
+
+            started_at  TIMESTAMPTZ NOT NULL,
+            ended_at    TIMESTAMPTZ
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Watch
+    client
+        .execute(
+            r#"CREATE TABLE watch (
+            member_id   INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            type_of     SMALLINT NOT NULL,
+            model_id    INT NOT NULL,
+
+            created_at  TIMESTAMPTZ NOT NULL,
+
+            UNIQUE(member_id, type_of, model_id)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Notification
+    client
+        .execute(
+            r#"CREATE TABLE notification (
+            id          SERIAL PRIMARY KEY,
+
+            member_id   INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            type_of     SMALLINT NOT NULL,
+
+            edit_id     INT NOT NULL references edit(id) ON DELETE CASCADE,
+            comment_id  INT references edit_comment(id) ON DELETE CASCADE,
+
+            is_read     BOOL NOT NULL DEFAULT false,
+            is_emailed  BOOL NOT NULL DEFAULT false,
+
+            created_at  TIMESTAMPTZ NOT NULL
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // API Token
+    client
+        .execute(
+            r#"CREATE TABLE api_token (
+            id            SERIAL PRIMARY KEY,
+
+            member_id     INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            name          TEXT NOT NULL,
+            token_hash    TEXT NOT NULL,
+            scopes        BIGINT NOT NULL,
+
+            last_used_at  TIMESTAMPTZ,
+            expires_at    TIMESTAMPTZ,
+            created_at    TIMESTAMPTZ NOT NULL,
+
+            UNIQUE(token_hash)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Member Token
+    client
+        .execute(
+            r#"CREATE TABLE member_token (
+            id            SERIAL PRIMARY KEY,
+
+            member_id     INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            type_of       SMALLINT NOT NULL,
+            token_hash    TEXT NOT NULL,
+            email         TEXT,
+
+            created_at    TIMESTAMPTZ NOT NULL,
+            expires_at    TIMESTAMPTZ NOT NULL,
+
+            UNIQUE(token_hash)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Member TOTP
+    client
+        .execute(
+            r#"CREATE TABLE member_totp (
+            member_id         INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            secret            TEXT NOT NULL,
+            last_used_step    BIGINT NOT NULL DEFAULT 0,
+
+            enabled_at        TIMESTAMPTZ,
+            created_at        TIMESTAMPTZ NOT NULL,
+
+            PRIMARY KEY (member_id)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Member Recovery Code
+    client
+        .execute(
+            r#"CREATE TABLE member_recovery_code (
+            id            SERIAL PRIMARY KEY,
+
+            member_id     INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            code_hash     TEXT NOT NULL,
+
+            created_at    TIMESTAMPTZ NOT NULL,
+
+            UNIQUE(member_id, code_hash)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Member Session
+    client
+        .execute(
+            r#"CREATE TABLE member_session (
+            id              SERIAL PRIMARY KEY,
+
+            key_hash        TEXT NOT NULL,
+            state           TEXT NOT NULL,
+
+            member_id       INT references member(id) ON DELETE CASCADE,
+            login_id        TEXT,
+            user_agent      TEXT,
+            ip_address      TEXT,
+
+            created_at      TIMESTAMPTZ NOT NULL,
+            last_seen_at    TIMESTAMPTZ NOT NULL,
+            expires_at      TIMESTAMPTZ NOT NULL,
+
+            UNIQUE(key_hash)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    client
+        .execute(
+            "CREATE INDEX member_session_member_id_idx ON member_session (member_id);",
+            &[],
+        )
+        .await?;
+
+    // Audit Log
+    client
+        .execute(
+            r#"CREATE TABLE audit_log (
+            id              SERIAL PRIMARY KEY,
+
+            member_id       INT NOT NULL,
+
+            action          SMALLINT NOT NULL,
+            target_id       TEXT,
+
+            before          TEXT,
+            after           TEXT,
+
+            ip_address      TEXT,
+
+            created_at      TIMESTAMPTZ NOT NULL
+        );"#,
+            &[],
+        )
+        .await?;
+
+    client
+        .execute(
+            "CREATE INDEX audit_log_member_id_idx ON audit_log (member_id);",
+            &[],
+        )
+        .await?;
+
+    client
+        .execute(
+            "CREATE RULE audit_log_no_update AS ON UPDATE TO audit_log DO INSTEAD NOTHING;",
+            &[],
+        )
+        .await?;
+
+    client
+        .execute(
+            "CREATE RULE audit_log_no_delete AS ON DELETE TO audit_log DO INSTEAD NOTHING;",
+            &[],
+        )
+        .await?;
+
+    // Server Link Nonce
+    client
+        .execute(
+            r#"CREATE TABLE server_link_nonce (
+            server_link_id  INT NOT NULL references server_link(id) ON DELETE CASCADE,
+            nonce           TEXT NOT NULL,
+
+            created_at      TIMESTAMPTZ NOT NULL,
+
+            PRIMARY KEY (server_link_id, nonce)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Server Link Quota
+    client
+        .execute(
+            r#"CREATE TABLE server_link_quota (
+            server_link_id      INT NOT NULL references server_link(id) ON DELETE CASCADE,
+
+            requests_per_minute INT,
+            requests_per_day    INT,
+
+            minute_started_at   TIMESTAMPTZ NOT NULL,
+            minute_requests     INT NOT NULL,
+            day_started_at      TIMESTAMPTZ NOT NULL,
+            day_requests        INT NOT NULL,
+
+            total_requests      BIGINT NOT NULL,
+            throttled_requests  BIGINT NOT NULL,
+
+            PRIMARY KEY (server_link_id)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // Server Link Grant
+    client
+        .execute(
+            r#"CREATE TABLE server_link_grant (
+            id              SERIAL PRIMARY KEY,
+
+            server_link_id  INT NOT NULL references server_link(id) ON DELETE CASCADE,
+            member_id       INT NOT NULL references member(id) ON DELETE CASCADE,
+
+            scopes          BIGINT NOT NULL,
+
+            created_at      TIMESTAMPTZ NOT NULL,
+            updated_at      TIMESTAMPTZ NOT NULL,
+            last_used_at    TIMESTAMPTZ,
+
+            UNIQUE(server_link_id, member_id)
+        );"#,
+            &[],
+        )
+        .await?;
+
+    // The edit table is created before server_link.
+    client
+        .execute(
+            "ALTER TABLE edit ADD COLUMN server_link_id INT references server_link(id) ON DELETE SET NULL;",
+            &[],
+        )
+        .await?;
+
     // Affiliated Book ISBN
     // client.execute(
     //     r#"CREATE TABLE IF NOT EXISTS book_isbn (
@@ -7,8 +7,24 @@ use crate::Result;
 
 mod main;
 
-const MIGRATIONS: [(i32, &str, &str, &str); 1] = [
-    (1, "1_isbn_separation", include_str!("files/1_isbn_separation.sql"), "Separate ISBN's into own table")
+const MIGRATIONS: [(i32, &str, &str, &str); 17] = [
+    (1, "1_isbn_separation", include_str!("files/1_isbn_separation.sql"), "Separate ISBN's into own table"),
+    (2, "2_model_redirect", include_str!("files/2_model_redirect.sql"), "Redirect merged Books and People to the remaining model"),
+    (3, "3_member_reputation", include_str!("files/3_member_reputation.sql"), "Store a reputation score on each member"),
+    (4, "4_edit_batch", include_str!("files/4_edit_batch.sql"), "Link edits which were created by the same mass edit"),
+    (5, "5_task_run", include_str!("files/5_task_run.sql"), "Store the run history of scheduled tasks"),
+    (6, "6_watch_notification", include_str!("files/6_watch_notification.sql"), "Let members watch models and receive notifications"),
+    (7, "7_member_suspension", include_str!("files/7_member_suspension.sql"), "Let members be suspended with a reason and expiry"),
+    (8, "8_api_token", include_str!("files/8_api_token.sql"), "Let members create API tokens for scripted access"),
+    (9, "9_auth_expiry", include_str!("files/9_auth_expiry.sql"), "Bind passwordless tokens to an email and expire them"),
+    (10, "10_member_verification", include_str!("files/10_member_verification.sql"), "Verify member emails and allow password resets"),
+    (11, "11_member_two_factor", include_str!("files/11_member_two_factor.sql"), "Let members protect their account with TOTP two-factor authentication"),
+    (12, "12_member_session", include_str!("files/12_member_session.sql"), "Store sessions server-side so they can be listed and revoked"),
+    (13, "13_audit_log", include_str!("files/13_audit_log.sql"), "Record administrative actions in an append-only audit log"),
+    (14, "14_server_link_last_seen", include_str!("files/14_server_link_last_seen.sql"), "Track when linked servers last searched through us"),
+    (15, "15_server_link_quota", include_str!("files/15_server_link_quota.sql"), "Replay protection and rate limits for signed linked server requests"),
+    (16, "16_server_link_grant", include_str!("files/16_server_link_grant.sql"), "Let members grant linked servers scoped access to act on their behalf"),
+    (17, "17_edit_comment_reply", include_str!("files/17_edit_comment_reply.sql"), "Let edit comments reply to another comment"),
 ];
 
 
@@ -20,9 +20,11 @@ use serde_json::Error as JsonError;
 use serde_urlencoded::ser::Error as UrlEncodedSerError;
 use serde_xml_rs::Error as XmlError;
 use tokio_postgres::Error as PostgresError;
+use totp_rs::{SecretParseError, TotpUrlError};
 use url::ParseError as UrlParseError;
 
 use actix_multipart::MultipartError;
+use actix_session::{SessionGetError, SessionInsertError};
 use actix_web::error::PayloadError;
 use actix_web::error::UrlencodedError;
 use actix_web::Error as ActixError;
@@ -57,9 +59,25 @@ pub enum WebError {
 
     #[error(transparent)]
     ApiResponse(#[from] ApiErrorResponse),
+
+    /// Seconds until the request can be retried.
+    #[error("Rate limit exceeded. Retry in {0} seconds")]
+    RateLimited(i64),
+
+    /// The request contained invalid values.
+    #[error("{0}")]
+    BadRequest(&'static str),
 }
 
 impl ResponseError for WebError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            Self::BadRequest(_) => actix_web::http::StatusCode::BAD_REQUEST,
+            Self::RateLimited(_) => actix_web::http::StatusCode::TOO_MANY_REQUESTS,
+            _ => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
     fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
         let resp_value = match self {
             Self::ApiResponse(r) => WrappingResponse::<()>::Error(r.clone()),
@@ -78,6 +96,13 @@ impl ResponseError for WebError {
             actix_web::http::header::HeaderValue::from_static("text/plain; charset=utf-8"),
         );
 
+        if let Self::RateLimited(secs) = self {
+            res.headers_mut().insert(
+                actix_web::http::header::RETRY_AFTER,
+                actix_web::http::header::HeaderValue::from(*secs),
+            );
+        }
+
         res.set_body(actix_web::body::BoxBody::new(
             serde_json::to_string(&resp_value).unwrap(),
         ))
@@ -125,6 +150,14 @@ pub enum Error {
     Postgres(#[from] PostgresError),
     #[error("Bcrypt Error: {0}")]
     Bcrypt(#[from] BcryptError),
+    #[error("TOTP Error: {0}")]
+    Totp(#[from] TotpUrlError),
+    #[error("TOTP Secret Error: {0}")]
+    TotpSecret(#[from] SecretParseError),
+    #[error("Session Get Error: {0}")]
+    SessionGet(#[from] SessionGetError),
+    #[error("Session Insert Error: {0}")]
+    SessionInsert(#[from] SessionInsertError),
 
     #[error("Backblaze Error: {0}")]
     B2(#[from] JsonErrorStruct),
@@ -138,6 +171,9 @@ pub enum InternalError {
     #[error("The user does not exist")]
     UserMissing,
 
+    #[error("The user is suspended: {0}")]
+    UserSuspended(String),
+
     #[error("The item does not exist")]
     ItemMissing,
 
@@ -147,6 +183,12 @@ pub enum InternalError {
     #[error("Invalid ISBN")]
     InvalidIsbn,
 
+    #[error("This edit operation isn't supported yet")]
+    UnsupportedEditOperation,
+
+    #[error("The edit data doesn't match the edit type")]
+    EditDataMismatch,
+
     // Backblaze
     #[error("Backblaze B2 Authorization Error.")]
     B2Authorization,
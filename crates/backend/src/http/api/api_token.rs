@@ -0,0 +1,108 @@
+use actix_web::{delete, get, post, web};
+use chrono::Utc;
+use common::api::{ApiErrorResponse, WrappingResponse};
+use common_local::{api, ApiTokenId};
+use tokio_postgres::Client;
+
+use crate::{
+    hash_token,
+    http::{passwordless::gen_sample_alphanumeric, JsonResponse, MemberCookie},
+    model::{ApiTokenModel, NewApiTokenModel},
+    WebResult,
+};
+
+#[get("/tokens")]
+async fn get_api_tokens(
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetApiTokensResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    let items = ApiTokenModel::find_by_member_id(member.id, &db)
+        .await?
+        .into_iter()
+        .map(|v| v.into())
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetApiTokensResponse { items },
+    )))
+}
+
+#[post("/token")]
+async fn create_api_token(
+    body: web::Json<api::NewApiTokenBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::NewApiTokenResponse>> {
+    // Tokens aren't able to create more tokens.
+    if member.is_api_token() {
+        return Err(ApiErrorResponse::new("API tokens must be created from a session").into());
+    }
+
+    let member = member.fetch_or_error(&db).await?;
+
+    let api::NewApiTokenBody {
+        name,
+        scopes,
+        expires_at,
+    } = body.into_inner();
+
+    let name = name.trim().to_string();
+
+    if name.is_empty() {
+        return Err(ApiErrorResponse::new("A name is required").into());
+    }
+
+    if scopes.is_empty() {
+        return Err(ApiErrorResponse::new("At least one scope is required").into());
+    }
+
+    if expires_at.filter(|v| *v <= Utc::now()).is_some() {
+        return Err(ApiErrorResponse::new("Expiration must be in the future").into());
+    }
+
+    let secret = format!(
+        "lib_{}",
+        gen_sample_alphanumeric(40, &mut rand::thread_rng())
+    );
+
+    let token = NewApiTokenModel {
+        member_id: member.id,
+        name,
+        token_hash: hash_token(&secret),
+        scopes,
+        expires_at,
+        created_at: Utc::now(),
+    }
+    .insert(&db)
+    .await?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::NewApiTokenResponse {
+            token: token.into(),
+            secret,
+        },
+    )))
+}
+
+#[delete("/token/{id}")]
+async fn revoke_api_token(
+    id: web::Path<ApiTokenId>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    if member.is_api_token() {
+        return Err(ApiErrorResponse::new("API tokens must be revoked from a session").into());
+    }
+
+    let member = member.fetch_or_error(&db).await?;
+
+    if ApiTokenModel::remove(*id, member.id, &db).await? == 0 {
+        return Ok(web::Json(WrappingResponse::error(
+            "Unable to find API Token.",
+        )));
+    }
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
@@ -0,0 +1,44 @@
+use actix_web::{get, web};
+use common::api::{ApiErrorResponse, QueryListResponse, WrappingResponse};
+use common_local::api;
+use serde_qs::actix::QsQuery;
+use tokio_postgres::Client;
+
+use crate::{
+    http::{JsonResponse, MemberCookie},
+    model::AuditLogModel,
+    WebResult,
+};
+
+#[get("/admin/audit")]
+async fn get_audit_log(
+    query: QsQuery<api::AuditLogQuery>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetAuditLogResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
+    }
+
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(25).min(100);
+
+    let mut query = query.into_inner();
+
+    query.limit = Some(limit);
+
+    let items = AuditLogModel::find_by_filter(&query, &db)
+        .await?
+        .into_iter()
+        .map(|v| v.into())
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(QueryListResponse {
+        offset,
+        limit,
+        total: AuditLogModel::count_by_filter(&query, &db).await?,
+        items,
+    })))
+}
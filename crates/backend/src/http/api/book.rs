@@ -1,19 +1,21 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 
 use chrono::{TimeZone, Utc};
-use common::api::WrappingResponse;
-use common::{BookId, Either, ImageType, ThumbnailStore};
-use common_local::edit::ModifyValuesBy;
+use common::api::{QueryListResponse, WrappingResponse};
+use common::{BookId, Either, ThumbnailStore};
+use common_local::audit::AuditAction;
+use common_local::edit::{EditOperation, EditStatus, EditType, ModifyValuesBy};
 use common_local::item::edit::{BookEdit, NewOrCachedImage};
 use common_local::{api, DisplayItem, DisplayMetaItem, MetadataItemCached};
+use serde_json::json;
 use serde_qs::actix::QsQuery;
 use tokio_postgres::Client;
 
-use crate::http::{JsonResponse, MemberCookie};
+use crate::http::{get_ip_address, JsonResponse, MemberCookie};
 use crate::metadata::MetadataReturned;
 use crate::model::{
-    BookModel, BookPersonModel, BookTagWithTagModel, ImageLinkModel, NewEditModel, PersonModel,
-    UploadedImageModel, BookIsbnModel, NewEditVoteModel,
+    BookIsbnModel, BookModel, BookPersonModel, BookTagWithTagModel, EditModel, ImageLinkModel,
+    NewAuditLogModel, NewEditModel, NewEditVoteModel, PersonModel, UploadedImageModel,
 };
 use crate::storage::get_storage;
 use crate::{metadata, Error, InternalError, WebResult};
@@ -24,6 +26,7 @@ pub async fn add_new_book(
     member: MemberCookie,
     db: web::Data<Client>,
 ) -> WebResult<JsonResponse<Option<DisplayMetaItem>>> {
+    let server_link_id = member.server_link_id();
     let member = member.fetch_or_error(&db).await?;
 
     if !member.permissions.has_editing_perms() {
@@ -35,78 +38,82 @@ pub async fn add_new_book(
     // TODO: Cleanup. We have one statement which doesn't utilize "value" field.
     let value = match body.into_inner() {
         api::NewBookBody::UpdateMultiple(edit) => {
-            // TODO: Optimize instead of using loops.
-            // People
-            match edit.people_list_mod {
-                // TODO: Utilize Edit
-                ModifyValuesBy::Overwrite => {
-                    for book_id in edit.book_ids {
-                        BookPersonModel::remove_by_book_id(book_id, &db).await?;
-
-                        for person_id in edit.people_list.iter().copied() {
-                            BookPersonModel {
-                                book_id,
-                                person_id,
-                                info: None,
-                            }
-                            .insert(&db)
-                            .await?;
-                        }
+            // Every book receives its own edit. They're grouped by the id of the first one.
+            let mut batch_id = None;
+
+            for book_id in edit.book_ids.iter().copied() {
+                let current_book = match BookModel::get_by_id(book_id, &db).await? {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                let mut updated_book = BookEdit {
+                    language: edit.language,
+                    publisher: edit.publisher.clone(),
+                    is_public: edit.is_public,
+                    ..BookEdit::default()
+                };
+
+                // People
+                if !edit.people_list.is_empty() {
+                    let current = BookPersonModel::get_all_by_book_id(book_id, &db)
+                        .await?
+                        .into_iter()
+                        .map(|v| v.person_id)
+                        .collect::<Vec<_>>();
+
+                    (updated_book.added_people, updated_book.removed_people) =
+                        modify_values_by(edit.people_list_mod, &current, &edit.people_list);
+
+                    if edit.people_list_mod == ModifyValuesBy::Overwrite {
+                        updated_book.display_person_id = edit.people_list.first().copied();
+                    }
+                }
 
-                        // Update the cached author name
-                        if let Some(person_id) = edit.people_list.first().copied() {
-                            let person = PersonModel::get_by_id(person_id, &db).await?;
-                            let book = BookModel::get_by_id(book_id, &db).await?;
+                // Tags
+                if !edit.tags_list.is_empty() {
+                    let current = BookTagWithTagModel::get_by_book_id(book_id, &db)
+                        .await?
+                        .into_iter()
+                        .map(|v| v.tag.id)
+                        .collect::<Vec<_>>();
 
-                            if let Some((person, mut book)) = person.zip(book) {
-                                book.cached.author = Some(person.name);
-                                book.update_book(&db).await?;
-                            }
-                        }
-                    }
+                    (updated_book.added_tags, updated_book.removed_tags) =
+                        modify_values_by(edit.tags_list_mod, &current, &edit.tags_list);
                 }
 
-                ModifyValuesBy::Append => {
-                    for book_id in edit.book_ids {
-                        for person_id in edit.people_list.iter().copied() {
-                            BookPersonModel {
-                                book_id,
-                                person_id,
-                                info: None,
-                            }
-                            .insert(&db)
-                            .await?;
-                        }
-                    }
+                if updated_book.is_empty() {
+                    continue;
                 }
 
-                ModifyValuesBy::Remove => {
-                    for book_id in edit.book_ids {
-                        for person_id in edit.people_list.iter().copied() {
-                            BookPersonModel {
-                                book_id,
-                                person_id,
-                                info: None,
-                            }
-                            .remove(&db)
-                            .await?;
-                        }
+                let mut model =
+                    NewEditModel::from_book_modify(member.id, current_book, updated_book, &db)
+                        .await?;
 
-                        // TODO: Check if we removed cached author
-                        // If book has no other people referenced we'll update the cached author name.
-                        // TODO: Exists instead of getting all items
-                        if BookPersonModel::get_all_by_book_id(book_id, &db)
-                            .await?
-                            .is_empty()
-                        {
-                            let book = BookModel::get_by_id(book_id, &db).await?;
-
-                            if let Some(mut book) = book {
-                                book.cached.author = None;
-                                book.update_book(&db).await?;
-                            }
-                        }
-                    }
+                model.server_link_id = server_link_id;
+
+                if model.is_modify_empty()? {
+                    continue;
+                }
+
+                if !member.permissions.is_admin() {
+                    model.vote_count += 1;
+                }
+
+                let mut model = model.insert(&db).await?;
+
+                let id = *batch_id.get_or_insert(model.id);
+                model.update_batch_id(id, &db).await?;
+
+                // Admins apply mass edits instantly. Everyone else has to go through voting.
+                if member.permissions.is_admin() {
+                    model
+                        .process_status_change(EditStatus::ForceAccepted, &db)
+                        .await?;
+                } else {
+                    NewEditVoteModel::create(model.id, member.id, true)
+                        .insert(&db)
+                        .await?;
                 }
             }
 
@@ -347,11 +354,27 @@ pub async fn load_book_list(
 #[get("/book/{id}")]
 pub async fn get_book_info(
     book_id: web::Path<BookId>,
+    member: Option<MemberCookie>,
     db: web::Data<Client>,
 ) -> WebResult<JsonResponse<api::MediaViewResponse>> {
-    let book = BookModel::get_by_id(*book_id, &db)
+    let book = BookModel::get_by_id_or_redirect(*book_id, &db)
         .await?
         .ok_or_else(|| Error::from(InternalError::ItemMissing))?;
+
+    // Deleted books are only visible to admins (from the trash).
+    if book.deleted_at.is_some() {
+        let is_admin = match member {
+            Some(member) => member
+                .fetch(&db)
+                .await?
+                .map_or(false, |v| v.permissions.is_admin()),
+            None => false,
+        };
+
+        if !is_admin {
+            return Err(Error::from(InternalError::ItemMissing).into());
+        }
+    }
     let people = PersonModel::get_all_by_book_id_w_info(book.id, &db).await?;
     let tags = BookTagWithTagModel::get_by_book_id(book.id, &db).await?;
 
@@ -378,6 +401,7 @@ pub async fn update_book_id(
 ) -> WebResult<JsonResponse<&'static str>> {
     let body = body.into_inner();
 
+    let server_link_id = member.server_link_id();
     let member = member.fetch_or_error(&db).await?;
 
     if !member.permissions.has_editing_perms() {
@@ -391,15 +415,30 @@ pub async fn update_book_id(
     if let Some((updated_book, current_book)) = Some(body).zip(current_book) {
         // Make sure we have something we're updating.
         if !updated_book.is_empty() {
+            // Trusted members can skip voting on low-risk changes.
+            let skip_voting = updated_book.is_low_risk() && member.is_trusted();
+
             let mut model =
                 NewEditModel::from_book_modify(member.id, current_book, updated_book, &db).await?;
 
-            if !model.data.is_empty() {
-                model.vote_count += 1;
+            model.server_link_id = server_link_id;
+
+            if !model.is_modify_empty()? {
+                if skip_voting {
+                    let mut model = model.insert(&db).await?;
+
+                    model
+                        .process_status_change(EditStatus::Accepted, &db)
+                        .await?;
+                } else {
+                    model.vote_count += 1;
 
-                let model = model.insert(&db).await?;
+                    let model = model.insert(&db).await?;
 
-                NewEditVoteModel::create(model.id, member.id, true).insert(&db).await?;
+                    NewEditVoteModel::create(model.id, member.id, true)
+                        .insert(&db)
+                        .await?;
+                }
             }
         }
     }
@@ -407,12 +446,93 @@ pub async fn update_book_id(
     Ok(web::Json(WrappingResponse::okay("success")))
 }
 
+#[post("/book/{id}/merge/{into_id}")]
+pub async fn merge_book_id(
+    req: HttpRequest,
+    path: web::Path<(BookId, BookId)>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    let (book_id, into_book_id) = path.into_inner();
+
+    let server_link_id = member.server_link_id();
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.has_editing_perms() {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot do this! No Permissions!",
+        )));
+    }
+
+    if book_id == into_book_id {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot merge the same book into itself!",
+        )));
+    }
+
+    let old_book = BookModel::get_by_id(book_id, &db)
+        .await?
+        .ok_or_else(|| Error::from(InternalError::ItemMissing))?;
+    let into_book = BookModel::get_by_id(into_book_id, &db)
+        .await?
+        .ok_or_else(|| Error::from(InternalError::ItemMissing))?;
+
+    if old_book.deleted_at.is_some() || into_book.deleted_at.is_some() {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot merge a deleted book!",
+        )));
+    }
+
+    if EditModel::exists_pending_merge(EditType::Book, *book_id, *into_book_id, &db).await? {
+        return Ok(web::Json(WrappingResponse::error(
+            "A merge involving this book is already pending.",
+        )));
+    }
+
+    let before = json!({ "book": old_book, "into_book": into_book });
+
+    let mut model = NewEditModel::from_book_merge(member.id, old_book, into_book)?;
+
+    model.server_link_id = server_link_id;
+
+    // Admins merge instantly. Everyone else has to go through voting.
+    if member.permissions.is_admin() {
+        let mut model = model.insert(&db).await?;
+
+        model
+            .process_status_change(EditStatus::ForceAccepted, &db)
+            .await?;
+
+        let into_book = BookModel::get_by_id(into_book_id, &db).await?;
+
+        NewAuditLogModel::new(member.id, AuditAction::MergeBook, get_ip_address(&req))
+            .target(book_id)
+            .before(&before)?
+            .after(&json!({ "into_book": into_book }))?
+            .insert(&db)
+            .await?;
+    } else {
+        model.vote_count += 1;
+
+        let model = model.insert(&db).await?;
+
+        NewEditVoteModel::create(model.id, member.id, true)
+            .insert(&db)
+            .await?;
+    }
+
+    Ok(web::Json(WrappingResponse::okay("success")))
+}
+
+/// Returns `true` if the book was moved into the trash or `false` if a Delete edit was created.
 #[delete("/book/{id}")]
 pub async fn delete_book_id(
+    req: HttpRequest,
     book_id: web::Path<BookId>,
     member: MemberCookie,
     db: web::Data<Client>,
 ) -> WebResult<JsonResponse<bool>> {
+    let server_link_id = member.server_link_id();
     let member = member.fetch_or_error(&db).await?;
 
     if !member.permissions.has_editing_perms() {
@@ -421,26 +541,195 @@ pub async fn delete_book_id(
         )));
     }
 
-    // TODO: Utilize edit
+    let book = match BookModel::get_by_id(*book_id, &db).await? {
+        Some(v) if v.deleted_at.is_none() => v,
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Book is already deleted.",
+            )))
+        }
+    };
+
+    // Admins skip the voting process. The book will still be placed in the trash.
+    if member.permissions.is_admin() {
+        let amount = BookModel::soft_delete_by_id(book.id, &db).await?;
+
+        if amount != 0 {
+            NewAuditLogModel::new(member.id, AuditAction::DeleteBook, get_ip_address(&req))
+                .target(book.id)
+                .before(&book)?
+                .insert(&db)
+                .await?;
+        }
+
+        return Ok(web::Json(WrappingResponse::okay(amount != 0)));
+    }
+
+    if EditModel::exists_pending_by_model(EditType::Book, EditOperation::Delete, *book.id, &db)
+        .await?
+    {
+        return Ok(web::Json(WrappingResponse::error(
+            "A deletion for this book is already pending.",
+        )));
+    }
+
+    let mut model = NewEditModel::from_book_delete(member.id, book)?;
+
+    model.server_link_id = server_link_id;
+    model.vote_count += 1;
+
+    let model = model.insert(&db).await?;
+
+    NewEditVoteModel::create(model.id, member.id, true)
+        .insert(&db)
+        .await?;
+
+    Ok(web::Json(WrappingResponse::okay(false)))
+}
+
+#[get("/books/deleted")]
+pub async fn load_deleted_book_list(
+    query: web::Query<api::SimpleListQuery>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<QueryListResponse<DisplayMetaItem>>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Ok(web::Json(WrappingResponse::error("Not Admin")));
+    }
+
+    let offset = query.offset.unwrap_or_default();
+    let limit = query.limit.unwrap_or(50);
+
+    let total = BookModel::get_deleted_count(&db).await?;
+    let items = BookModel::find_deleted(offset, limit, &db)
+        .await?
+        .into_iter()
+        .map(|v| v.into())
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(QueryListResponse {
+        offset,
+        limit,
+        total,
+        items,
+    })))
+}
+
+#[post("/book/{id}/restore")]
+pub async fn restore_book_id(
+    req: HttpRequest,
+    book_id: web::Path<BookId>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<bool>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Ok(web::Json(WrappingResponse::error("Not Admin")));
+    }
+
+    let book = match BookModel::get_by_id(*book_id, &db).await? {
+        Some(v) if v.deleted_at.is_some() => v,
+        _ => return Ok(web::Json(WrappingResponse::okay(false))),
+    };
+
+    let amount = BookModel::restore_by_id(book.id, &db).await?;
 
-    let image_links = ImageLinkModel::find_by_link_id(**book_id, ImageType::Book, &db).await?;
+    if amount != 0 {
+        NewAuditLogModel::new(member.id, AuditAction::RestoreBook, get_ip_address(&req))
+            .target(book.id)
+            .before(&json!({ "deleted_at": book.deleted_at }))?
+            .after(&json!({ "deleted_at": null }))?
+            .insert(&db)
+            .await?;
+    }
 
-    let amount = BookModel::remove_by_id(*book_id, &db).await?;
+    Ok(web::Json(WrappingResponse::okay(amount != 0)))
+}
+
+#[delete("/book/{id}/purge")]
+pub async fn purge_book_id(
+    req: HttpRequest,
+    book_id: web::Path<BookId>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<bool>> {
+    let member = member.fetch_or_error(&db).await?;
 
-    // Remove remaining images
-    for ImageLinkModel { image_id, .. } in image_links {
-        // Check how many links there are for the image
-        let count = ImageLinkModel::count_by_image_id(image_id, &db).await?;
+    if !member.permissions.is_admin() {
+        return Ok(web::Json(WrappingResponse::error("Not Admin")));
+    }
 
-        if count == 0 {
-            // If we have no more links then remove it.
-            UploadedImageModel::remove_by_id(image_id, &db).await?;
+    let book = match BookModel::get_by_id(*book_id, &db).await? {
+        Some(book) if book.deleted_at.is_some() => book,
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Book must be in the trash before it can be purged.",
+            )))
         }
+    };
+
+    let amount = BookModel::purge_by_id(book.id, &db).await?;
+
+    if amount != 0 {
+        NewAuditLogModel::new(member.id, AuditAction::PurgeBook, get_ip_address(&req))
+            .target(book.id)
+            .before(&book)?
+            .insert(&db)
+            .await?;
     }
 
     Ok(web::Json(WrappingResponse::okay(amount != 0)))
 }
 
+/// Returns the values which would be (added, removed) from `current` when modified by `values`.
+fn modify_values_by<V: Copy + PartialEq>(
+    modify: ModifyValuesBy,
+    current: &[V],
+    values: &[V],
+) -> (Option<Vec<V>>, Option<Vec<V>>) {
+    let added = match modify {
+        ModifyValuesBy::Overwrite | ModifyValuesBy::Append => values
+            .iter()
+            .filter(|v| !current.contains(v))
+            .copied()
+            .collect(),
+        ModifyValuesBy::Remove => Vec::new(),
+    };
+
+    let removed = match modify {
+        ModifyValuesBy::Overwrite => current
+            .iter()
+            .filter(|v| !values.contains(v))
+            .copied()
+            .collect(),
+        ModifyValuesBy::Remove => values
+            .iter()
+            .filter(|v| current.contains(v))
+            .copied()
+            .collect(),
+        ModifyValuesBy::Append => Vec::new(),
+    };
+
+    (
+        Some(added).filter(|v| !v.is_empty()),
+        Some(removed).filter(|v| !v.is_empty()),
+    )
+}
+
+#[get("/book/{id}/history")]
+async fn load_book_history(
+    book_id: web::Path<BookId>,
+    query: web::Query<api::SimpleListQuery>,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetEditListResponse>> {
+    Ok(web::Json(WrappingResponse::okay(
+        super::edit::load_model_history(EditType::Book, **book_id, &query, &db).await?,
+    )))
+}
+
 #[get("/book/{id}/thumbnail")]
 async fn load_book_thumbnail(
     path: web::Path<BookId>,
@@ -449,7 +738,7 @@ async fn load_book_thumbnail(
 ) -> WebResult<HttpResponse> {
     let book_id = path.into_inner();
 
-    let meta = BookModel::get_by_id(book_id, &db).await?;
+    let meta = BookModel::get_by_id_or_redirect(book_id, &db).await?;
 
     if let Some(file_name) = meta.as_ref().and_then(|v| v.thumb_path.as_value()) {
         Ok(get_storage()
@@ -460,3 +749,56 @@ async fn load_book_thumbnail(
         Ok(HttpResponse::NotFound().finish())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common::PersonId;
+
+    use super::*;
+
+    #[test]
+    fn test_modify_values_by_overwrite() {
+        let (a, b) = (PersonId::from(1), PersonId::from(2));
+
+        let (added, removed) = modify_values_by(ModifyValuesBy::Overwrite, &[a], &[b]);
+
+        assert_eq!(added, Some(vec![b]));
+        assert_eq!(removed, Some(vec![a]));
+
+        let edit = BookEdit {
+            added_people: added,
+            removed_people: removed,
+            ..BookEdit::default()
+        };
+
+        assert!(!edit.is_empty());
+    }
+
+    #[test]
+    fn test_modify_values_by_remove_last() {
+        let a = PersonId::from(1);
+
+        let (added, removed) = modify_values_by(ModifyValuesBy::Remove, &[a], &[a]);
+
+        assert_eq!(added, None);
+        assert_eq!(removed, Some(vec![a]));
+
+        let edit = BookEdit {
+            added_people: added,
+            removed_people: removed,
+            ..BookEdit::default()
+        };
+
+        assert!(!edit.is_empty());
+    }
+
+    #[test]
+    fn test_modify_values_by_unchanged() {
+        let a = PersonId::from(1);
+
+        let (added, removed) = modify_values_by(ModifyValuesBy::Overwrite, &[a], &[a]);
+
+        assert_eq!(added, None);
+        assert_eq!(removed, None);
+    }
+}
@@ -1,11 +1,13 @@
 use actix_web::{get, post, web};
 use common::api::WrappingResponse;
-use common_local::{api, util::parse_num_description_string, CollectionId, DisplayItem};
+use common_local::{
+    api, edit::EditStatus, util::parse_num_description_string, CollectionId, DisplayItem,
+};
 
 use crate::{
     http::{JsonResponse, MemberCookie},
-    model::{CollectionModel, NewCollectionModel},
-    WebResult,
+    model::{CollectionModel, NewCollectionModel, NewEditModel, NewEditVoteModel},
+    InternalError, WebResult,
 };
 
 #[get("/collection/{id}")]
@@ -25,16 +27,18 @@ async fn get_collection_by_id(
     )))
 }
 
+/// Returns `true` if the collection was updated or `false` if an edit was created.
 #[post("/collection/{id}")]
 async fn update_collection_by_id(
     coll_id: web::Path<String>,
     body: web::Json<api::UpdateCollectionModel>,
     member: MemberCookie,
     db: web::Data<tokio_postgres::Client>,
-) -> WebResult<JsonResponse<&'static str>> {
+) -> WebResult<JsonResponse<bool>> {
     let coll_id =
         parse_num_description_string::<CollectionId>(&coll_id).map_err(crate::Error::from)?;
 
+    let server_link_id = member.server_link_id();
     let member = member.fetch_or_error(&db).await?;
 
     if !member.permissions.has_editing_perms() {
@@ -43,9 +47,35 @@ async fn update_collection_by_id(
         )));
     }
 
-    CollectionModel::update_by_id(coll_id, body.into_inner(), &db).await?;
+    let current = CollectionModel::find_by_id(coll_id, &db)
+        .await?
+        .ok_or_else(|| crate::Error::from(InternalError::ItemMissing))?;
+
+    let mut model =
+        NewEditModel::from_collection_modify(member.id, current, body.into_inner()).await?;
+
+    model.server_link_id = server_link_id;
+
+    // Admins update instantly. Everyone else has to go through voting.
+    if member.permissions.is_admin() {
+        let mut model = model.insert(&db).await?;
+
+        model
+            .process_status_change(EditStatus::ForceAccepted, &db)
+            .await?;
 
-    Ok(web::Json(WrappingResponse::okay("ok")))
+        Ok(web::Json(WrappingResponse::okay(true)))
+    } else {
+        model.vote_count += 1;
+
+        let model = model.insert(&db).await?;
+
+        NewEditVoteModel::create(model.id, member.id, true)
+            .insert(&db)
+            .await?;
+
+        Ok(web::Json(WrappingResponse::okay(false)))
+    }
 }
 
 #[get("/collection/{id}/books")]
@@ -1,13 +1,33 @@
 use std::ops::Neg;
 
-use actix_web::{get, post, web};
-use common::api::{QueryListResponse, WrappingResponse};
-use common_local::{api, item::edit::*, EditId};
+use actix_web::{delete, get, post, web, HttpRequest};
+use common::{
+    api::{QueryListResponse, WrappingResponse},
+    MemberId,
+};
+use common_local::{
+    api,
+    api_token::ApiTokenScopes,
+    audit::AuditAction,
+    edit::{EditOperation, EditStatus, EditType},
+    item::edit::*,
+    ws::WebsocketNotification,
+    EditCommentId, EditId,
+};
+use serde_json::json;
+use serde_qs::actix::QsQuery;
 
 use crate::{
-    http::{JsonResponse, MemberCookie},
-    model::{BookModel, EditModel, EditVoteModel, MemberModel, NewEditVoteModel},
-    Error, InternalError, WebResult,
+    config::get_config,
+    edit_diff::create_edit_diff,
+    http::{get_ip_address, send_notification, JsonResponse, MemberCookie},
+    model::{
+        find_book_conflicts, find_person_conflicts, notify_edit_comment, revert_book_edit_data,
+        revert_person_edit_data, timestamp_to_date, BookModel, CollectionModel, EditCommentModel,
+        EditModel, EditVoteModel, MemberModel, NewAuditLogModel, NewEditCommentModel, NewEditModel,
+        NewEditVoteModel, PersonModel, TagModel, SYSTEM_MEMBER_ID,
+    },
+    Error, InternalError, WebError, WebResult,
 };
 
 // Get List Of Edits
@@ -15,17 +35,27 @@ use crate::{
 pub async fn load_edit_list(
     db: web::Data<tokio_postgres::Client>,
     this_member: Option<MemberCookie>,
-    query: web::Query<api::SimpleListQuery>,
+    query: QsQuery<api::EditListQuery>,
 ) -> WebResult<JsonResponse<api::GetEditListResponse>> {
+    if [query.created_after, query.created_before]
+        .into_iter()
+        .flatten()
+        .any(|secs| timestamp_to_date(secs).is_none())
+    {
+        return Err(WebError::BadRequest("Invalid date range"));
+    }
+
     let offset = query.offset.unwrap_or(0);
-    let limit = query.offset.unwrap_or(25);
+    let limit = query.limit.unwrap_or(25);
+
+    let this_member_id = this_member.as_ref().map(|v| v.member_id());
 
     let mut existing_books: Vec<BookModel> = Vec::new();
     let mut existing_members: Vec<MemberModel> = Vec::new();
 
     let mut items = Vec::new();
 
-    for item in EditModel::find_by_status(offset, limit, None, None, &db).await? {
+    for item in EditModel::find_by_filter(&query, this_member_id, &db).await? {
         let member = if let Some(v) = existing_members
             .iter()
             .find(|v| v.id == item.member_id)
@@ -41,6 +71,8 @@ pub async fn load_edit_list(
 
         let mut item = item.into_shared_edit(member)?;
 
+        item.diff = Some(create_edit_diff(&item.data, &db).await?);
+
         let my_vote = if let Some(this_member) = this_member.as_ref() {
             // If we've voted, return our vote in there.
             EditVoteModel::find_one(item.id, this_member.member_id(), &db)
@@ -61,9 +93,9 @@ pub async fn load_edit_list(
 
         // Only add `current` field if status is pending.
         if item.status.is_pending() {
-            // Attempt to get the Book Model ID we're editing.
-            if let Some(ModelIdGroup::Book(book_id)) = item.get_model_id() {
-                if let EditData::Book(book_data) = &mut item.data {
+            match (item.get_model_id(), &mut item.data) {
+                // Attempt to get the Book Model ID we're editing.
+                (Some(ModelIdGroup::Book(book_id)), EditData::Book(book_data)) => {
                     // If we've already queried the database for this book id, clone it.
                     if let Some(book_model) =
                         existing_books.iter().find(|v| v.id == book_id).cloned()
@@ -75,7 +107,30 @@ pub async fn load_edit_list(
                         existing_books.push(book_model.clone());
                         book_data.current = Some(book_model.into());
                     }
+
+                    if let Some(book_model) = existing_books.iter().find(|v| v.id == book_id) {
+                        book_data.conflicts = find_book_conflicts(book_model, book_data);
+                    }
+                }
+
+                (Some(ModelIdGroup::Person(person_id)), EditData::Person(person_data)) => {
+                    if let Some(person_model) = PersonModel::get_by_id(person_id, &db).await? {
+                        person_data.conflicts = find_person_conflicts(&person_model, person_data);
+                        person_data.current = Some(person_model.into_public_person(None));
+                    }
+                }
+
+                (Some(ModelIdGroup::Tag(tag_id)), EditData::Tag(tag_data)) => {
+                    tag_data.current = TagModel::get_by_id(tag_id, &db).await?.map(|v| v.into());
+                }
+
+                (Some(ModelIdGroup::Collection(coll_id)), EditData::Collection(coll_data)) => {
+                    coll_data.current = CollectionModel::find_by_id(coll_id, &db)
+                        .await?
+                        .map(|v| v.into());
                 }
+
+                _ => (),
             }
         }
 
@@ -86,7 +141,7 @@ pub async fn load_edit_list(
         api::GetEditListResponse {
             offset,
             limit,
-            total: EditModel::get_count(&db).await?,
+            total: EditModel::count_by_filter(&query, this_member_id, &db).await?,
             items,
         },
     )))
@@ -104,13 +159,17 @@ async fn load_edit(
 
     let member = MemberModel::get_by_id(model.member_id, &db).await?;
 
+    let mut model = model.into_shared_edit(member)?;
+    model.diff = Some(create_edit_diff(&model.data, &db).await?);
+
     Ok(web::Json(WrappingResponse::okay(api::GetEditResponse {
-        model: model.into_shared_edit(member)?,
+        model,
     })))
 }
 
 #[post("/edit/{id}")]
 async fn update_edit(
+    req: HttpRequest,
     edit_id: web::Path<EditId>,
     json: web::Json<UpdateEditModel>,
     member: MemberCookie,
@@ -131,8 +190,7 @@ async fn update_edit(
         }
     };
 
-    // Check that we're pending.
-    // TODO: Add Admin Override.
+    // Check that we're pending. Admins change finished edits through the override.
     if !edit_model.status.is_pending() {
         return Ok(web::Json(WrappingResponse::error(
             "Edit Model is not currently pending!",
@@ -151,7 +209,30 @@ async fn update_edit(
             )));
         }
 
+        // Forcing requires a reason so it has to go through the override.
+        if !matches!(
+            new_status,
+            EditStatus::Accepted | EditStatus::Rejected | EditStatus::Cancelled
+        ) {
+            return Ok(web::Json(WrappingResponse::error(
+                "Use the edit override to force accept or force reject an edit!",
+            )));
+        }
+
+        let previous_status = edit_model.status;
+
         edit_model.process_status_change(new_status, &db).await?;
+
+        NewAuditLogModel::new(
+            member.id,
+            AuditAction::ChangeEditStatus,
+            get_ip_address(&req),
+        )
+        .target(edit_model.id)
+        .before(&json!({ "status": previous_status }))?
+        .after(&json!({ "status": edit_model.status }))?
+        .insert(&db)
+        .await?;
     }
 
     // Has Voting Or Admin Perms.
@@ -178,35 +259,94 @@ async fn update_edit(
             )));
         }
 
-        if let Some(mut vote_model) = EditVoteModel::find_one(*edit_id, member.id, &db).await? {
-            let model_vote_as_num = if vote_model.vote { 1 } else { -1 };
+        let (vote_model, amount) = store_vote(*edit_id, member.id, *vote_amount, &db).await?;
+
+        *vote_amount = amount;
+
+        Some(vote_model)
+    } else {
+        None
+    };
+
+    let new_status = update.status;
+    let vote_change = update.vote;
+
+    EditModel::update_by_id(*edit_id, update, &db).await?;
+
+    if let Some(amount) = vote_change {
+        send_notification(WebsocketNotification::EditVoted {
+            id: edit_model.id,
+            vote_count: edit_model.vote_count as i16 + amount,
+        });
+    }
+
+    // Enough weighted votes can decide the edit before it expires.
+    if vote_model.is_some() && edit_model.status.is_pending() {
+        let policy = get_config().voting;
+
+        let tally = EditVoteModel::tally(edit_model.id, &policy, &db).await?;
+
+        if let Some(new_status) = tally.decide(&policy, false) {
+            edit_model.process_status_change(new_status, &db).await?;
+        }
+    }
 
-            // Remove Vote.
-            if model_vote_as_num == *vote_amount {
-                EditVoteModel::remove(*edit_id, member.id, &db).await?;
+    // Edits from the same mass edit are decided on together.
+    if let Some(batch_id) = edit_model.batch_id {
+        let vote = vote_model.as_ref().map(|v| if v.vote { 1 } else { -1 });
 
-                // Opposite vote_amount value
-                *vote_amount = vote_amount.neg();
+        for mut other in EditModel::find_by_batch_id(batch_id, &db).await? {
+            if other.id == edit_model.id || !other.status.is_pending() {
+                continue;
             }
-            // Double the value since we're switching ie: Total Votes = 10, Going from true -> false which means we have to go minus 2 votes.
-            else {
-                vote_model.vote = *vote_amount == 1;
-                vote_model.update(&db).await?;
 
-                *vote_amount *= 2;
+            if let Some(new_status) = new_status {
+                let previous_status = other.status;
+
+                other.process_status_change(new_status, &db).await?;
+
+                NewAuditLogModel::new(
+                    member.id,
+                    AuditAction::ChangeEditStatus,
+                    get_ip_address(&req),
+                )
+                .target(other.id)
+                .before(&json!({ "status": previous_status }))?
+                .after(&json!({ "status": other.status }))?
+                .insert(&db)
+                .await?;
+
+                continue;
             }
 
-            Some(vote_model)
-        } else {
-            let vote_model = NewEditVoteModel::create(*edit_id, member.id, *vote_amount == 1);
+            if let Some(vote) = vote {
+                let (_, amount) = store_vote(other.id, member.id, vote, &db).await?;
 
-            Some(vote_model.insert(&db).await?)
-        }
-    } else {
-        None
-    };
+                EditModel::update_by_id(
+                    other.id,
+                    UpdateEditModel {
+                        vote: Some(amount),
+                        ..UpdateEditModel::default()
+                    },
+                    &db,
+                )
+                .await?;
 
-    EditModel::update_by_id(*edit_id, update, &db).await?;
+                send_notification(WebsocketNotification::EditVoted {
+                    id: other.id,
+                    vote_count: other.vote_count as i16 + amount,
+                });
+
+                let policy = get_config().voting;
+
+                let tally = EditVoteModel::tally(other.id, &policy, &db).await?;
+
+                if let Some(new_status) = tally.decide(&policy, false) {
+                    other.process_status_change(new_status, &db).await?;
+                }
+            }
+        }
+    }
 
     Ok(web::Json(WrappingResponse::okay(api::PostEditResponse {
         edit_model: Some(edit_model.into_shared_edit(Some(member))?),
@@ -222,3 +362,460 @@ async fn update_edit(
         }),
     })))
 }
+
+/// Stores a members' vote (1 or -1) on an edit. Voting the same way twice removes the vote.
+///
+/// Returns the vote and the amount the edits' vote count changes by.
+async fn store_vote(
+    edit_id: EditId,
+    member_id: MemberId,
+    vote_amount: i16,
+    db: &tokio_postgres::Client,
+) -> crate::Result<(EditVoteModel, i16)> {
+    if let Some(mut vote_model) = EditVoteModel::find_one(edit_id, member_id, db).await? {
+        let model_vote_as_num = if vote_model.vote { 1 } else { -1 };
+
+        // Remove Vote.
+        if model_vote_as_num == vote_amount {
+            EditVoteModel::remove(edit_id, member_id, db).await?;
+
+            // Opposite vote_amount value
+            Ok((vote_model, vote_amount.neg()))
+        }
+        // Double the value since we're switching ie: Total Votes = 10, Going from true -> false which means we have to go minus 2 votes.
+        else {
+            vote_model.vote = vote_amount == 1;
+            vote_model.update(db).await?;
+
+            Ok((vote_model, vote_amount * 2))
+        }
+    } else {
+        let vote_model = NewEditVoteModel::create(edit_id, member_id, vote_amount == 1);
+
+        Ok((vote_model.insert(db).await?, vote_amount))
+    }
+}
+
+/// Creates a new edit which restores the values from before an accepted edit.
+///
+/// Returns `true` if it was applied instantly or `false` if it was submitted for review.
+#[post("/edit/{id}/revert")]
+async fn revert_edit(
+    edit_id: web::Path<EditId>,
+    member: MemberCookie,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<bool>> {
+    let server_link_id = member.server_link_id();
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.has_editing_perms() {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot do this! No Permissions!",
+        )));
+    }
+
+    let edit_model = match EditModel::get_by_id(*edit_id, &db).await? {
+        Some(value) => value,
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Unable to find Edit Model.",
+            )))
+        }
+    };
+
+    if !edit_model.status.is_accepted() || edit_model.operation != EditOperation::Modify {
+        return Ok(web::Json(WrappingResponse::error(
+            "Only accepted modifications can be reverted!",
+        )));
+    }
+
+    let mut new_edit = match (edit_model.parse_data()?, edit_model.get_model_id()) {
+        (EditData::Book(data), Some(ModelIdGroup::Book(book_id))) => {
+            match (
+                revert_book_edit_data(&data),
+                BookModel::get_by_id(book_id, &db).await?,
+            ) {
+                (Some(revert), Some(book_model)) => {
+                    NewEditModel::from_book_modify(member.id, book_model, revert, &db).await?
+                }
+
+                _ => {
+                    return Ok(web::Json(WrappingResponse::error(
+                        "There's nothing to revert!",
+                    )))
+                }
+            }
+        }
+
+        (EditData::Person(data), Some(ModelIdGroup::Person(person_id))) => {
+            match (
+                revert_person_edit_data(&data),
+                PersonModel::get_by_id(person_id, &db).await?,
+            ) {
+                (Some(revert), Some(person_model)) => {
+                    NewEditModel::from_person_modify(member.id, person_model, revert).await?
+                }
+
+                _ => {
+                    return Ok(web::Json(WrappingResponse::error(
+                        "There's nothing to revert!",
+                    )))
+                }
+            }
+        }
+
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Only Book and Person edits can be reverted!",
+            )))
+        }
+    };
+
+    new_edit.server_link_id = server_link_id;
+
+    // Admins revert instantly. Everyone else has to go through voting.
+    if member.permissions.is_admin() {
+        let mut model = new_edit.insert(&db).await?;
+
+        model
+            .process_status_change(EditStatus::ForceAccepted, &db)
+            .await?;
+
+        Ok(web::Json(WrappingResponse::okay(true)))
+    } else {
+        new_edit.vote_count += 1;
+
+        let model = new_edit.insert(&db).await?;
+
+        NewEditVoteModel::create(model.id, member.id, true)
+            .insert(&db)
+            .await?;
+
+        Ok(web::Json(WrappingResponse::okay(false)))
+    }
+}
+
+/// Accepted edits of a model, oldest first. Used for a models' history.
+pub async fn load_model_history(
+    type_of: EditType,
+    model_id: usize,
+    query: &api::SimpleListQuery,
+    db: &tokio_postgres::Client,
+) -> crate::Result<api::GetEditListResponse> {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(25);
+
+    let mut existing_members: Vec<MemberModel> = Vec::new();
+
+    let mut items = Vec::new();
+
+    for item in EditModel::find_accepted_by_model(type_of, model_id, offset, limit, db).await? {
+        let member = if let Some(v) = existing_members
+            .iter()
+            .find(|v| v.id == item.member_id)
+            .cloned()
+        {
+            Some(v)
+        } else if let Some(v) = MemberModel::get_by_id(item.member_id, db).await? {
+            existing_members.push(v.clone());
+            Some(v)
+        } else {
+            None
+        };
+
+        let mut item = item.into_shared_edit(member)?;
+
+        item.diff = Some(create_edit_diff(&item.data, db).await?);
+
+        items.push(item);
+    }
+
+    Ok(api::GetEditListResponse {
+        offset,
+        limit,
+        total: EditModel::count_accepted_by_model(type_of, model_id, db).await?,
+        items,
+    })
+}
+
+/// Refreshes a pending edit against the current model so it no longer conflicts.
+#[post("/edit/{id}/rebase")]
+async fn rebase_edit(
+    edit_id: web::Path<EditId>,
+    member: MemberCookie,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<api::PostEditResponse>> {
+    member.require_scope(ApiTokenScopes::EDIT)?;
+
+    let member = member.fetch_or_error(&db).await?;
+
+    let mut edit_model = match EditModel::get_by_id(*edit_id, &db).await? {
+        Some(value) => value,
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Unable to find Edit Model.",
+            )))
+        }
+    };
+
+    // Only the author or an Admin can rebase.
+    if edit_model.member_id != member.id && !member.permissions.is_admin() {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot do this! No Permissions!",
+        )));
+    }
+
+    if !edit_model.status.is_pending() {
+        return Ok(web::Json(WrappingResponse::error(
+            "Edit Model is not currently pending!",
+        )));
+    }
+
+    if edit_model.operation != EditOperation::Modify {
+        return Ok(web::Json(WrappingResponse::error(
+            "Only modifications can be rebased!",
+        )));
+    }
+
+    edit_model.rebase(&db).await?;
+
+    let edit_member = MemberModel::get_by_id(edit_model.member_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(api::PostEditResponse {
+        edit_model: Some(edit_model.into_shared_edit(edit_member)?),
+        vote: None,
+    })))
+}
+
+/// Lets an Admin re-open, force-accept or force-reject an edit regardless of its current status.
+#[post("/edit/{id}/override")]
+async fn override_edit(
+    req: HttpRequest,
+    edit_id: web::Path<EditId>,
+    body: web::Json<api::OverrideEditBody>,
+    member: MemberCookie,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<api::PostEditResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot do this! Not Admin!",
+        )));
+    }
+
+    let api::OverrideEditBody { status, reason } = body.into_inner();
+
+    let reason = reason.trim();
+
+    if reason.is_empty() {
+        return Ok(web::Json(WrappingResponse::error("A reason is required!")));
+    }
+
+    let mut edit_model = match EditModel::get_by_id(*edit_id, &db).await? {
+        Some(value) => value,
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Unable to find Edit Model.",
+            )))
+        }
+    };
+
+    // Accepted edits have already changed the model. Undoing them is done by reverting.
+    if edit_model.status.is_accepted() {
+        return Ok(web::Json(WrappingResponse::error(
+            "Edit Model was already accepted! Revert it instead.",
+        )));
+    }
+
+    let previous_status = edit_model.status;
+
+    let (action, audit_action) = match status {
+        EditStatus::Pending => {
+            if edit_model.status.is_pending() {
+                return Ok(web::Json(WrappingResponse::error(
+                    "Edit Model is already pending!",
+                )));
+            }
+
+            edit_model.reopen(&db).await?;
+
+            ("Reopened", AuditAction::ReopenEdit)
+        }
+
+        // Applied against the current model so conflicting values are skipped.
+        EditStatus::ForceAccepted => {
+            edit_model.process_status_change(status, &db).await?;
+
+            ("Force accepted", AuditAction::ForceAcceptEdit)
+        }
+
+        EditStatus::ForceRejected => {
+            edit_model.process_status_change(status, &db).await?;
+
+            ("Force rejected", AuditAction::ForceRejectEdit)
+        }
+
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Edits can only be reopened, force accepted or force rejected!",
+            )))
+        }
+    };
+
+    NewEditCommentModel::new(
+        edit_model.id,
+        *SYSTEM_MEMBER_ID,
+        format!("SYSTEM: {action} by {}. Reason: {reason}", member.name),
+    )
+    .insert(&db)
+    .await?;
+
+    NewAuditLogModel::new(member.id, audit_action, get_ip_address(&req))
+        .target(edit_model.id)
+        .before(&json!({ "status": previous_status }))?
+        .after(&json!({ "status": edit_model.status, "reason": reason }))?
+        .insert(&db)
+        .await?;
+
+    let edit_member = MemberModel::get_by_id(edit_model.member_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(api::PostEditResponse {
+        edit_model: Some(edit_model.into_shared_edit(edit_member)?),
+        vote: None,
+    })))
+}
+
+// Comments
+
+#[get("/edit/{id}/comments")]
+async fn load_edit_comments(
+    edit_id: web::Path<EditId>,
+    query: web::Query<api::SimpleListQuery>,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<api::GetEditCommentListResponse>> {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(50);
+
+    let mut existing_members: Vec<MemberModel> = Vec::new();
+
+    let mut items = Vec::new();
+
+    for comment in
+        EditCommentModel::get_by_edit_id(*edit_id, offset, limit, Some(false), &db).await?
+    {
+        let member = if let Some(v) = existing_members
+            .iter()
+            .find(|v| v.id == comment.member_id)
+            .cloned()
+        {
+            Some(v)
+        } else if let Some(v) = MemberModel::get_by_id(comment.member_id, &db).await? {
+            existing_members.push(v.clone());
+            Some(v)
+        } else {
+            None
+        };
+
+        items.push(comment.into_shared_comment(member));
+    }
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetEditCommentListResponse {
+            offset,
+            limit,
+            total: EditCommentModel::get_count(*edit_id, Some(false), &db).await?,
+            items,
+        },
+    )))
+}
+
+#[post("/edit/{id}/comments")]
+async fn new_edit_comment(
+    edit_id: web::Path<EditId>,
+    body: web::Json<api::NewEditCommentBody>,
+    member: MemberCookie,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<api::NewEditCommentResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.has_comment_perms() {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot do this! No Permissions!",
+        )));
+    }
+
+    let body = body.into_inner();
+    let text = body.text.trim().to_string();
+
+    if text.is_empty() {
+        return Ok(web::Json(WrappingResponse::error(
+            "Comment cannot be empty!",
+        )));
+    }
+
+    let edit_model = match EditModel::get_by_id(*edit_id, &db).await? {
+        Some(v) => v,
+        None => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Unable to find Edit Model.",
+            )))
+        }
+    };
+
+    let parent = if let Some(parent_id) = body.parent_id {
+        match EditCommentModel::get_by_id(parent_id, &db).await? {
+            Some(v) if v.edit_id == *edit_id && !v.deleted => Some(v),
+            _ => {
+                return Ok(web::Json(WrappingResponse::error(
+                    "Unable to find the comment being replied to.",
+                )))
+            }
+        }
+    } else {
+        None
+    };
+
+    let comment = NewEditCommentModel::new(*edit_id, member.id, text, body.parent_id)
+        .insert(&db)
+        .await?;
+
+    notify_edit_comment(&edit_model, &comment, parent.as_ref(), &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        comment.into_shared_comment(Some(member)),
+    )))
+}
+
+#[delete("/edit/{id}/comments/{comment_id}")]
+async fn delete_edit_comment(
+    path: web::Path<(EditId, EditCommentId)>,
+    member: MemberCookie,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    let (edit_id, comment_id) = path.into_inner();
+
+    let member = member.fetch_or_error(&db).await?;
+
+    let comment = match EditCommentModel::get_by_id(comment_id, &db).await? {
+        Some(v) if v.edit_id == edit_id && !v.deleted => v,
+        _ => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Unable to find Edit Comment.",
+            )))
+        }
+    };
+
+    // Only the author (if they can still comment) or an Admin can remove a comment.
+    if !member.permissions.is_admin()
+        && (comment.member_id != member.id || !member.permissions.has_comment_perms())
+    {
+        return Ok(web::Json(WrappingResponse::error(
+            "You cannot do this! No Permissions!",
+        )));
+    }
+
+    EditCommentModel::soft_delete_by_id(comment.id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("success")))
+}
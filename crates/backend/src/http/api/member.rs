@@ -1,10 +1,25 @@
-use actix_web::{get, web};
-use common::api::{QueryListResponse, WrappingResponse};
-use common_local::{api, Member};
+use actix_session::Session;
+use actix_web::{delete, get, post, web, HttpRequest};
+use common::{
+    api::{ApiErrorResponse, QueryListResponse, WrappingResponse},
+    MemberId,
+};
+use common_local::{api, audit::AuditAction, Member, Permissions};
+use serde_json::json;
+use tokio_postgres::Client;
 
 use crate::{
-    http::{JsonResponse, MemberCookie},
-    model::MemberModel,
+    http::{
+        account::{get_base_url, send_verification_email},
+        get_ip_address,
+        password::{hash_password, validate_password, verify_password},
+        session::get_login_id,
+        JsonResponse, MemberCookie,
+    },
+    model::{
+        ApiTokenModel, MemberModel, MemberSessionModel, NewAuditLogModel, ServerLinkGrantModel,
+        SYSTEM_MEMBER_ID,
+    },
     WebResult,
 };
 
@@ -31,7 +46,7 @@ pub async fn get_members(
 ) -> WebResult<JsonResponse<QueryListResponse<Member>>> {
     let member = member.fetch_or_error(&db).await?;
 
-    if !member.permissions.is_admin() {
+    if !member.permissions.has_manage_members_perms() {
         return Ok(web::Json(WrappingResponse::error("Not Admin")));
     }
 
@@ -52,3 +67,402 @@ pub async fn get_members(
         items,
     })))
 }
+
+#[post("/member/{id}")]
+pub async fn update_member(
+    req: HttpRequest,
+    member_id: web::Path<MemberId>,
+    body: web::Json<api::UpdateMemberBody>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<Member>> {
+    let member = member.fetch_or_error(&db).await?;
+    let mut managed = get_managed_member(*member_id, &member, &db).await?;
+
+    let before = json!({ "name": managed.name, "permissions": managed.permissions });
+
+    let api::UpdateMemberBody { name, permissions } = body.into_inner();
+
+    if let Some(name) = name {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(ApiErrorResponse::new("Name cannot be empty").into());
+        }
+
+        managed.name = name.to_string();
+    }
+
+    if let Some(permissions) = permissions {
+        if !member.permissions.is_admin() {
+            if permissions.is_admin() {
+                return Err(ApiErrorResponse::new("Only Admins can grant Admin").into());
+            }
+
+            let added = Permissions {
+                group: permissions.group - managed.permissions.group,
+                specific: permissions.specific - managed.permissions.specific,
+            };
+
+            if !member.permissions.contains_all(added) {
+                return Err(
+                    ApiErrorResponse::new("You can only grant permissions you have").into(),
+                );
+            }
+        }
+
+        managed.permissions = permissions;
+    }
+
+    managed.update(&db).await?;
+
+    NewAuditLogModel::new(member.id, AuditAction::UpdateMember, get_ip_address(&req))
+        .target(managed.id)
+        .before(&before)?
+        .after(&json!({ "name": managed.name, "permissions": managed.permissions }))?
+        .insert(&db)
+        .await?;
+
+    Ok(web::Json(WrappingResponse::okay(managed.into())))
+}
+
+#[post("/member/{id}/suspend")]
+pub async fn suspend_member(
+    req: HttpRequest,
+    member_id: web::Path<MemberId>,
+    body: web::Json<api::SuspendMemberBody>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<Member>> {
+    let member = member.fetch_or_error(&db).await?;
+    let mut managed = get_managed_member(*member_id, &member, &db).await?;
+
+    let api::SuspendMemberBody { reason, until } = body.into_inner();
+
+    let reason = reason.trim();
+
+    if reason.is_empty() {
+        return Err(ApiErrorResponse::new("A reason is required").into());
+    }
+
+    let before = json!({
+        "suspension_reason": managed.suspension_reason,
+        "suspended_until": managed.suspended_until,
+    });
+
+    managed.suspension_reason = Some(reason.to_string());
+    managed.suspended_until = until;
+
+    managed.update(&db).await?;
+
+    NewAuditLogModel::new(member.id, AuditAction::SuspendMember, get_ip_address(&req))
+        .target(managed.id)
+        .before(&before)?
+        .after(&json!({
+            "suspension_reason": managed.suspension_reason,
+            "suspended_until": managed.suspended_until,
+        }))?
+        .insert(&db)
+        .await?;
+
+    // Not every endpoint checks for suspensions so their logins are removed.
+    MemberSessionModel::remove_by_member_id(managed.id, &db).await?;
+    ApiTokenModel::remove_by_member_id(managed.id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(managed.into())))
+}
+
+#[delete("/member/{id}/suspend")]
+pub async fn unsuspend_member(
+    req: HttpRequest,
+    member_id: web::Path<MemberId>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<Member>> {
+    let member = member.fetch_or_error(&db).await?;
+    let mut managed = get_managed_member(*member_id, &member, &db).await?;
+
+    let before = json!({
+        "suspension_reason": managed.suspension_reason,
+        "suspended_until": managed.suspended_until,
+    });
+
+    managed.suspension_reason = None;
+    managed.suspended_until = None;
+
+    managed.update(&db).await?;
+
+    NewAuditLogModel::new(
+        member.id,
+        AuditAction::UnsuspendMember,
+        get_ip_address(&req),
+    )
+    .target(managed.id)
+    .before(&before)?
+    .insert(&db)
+    .await?;
+
+    Ok(web::Json(WrappingResponse::okay(managed.into())))
+}
+
+#[delete("/member/{id}")]
+pub async fn delete_member(
+    req: HttpRequest,
+    member_id: web::Path<MemberId>,
+    query: web::Query<api::DeleteMemberQuery>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = member.fetch_or_error(&db).await?;
+    let mut managed = get_managed_member(*member_id, &member, &db).await?;
+
+    let managed_id = managed.id;
+    let before = json!({
+        "name": managed.name,
+        "email": managed.email,
+        "permissions": managed.permissions,
+    });
+
+    if query.anonymize {
+        managed.anonymize(&db).await?;
+
+        MemberSessionModel::remove_by_member_id(managed.id, &db).await?;
+        ApiTokenModel::remove_by_member_id(managed.id, &db).await?;
+    } else {
+        managed.delete_and_reattribute(&db).await?;
+    }
+
+    NewAuditLogModel::new(member.id, AuditAction::DeleteMember, get_ip_address(&req))
+        .target(managed_id)
+        .before(&before)?
+        .after(&json!({ "anonymize": query.anonymize }))?
+        .insert(&db)
+        .await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+// Account
+
+#[post("/account/name")]
+pub async fn change_account_name(
+    body: web::Json<api::ChangeNameBody>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<Member>> {
+    let mut member = fetch_session_member(member, &db).await?;
+
+    let name = body.name.trim();
+
+    if name.is_empty() {
+        return Err(ApiErrorResponse::new("Name cannot be empty").into());
+    }
+
+    member.name = name.to_string();
+    member.update(&db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(member.into())))
+}
+
+/// The email is only changed once the verification link sent to it is opened.
+#[post("/account/email")]
+pub async fn change_account_email(
+    body: web::Json<api::ChangeEmailBody>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let api::ChangeEmailBody { email, password } = body.into_inner();
+
+    if member.password.is_some() && !verify_password(&password.unwrap_or_default(), &member)? {
+        return Err(ApiErrorResponse::new("Invalid password").into());
+    }
+
+    let email = email.trim();
+
+    if !email.contains('@') {
+        return Err(ApiErrorResponse::new("Invalid email").into());
+    }
+
+    if MemberModel::get_by_email(email, &db).await?.is_some() {
+        return Err(ApiErrorResponse::new("An account with this email already exists").into());
+    }
+
+    let base_url = match get_base_url() {
+        Some(v) => v,
+        None => return Err(ApiErrorResponse::new("Missing server base_url from config").into()),
+    };
+
+    send_verification_email(member.id, email, &base_url, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+#[post("/account/password")]
+pub async fn change_account_password(
+    body: web::Json<api::ChangePasswordBody>,
+    session: Session,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<&'static str>> {
+    let mut member = fetch_session_member(member, &db).await?;
+
+    let api::ChangePasswordBody {
+        current_password,
+        new_password,
+    } = body.into_inner();
+
+    if member.password.is_some()
+        && !verify_password(&current_password.unwrap_or_default(), &member)?
+    {
+        return Err(ApiErrorResponse::new("Invalid password").into());
+    }
+
+    validate_password(&new_password)?;
+
+    member.password = Some(hash_password(&new_password)?);
+    member.update(&db).await?;
+
+    // Log out everywhere else in case the old password was compromised.
+    MemberSessionModel::remove_others_by_member_id(
+        member.id,
+        get_login_id(&session)?.as_deref(),
+        &db,
+    )
+    .await?;
+    ApiTokenModel::remove_by_member_id(member.id, &db).await?;
+    ServerLinkGrantModel::remove_by_member_id(member.id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+#[post("/account/verify")]
+pub async fn resend_account_verification(
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let email = match member.email.as_deref() {
+        Some(v) if member.email_verified_at.is_none() => v,
+        _ => return Err(ApiErrorResponse::new("Email is already verified").into()),
+    };
+
+    let base_url = match get_base_url() {
+        Some(v) => v,
+        None => return Err(ApiErrorResponse::new("Missing server base_url from config").into()),
+    };
+
+    send_verification_email(member.id, email, &base_url, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+#[get("/account/sessions")]
+pub async fn get_account_sessions(
+    session: Session,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<api::GetSessionsResponse>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let login_id = get_login_id(&session)?;
+
+    let items = MemberSessionModel::find_by_member_id(member.id, &db)
+        .await?
+        .into_iter()
+        .map(|v| api::MemberSession {
+            id: v.id,
+            is_current: v.login_id.is_some() && v.login_id == login_id,
+            user_agent: v.user_agent,
+            ip_address: v.ip_address,
+            created_at: v.created_at,
+            last_seen_at: v.last_seen_at,
+        })
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetSessionsResponse { items },
+    )))
+}
+
+#[delete("/account/session/{id}")]
+pub async fn revoke_account_session(
+    session_id: web::Path<usize>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    MemberSessionModel::remove_by_id(member.id, *session_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+/// Logs the member out everywhere.
+#[delete("/member/{id}/sessions")]
+pub async fn revoke_member_sessions(
+    member_id: web::Path<MemberId>,
+    db: web::Data<tokio_postgres::Client>,
+    member: MemberCookie,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Not Admin").into());
+    }
+
+    let managed = get_managed_member(*member_id, &member, &db).await?;
+
+    MemberSessionModel::remove_by_member_id(managed.id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+/// Account changes aren't allowed through API tokens.
+///
+/// The member is loaded again since API tokens restrict the permissions of the fetched one.
+pub(super) async fn fetch_session_member(
+    member: MemberCookie,
+    db: &Client,
+) -> WebResult<MemberModel> {
+    if member.is_api_token() {
+        return Err(ApiErrorResponse::new("Account changes require a session").into());
+    }
+
+    Ok(member.fetch_or_error(db).await?)
+}
+
+/// Loads the member which is about to be managed by `member`.
+///
+/// The System Member, the Deleted Member placeholder and yourself cannot be managed.
+/// Only Admins can manage Admins.
+async fn get_managed_member(
+    member_id: MemberId,
+    member: &MemberModel,
+    db: &Client,
+) -> WebResult<MemberModel> {
+    if !member.permissions.has_manage_members_perms() {
+        return Err(ApiErrorResponse::new("Not Admin").into());
+    }
+
+    if member_id == *SYSTEM_MEMBER_ID || member_id == member.id {
+        return Err(ApiErrorResponse::new("Unable to manage this member").into());
+    }
+
+    let managed = match MemberModel::get_by_id(member_id, db).await? {
+        Some(v) => v,
+        None => return Err(ApiErrorResponse::new("Unable to find member").into()),
+    };
+
+    if managed.is_deleted_placeholder() {
+        return Err(ApiErrorResponse::new("Unable to manage this member").into());
+    }
+
+    if managed.permissions.is_admin() && !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Only Admins can manage Admins").into());
+    }
+
+    Ok(managed)
+}
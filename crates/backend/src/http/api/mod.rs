@@ -3,17 +3,24 @@ use actix_web::{
     web, HttpResponse, Scope,
 };
 
+pub mod api_token;
+pub mod audit;
 pub mod book;
 pub mod collection;
 pub mod edit;
 pub mod external;
 pub mod member;
+pub mod notification;
 pub mod person;
 pub mod poster;
 pub mod publisher;
 pub mod search;
+pub mod server_link;
+pub mod server_link_grant;
 pub mod settings;
 pub mod tag;
+pub mod task;
+pub mod ws;
 
 pub fn api_route() -> Scope<
     impl ServiceFactory<
@@ -31,7 +38,12 @@ pub fn api_route() -> Scope<
         .service(book::get_book_info)
         .service(book::update_book_id)
         .service(book::load_book_thumbnail)
+        .service(book::merge_book_id)
         .service(book::delete_book_id)
+        .service(book::load_deleted_book_list)
+        .service(book::restore_book_id)
+        .service(book::purge_book_id)
+        .service(book::load_book_history)
         // Collection
         .service(collection::get_collection_list)
         .service(collection::get_collection_by_id)
@@ -50,12 +62,24 @@ pub fn api_route() -> Scope<
         // Member
         .service(member::load_member_self)
         .service(member::get_members)
+        .service(member::update_member)
+        .service(member::suspend_member)
+        .service(member::unsuspend_member)
+        .service(member::delete_member)
+        .service(member::change_account_name)
+        .service(member::change_account_email)
+        .service(member::change_account_password)
+        .service(member::resend_account_verification)
+        .service(member::get_account_sessions)
+        .service(member::revoke_account_session)
+        .service(member::revoke_member_sessions)
         // Person
         .service(person::load_author_list)
         .service(person::load_person)
         .service(person::load_person_thumbnail)
         .service(person::add_new_person)
         .service(person::update_person_data)
+        .service(person::load_person_history)
         // Poster
         .service(poster::get_local_image)
         .service(poster::get_poster_list)
@@ -65,6 +89,12 @@ pub fn api_route() -> Scope<
         .service(edit::load_edit_list)
         .service(edit::load_edit)
         .service(edit::update_edit)
+        .service(edit::rebase_edit)
+        .service(edit::override_edit)
+        .service(edit::revert_edit)
+        .service(edit::load_edit_comments)
+        .service(edit::new_edit_comment)
+        .service(edit::delete_edit_comment)
         // Searches
         .service(search::get_searches)
         .service(search::update_search_id)
@@ -74,6 +104,38 @@ pub fn api_route() -> Scope<
         // Settings
         .service(settings::get_settings)
         .service(settings::update_settings)
+        // Tasks
+        .service(task::get_tasks)
+        .service(task::get_task_runs)
+        .service(task::run_task)
+        .service(task::cancel_task)
+        // Watches
+        .service(notification::get_watches)
+        .service(notification::add_watch)
+        .service(notification::remove_watch)
+        // Notifications
+        .service(notification::get_notifications)
+        .service(notification::mark_notifications)
+        // API Tokens
+        .service(api_token::get_api_tokens)
+        .service(api_token::create_api_token)
+        .service(api_token::revoke_api_token)
+        // Audit Log
+        .service(audit::get_audit_log)
+        // Server Links
+        .service(server_link::get_account_server_links)
+        .service(server_link::get_all_server_links)
+        .service(server_link::update_server_link)
+        .service(server_link::rotate_server_link)
+        .service(server_link::revoke_server_link)
+        .service(server_link::update_server_link_limits)
+        // Server Link Grants
+        .service(server_link_grant::get_server_link_grants)
+        .service(server_link_grant::get_grant_server)
+        .service(server_link_grant::create_server_link_grant)
+        .service(server_link_grant::revoke_server_link_grant)
+        // Websocket
+        .service(ws::ws_index)
         .default_service(web::route().to(default_handler))
 }
 
@@ -0,0 +1,126 @@
+use actix_web::{delete, get, post, web};
+use common::{api::WrappingResponse, BookId, PersonId};
+use common_local::{api, api_token::ApiTokenScopes, edit::EditType, CollectionId};
+use tokio_postgres::Client;
+
+use crate::{
+    http::{JsonResponse, MemberCookie},
+    model::{BookModel, CollectionModel, NotificationModel, PersonModel, WatchModel},
+    WebResult,
+};
+
+// Watches
+
+#[get("/watches")]
+async fn get_watches(
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetWatchesResponse>> {
+    let items = WatchModel::find_by_member_id(member.member_id(), &db)
+        .await?
+        .into_iter()
+        .map(|v| v.into())
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(api::GetWatchesResponse {
+        items,
+    })))
+}
+
+#[post("/watch")]
+async fn add_watch(
+    body: web::Json<api::WatchBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    member.require_scope(ApiTokenScopes::EDIT)?;
+
+    let member = member.fetch_or_error(&db).await?;
+
+    let api::WatchBody { type_of, model_id } = body.into_inner();
+
+    let exists = match type_of {
+        EditType::Book => BookModel::get_by_id(BookId::from(model_id), &db)
+            .await?
+            .is_some(),
+        EditType::Person => PersonModel::get_by_id(PersonId::from(model_id), &db)
+            .await?
+            .is_some(),
+        EditType::Collection => CollectionModel::find_by_id(CollectionId::from(model_id), &db)
+            .await?
+            .is_some(),
+        EditType::Tag => {
+            return Ok(web::Json(WrappingResponse::error(
+                "Tags cannot be watched.",
+            )))
+        }
+    };
+
+    if !exists {
+        return Ok(web::Json(WrappingResponse::error("Unable to find Model.")));
+    }
+
+    WatchModel::new(member.id, type_of, model_id)
+        .insert(&db)
+        .await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+#[delete("/watch")]
+async fn remove_watch(
+    body: web::Json<api::WatchBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    member.require_scope(ApiTokenScopes::EDIT)?;
+
+    WatchModel::remove(member.member_id(), body.type_of, body.model_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+// Notifications
+
+#[get("/notifications")]
+async fn get_notifications(
+    query: web::Query<api::NotificationListQuery>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetNotificationsResponse>> {
+    let member_id = member.member_id();
+
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(25).min(100);
+
+    let items =
+        NotificationModel::find_by_member_id(member_id, query.unread_only, offset, limit, &db)
+            .await?
+            .into_iter()
+            .map(|v| v.into())
+            .collect();
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetNotificationsResponse {
+            offset,
+            limit,
+            total: NotificationModel::count_by_member_id(member_id, query.unread_only, &db).await?,
+            unread: NotificationModel::count_by_member_id(member_id, true, &db).await?,
+            items,
+        },
+    )))
+}
+
+#[post("/notifications/read")]
+async fn mark_notifications(
+    body: web::Json<api::MarkNotificationsBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    member.require_scope(ApiTokenScopes::EDIT)?;
+
+    NotificationModel::update_is_read(member.member_id(), body.ids.as_deref(), body.is_read, &db)
+        .await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
@@ -4,14 +4,20 @@ use common::{
     api::{ApiErrorResponse, WrappingResponse},
     PersonId, Source, ThumbnailStore,
 };
-use common_local::api;
+use common_local::{
+    api,
+    audit::AuditAction,
+    edit::{EditStatus, EditType},
+};
+use serde_json::json;
 use tokio_postgres::Client;
 
 use crate::{
-    http::{JsonResponse, MemberCookie},
+    http::{get_ip_address, JsonResponse, MemberCookie},
     metadata,
     model::{
-        BookModel, BookPersonModel, NewEditModel, NewPersonModel, PersonAltModel, PersonModel,
+        EditModel, NewAuditLogModel, NewEditModel, NewEditVoteModel, NewPersonModel,
+        PersonAltModel, PersonModel,
     },
     storage::get_storage,
     Error, InternalError, WebResult,
@@ -123,10 +129,10 @@ async fn load_person(
     person_id: web::Path<PersonId>,
     db: web::Data<tokio_postgres::Client>,
 ) -> WebResult<JsonResponse<api::GetPersonResponse>> {
-    let person = PersonModel::get_by_id(*person_id, &db)
+    let person = PersonModel::get_by_id_or_redirect(*person_id, &db)
         .await?
         .ok_or_else(|| Error::from(InternalError::ItemMissing))?;
-    let person_alts = PersonAltModel::find_all_by_person_id(*person_id, &db).await?;
+    let person_alts = PersonAltModel::find_all_by_person_id(person.id, &db).await?;
 
     Ok(web::Json(WrappingResponse::okay(api::GetPersonResponse {
         person: person.into_public_person(None),
@@ -134,6 +140,17 @@ async fn load_person(
     })))
 }
 
+#[get("/person/{id}/history")]
+async fn load_person_history(
+    person_id: web::Path<PersonId>,
+    query: web::Query<api::SimpleListQuery>,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<api::GetEditListResponse>> {
+    Ok(web::Json(WrappingResponse::okay(
+        super::edit::load_model_history(EditType::Person, **person_id, &query, &db).await?,
+    )))
+}
+
 // Person Thumbnail
 #[get("/person/{id}/thumbnail")]
 async fn load_person_thumbnail(
@@ -141,7 +158,7 @@ async fn load_person_thumbnail(
     req: HttpRequest,
     db: web::Data<tokio_postgres::Client>,
 ) -> WebResult<HttpResponse> {
-    let meta = PersonModel::get_by_id(*person_id, &db).await?;
+    let meta = PersonModel::get_by_id_or_redirect(*person_id, &db).await?;
 
     if let Some(file_name) = meta.as_ref().and_then(|v| v.thumb_url.as_value()) {
         Ok(get_storage()
@@ -156,6 +173,7 @@ async fn load_person_thumbnail(
 // Person Tasks - Update Person, Overwrite Person with another source.
 #[post("/person/{id}")]
 pub async fn update_person_data(
+    req: HttpRequest,
     person_id: web::Path<PersonId>,
     body: web::Json<api::PostPersonBody>,
     member: MemberCookie,
@@ -163,6 +181,7 @@ pub async fn update_person_data(
 ) -> WebResult<JsonResponse<&'static str>> {
     let person_id = *person_id;
 
+    let server_link_id = member.server_link_id();
     let member = member.fetch_or_error(&db).await?;
 
     if !member.permissions.has_editing_perms() {
@@ -179,20 +198,29 @@ pub async fn update_person_data(
             if let Some((updated_book, current_book)) = Some(person_edit).zip(current_book) {
                 // Make sure we have something we're updating.
                 if !updated_book.is_empty() {
-                    let model =
+                    // Trusted members can skip voting on low-risk changes.
+                    let skip_voting = updated_book.is_low_risk() && member.is_trusted();
+
+                    let mut model =
                         NewEditModel::from_person_modify(member.id, current_book, updated_book)
                             .await?;
 
+                    model.server_link_id = server_link_id;
+
                     if !model.data.is_empty() {
-                        model.insert(&db).await?;
+                        let mut model = model.insert(&db).await?;
+
+                        if skip_voting {
+                            model
+                                .process_status_change(EditStatus::Accepted, &db)
+                                .await?;
+                        }
                     }
                 }
             }
         }
 
         api::PostPersonBody::CombinePersonWith(into_person_id) => {
-            // TODO: Tests for this to ensure it's correct.
-
             if person_id == into_person_id {
                 return Err(
                     ApiErrorResponse::new("You cannot join the same person into itself!").into(),
@@ -202,69 +230,49 @@ pub async fn update_person_data(
             let old_person = PersonModel::get_by_id(person_id, &db)
                 .await?
                 .ok_or_else(|| Error::from(InternalError::ItemMissing))?;
-            let mut into_person = PersonModel::get_by_id(into_person_id, &db)
+            let into_person = PersonModel::get_by_id(into_person_id, &db)
                 .await?
                 .ok_or_else(|| Error::from(InternalError::ItemMissing))?;
 
-            // Attempt to transfer to other person
-            let _ = PersonAltModel::transfer_by_person_id(old_person.id, into_person.id, &db).await;
-
-            // Delete remaining Alt Names
-            PersonAltModel::remove_by_person_id(old_person.id, &db).await?;
-
-            // Make Old Person Name an Alt Name
-            let _ = PersonAltModel {
-                name: old_person.name,
-                person_id: into_person.id,
-            }
-            .insert(&db)
-            .await;
-
-            // Transfer Old Person Book to New Person
-            let trans_book_person_vec =
-                BookPersonModel::find_by_person_id(old_person.id, &db).await?;
-            for met_per in &trans_book_person_vec {
-                let _ = BookPersonModel {
-                    book_id: met_per.book_id,
-                    person_id: into_person.id,
-                    info: None,
-                }
-                .insert(&db)
-                .await;
+            if EditModel::exists_pending_merge(EditType::Person, *person_id, *into_person_id, &db)
+                .await?
+            {
+                return Err(ApiErrorResponse::new(
+                    "A merge involving this person is already pending.",
+                )
+                .into());
             }
 
-            BookPersonModel::remove_by_person_id(old_person.id, &db).await?;
+            let before = json!({ "person": old_person, "into_person": into_person });
 
-            if into_person.birth_date.is_none() {
-                into_person.birth_date = old_person.birth_date;
-            }
+            let mut model = NewEditModel::from_person_merge(member.id, old_person, into_person)?;
 
-            if into_person.description.is_none() {
-                into_person.description = old_person.description;
-            }
+            model.server_link_id = server_link_id;
 
-            if into_person.thumb_url.is_none() {
-                into_person.thumb_url = old_person.thumb_url;
-            }
+            // Admins merge instantly. Everyone else has to go through voting.
+            if member.permissions.is_admin() {
+                let mut model = model.insert(&db).await?;
 
-            into_person.updated_at = Utc::now();
+                model
+                    .process_status_change(EditStatus::ForceAccepted, &db)
+                    .await?;
 
-            // Update New Person
-            into_person.update(&db).await?;
+                let into_person = PersonModel::get_by_id(into_person_id, &db).await?;
 
-            // Delete Old Person
-            PersonModel::remove_by_id(old_person.id, &db).await?;
+                NewAuditLogModel::new(member.id, AuditAction::MergePerson, get_ip_address(&req))
+                    .target(person_id)
+                    .before(&before)?
+                    .after(&json!({ "into_person": into_person }))?
+                    .insert(&db)
+                    .await?;
+            } else {
+                model.vote_count += 1;
 
-            // Update book cache author name cache
-            for met_per in trans_book_person_vec {
-                let person = PersonModel::get_by_id(into_person_id, &db).await?;
-                let book = BookModel::get_by_id(met_per.book_id, &db).await?;
+                let model = model.insert(&db).await?;
 
-                if let Some((person, mut book)) = person.zip(book) {
-                    book.cached.author = Some(person.name);
-                    book.cached.author_id = Some(person.id);
-                    book.update_book(&db).await?;
-                }
+                NewEditVoteModel::create(model.id, member.id, true)
+                    .insert(&db)
+                    .await?;
             }
         }
     }
@@ -1,10 +1,11 @@
-use actix_web::{get, post, web};
+use actix_web::{get, post, web, HttpRequest};
 use common::api::{QueryListResponse, WrappingResponse};
-use common_local::{api, SearchGroup, SearchGroupId};
+use common_local::{api, audit::AuditAction, SearchGroup, SearchGroupId};
+use serde_json::json;
 
 use crate::{
-    http::{JsonResponse, MemberCookie},
-    model::SearchGroupModel,
+    http::{get_ip_address, JsonResponse, MemberCookie},
+    model::{NewAuditLogModel, SearchGroupModel},
     WebResult,
 };
 
@@ -40,6 +41,7 @@ pub async fn get_searches(
 
 #[post("/search/{id}")]
 pub async fn update_search_id(
+    req: HttpRequest,
     id: web::Path<SearchGroupId>,
     body: web::Json<api::PostUpdateSearchIdBody>,
     member: MemberCookie,
@@ -54,7 +56,21 @@ pub async fn update_search_id(
     }
 
     if let Some(value) = body.update_id {
+        let before = SearchGroupModel::find_one_by_id(*id, &db)
+            .await?
+            .and_then(|v| v.found_id)
+            .map(|v| v.to_string());
+
+        let after = value.as_ref().map(|v| v.to_string());
+
         SearchGroupModel::update_found_id(*id, value, &db).await?;
+
+        NewAuditLogModel::new(member.id, AuditAction::UpdateSearch, get_ip_address(&req))
+            .target(*id)
+            .before(&json!({ "found_id": before }))?
+            .after(&json!({ "found_id": after }))?
+            .insert(&db)
+            .await?;
     }
 
     Ok(web::Json(WrappingResponse::okay("ok")))
@@ -0,0 +1,259 @@
+use actix_web::{delete, get, post, web, HttpRequest};
+use chrono::Utc;
+use common::api::{ApiErrorResponse, QueryListResponse, WrappingResponse};
+use common_local::{api, audit::AuditAction, ServerLinkId};
+use serde_json::json;
+use tokio_postgres::Client;
+
+use crate::{
+    http::{external::gen_server_ids, get_ip_address, JsonResponse, MemberCookie},
+    model::{MemberModel, NewAuditLogModel, ServerLinkModel, ServerLinkQuotaModel},
+    WebResult,
+};
+
+use super::member::fetch_session_member;
+
+const MAX_NAME_LENGTH: usize = 32;
+
+#[get("/account/servers")]
+async fn get_account_server_links(
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetServerLinksResponse>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let items = ServerLinkModel::find_by_member_id(member.id, &db)
+        .await?
+        .into_iter()
+        .map(|v| v.into())
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetServerLinksResponse { items },
+    )))
+}
+
+#[get("/admin/servers")]
+async fn get_all_server_links(
+    query: web::Query<api::SimpleListQuery>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetAllServerLinksResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
+    }
+
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(25);
+
+    let mut items = Vec::new();
+
+    for link in ServerLinkModel::find_all(offset, limit, &db).await? {
+        let usage = ServerLinkQuotaModel::find_by_server_link_id(link.id, &db)
+            .await?
+            .map(|v| v.into());
+
+        let mut item = api::SharedServerLink::from(link);
+        item.usage = usage;
+
+        items.push(item);
+    }
+
+    Ok(web::Json(WrappingResponse::okay(QueryListResponse {
+        offset,
+        limit,
+        total: ServerLinkModel::count(&db).await?,
+        items,
+    })))
+}
+
+#[post("/server/{id}")]
+async fn update_server_link(
+    link_id: web::Path<ServerLinkId>,
+    body: web::Json<api::UpdateServerLinkBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::SharedServerLink>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let mut link = get_server_link(*link_id, &member, &db).await?;
+
+    let api::UpdateServerLinkBody {
+        server_owner_name,
+        server_name,
+    } = body.into_inner();
+
+    let server_owner_name = server_owner_name
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let server_name = server_name
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+
+    if [&server_owner_name, &server_name]
+        .into_iter()
+        .flatten()
+        .any(|v| v.chars().count() > MAX_NAME_LENGTH)
+    {
+        return Err(ApiErrorResponse::new("Names can be at most 32 characters").into());
+    }
+
+    link.server_owner_name = server_owner_name;
+    link.server_name = server_name;
+    link.updated_at = Utc::now();
+
+    link.update(&db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(link.into())))
+}
+
+/// The server has to be updated with the new IDs and verify itself again.
+#[post("/server/{id}/rotate")]
+async fn rotate_server_link(
+    req: HttpRequest,
+    link_id: web::Path<ServerLinkId>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::RotateServerLinkResponse>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let mut link = get_server_link(*link_id, &member, &db).await?;
+
+    let before = json!({ "public_id": link.public_id, "verified": link.verified });
+
+    let (server_id, public_id) = gen_server_ids();
+
+    link.server_id = server_id.clone();
+    link.public_id = public_id;
+    link.verified = false;
+    link.updated_at = Utc::now();
+
+    link.update(&db).await?;
+
+    if link.member_id != member.id {
+        NewAuditLogModel::new(
+            member.id,
+            AuditAction::RotateServerLink,
+            get_ip_address(&req),
+        )
+        .target(link.id)
+        .before(&before)?
+        .after(&json!({ "public_id": link.public_id, "verified": link.verified }))?
+        .insert(&db)
+        .await?;
+    }
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::RotateServerLinkResponse {
+            link: link.into(),
+            server_id,
+        },
+    )))
+}
+
+/// The server is unable to search through us afterwards.
+#[delete("/server/{id}")]
+async fn revoke_server_link(
+    req: HttpRequest,
+    link_id: web::Path<ServerLinkId>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let link = get_server_link(*link_id, &member, &db).await?;
+
+    ServerLinkModel::delete_by_id(link.id, &db).await?;
+
+    if link.member_id != member.id {
+        NewAuditLogModel::new(
+            member.id,
+            AuditAction::RevokeServerLink,
+            get_ip_address(&req),
+        )
+        .target(link.id)
+        .before(&api::SharedServerLink::from(link))?
+        .insert(&db)
+        .await?;
+    }
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+/// Overrides the configured rate limits of a single server.
+#[post("/admin/server/{id}/limits")]
+async fn update_server_link_limits(
+    req: HttpRequest,
+    link_id: web::Path<ServerLinkId>,
+    body: web::Json<api::UpdateServerLinkLimitsBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
+    }
+
+    let link = match ServerLinkModel::get_by_id(*link_id, &db).await? {
+        Some(v) => v,
+        None => return Err(ApiErrorResponse::new("Unable to find Server Link").into()),
+    };
+
+    let api::UpdateServerLinkLimitsBody {
+        requests_per_minute,
+        requests_per_day,
+    } = body.into_inner();
+
+    if [requests_per_minute, requests_per_day]
+        .into_iter()
+        .flatten()
+        .any(|v| v < 0)
+    {
+        return Err(ApiErrorResponse::new("Limits cannot be negative").into());
+    }
+
+    let (before_per_minute, before_per_day) =
+        ServerLinkQuotaModel::find_by_server_link_id(link.id, &db)
+            .await?
+            .map(|v| (v.requests_per_minute, v.requests_per_day))
+            .unwrap_or_default();
+
+    let before = json!({
+        "requests_per_minute": before_per_minute,
+        "requests_per_day": before_per_day,
+    });
+
+    ServerLinkQuotaModel::update_limits(link.id, requests_per_minute, requests_per_day, &db)
+        .await?;
+
+    NewAuditLogModel::new(
+        member.id,
+        AuditAction::UpdateServerLinkLimits,
+        get_ip_address(&req),
+    )
+    .target(link.id)
+    .before(&before)?
+    .after(&json!({
+        "requests_per_minute": requests_per_minute,
+        "requests_per_day": requests_per_day,
+    }))?
+    .insert(&db)
+    .await?;
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+/// Admins are able to manage every link. Everyone else only their own.
+async fn get_server_link(
+    link_id: ServerLinkId,
+    member: &MemberModel,
+    db: &Client,
+) -> WebResult<ServerLinkModel> {
+    match ServerLinkModel::get_by_id(link_id, db).await? {
+        Some(link) if link.member_id == member.id || member.permissions.is_admin() => Ok(link),
+        _ => Err(ApiErrorResponse::new("Unable to find Server Link").into()),
+    }
+}
@@ -0,0 +1,101 @@
+use actix_web::{delete, get, post, web};
+use common::api::{ApiErrorResponse, WrappingResponse};
+use common_local::{api, api_token::ApiTokenScopes, ServerLinkGrantId};
+use tokio_postgres::Client;
+
+use crate::{
+    http::{JsonResponse, MemberCookie},
+    model::{ServerLinkGrantModel, ServerLinkModel},
+    WebResult,
+};
+
+use super::member::fetch_session_member;
+
+#[get("/account/grants")]
+async fn get_server_link_grants(
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetServerLinkGrantsResponse>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let mut items = Vec::new();
+
+    for grant in ServerLinkGrantModel::find_by_member_id(member.id, &db).await? {
+        let link = ServerLinkModel::get_by_id(grant.server_link_id, &db).await?;
+
+        items.push(grant.into_shared(link));
+    }
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetServerLinkGrantsResponse { items },
+    )))
+}
+
+#[get("/grant/server/{public_id}")]
+async fn get_grant_server(
+    public_id: web::Path<String>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetGrantServerResponse>> {
+    fetch_session_member(member, &db).await?;
+
+    let link = get_verified_server_link(&public_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::GetGrantServerResponse {
+            server_owner_name: link.server_owner_name,
+            server_name: link.server_name,
+        },
+    )))
+}
+
+/// Lets the server act on behalf of the member within the scopes.
+#[post("/account/grant")]
+async fn create_server_link_grant(
+    body: web::Json<api::NewServerLinkGrantBody>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::SharedServerLinkGrant>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    let api::NewServerLinkGrantBody { public_id, scopes } = body.into_inner();
+
+    if scopes.is_empty() {
+        return Err(ApiErrorResponse::new("At least one scope is required").into());
+    }
+
+    if scopes.contains(ApiTokenScopes::ADMIN) {
+        return Err(ApiErrorResponse::new("Servers cannot be granted admin access").into());
+    }
+
+    let link = get_verified_server_link(&public_id, &db).await?;
+
+    let grant = ServerLinkGrantModel::upsert(link.id, member.id, scopes, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        grant.into_shared(Some(link)),
+    )))
+}
+
+#[delete("/account/grant/{id}")]
+async fn revoke_server_link_grant(
+    id: web::Path<ServerLinkGrantId>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<&'static str>> {
+    let member = fetch_session_member(member, &db).await?;
+
+    if ServerLinkGrantModel::remove(*id, member.id, &db).await? == 0 {
+        return Err(ApiErrorResponse::new("Unable to find Grant").into());
+    }
+
+    Ok(web::Json(WrappingResponse::okay("ok")))
+}
+
+/// Only servers which completed the handshake can be granted access.
+async fn get_verified_server_link(public_id: &str, db: &Client) -> WebResult<ServerLinkModel> {
+    match ServerLinkModel::get_by_public_id(public_id, db).await? {
+        Some(link) if link.verified => Ok(link),
+        _ => Err(ApiErrorResponse::new("Unable to find a verified Server").into()),
+    }
+}
@@ -1,10 +1,12 @@
-use actix_web::{get, post, web};
+use actix_web::{get, post, web, HttpRequest};
 use common::api::{ApiErrorResponse, WrappingResponse};
-use common_local::{api, update::OptionsUpdate};
+use common_local::{api, audit::AuditAction, update::OptionsUpdate};
+use serde_json::json;
 
 use crate::{
     config,
-    http::{JsonResponse, MemberCookie},
+    http::{get_ip_address, JsonResponse, MemberCookie},
+    model::NewAuditLogModel,
     WebResult,
 };
 
@@ -33,6 +35,7 @@ async fn get_settings(
 
 #[post("/settings")]
 async fn update_settings(
+    req: HttpRequest,
     modify: web::Json<OptionsUpdate>,
     member: MemberCookie,
     db: web::Data<tokio_postgres::Client>,
@@ -44,7 +47,7 @@ async fn update_settings(
     // Member Changes
     if let Some(settings) = update.member {
         member.set_settings(settings)?;
-        member.update(&db).await?;
+        member.update_settings(&db).await?;
 
         return Ok(web::Json(WrappingResponse::okay("ok")));
     }
@@ -56,6 +59,11 @@ async fn update_settings(
 
     let mut config = config::get_config();
 
+    let before = json!({
+        "server_name": config.server.name,
+        "user_signup": config.auth.new_users,
+    });
+
     if let Some(value) = update.server_name {
         config.server.name = value;
     }
@@ -64,11 +72,22 @@ async fn update_settings(
         config.auth.new_users = value
     }
 
+    let after = json!({
+        "server_name": config.server.name,
+        "user_signup": config.auth.new_users,
+    });
+
     config::update_config(move |v| {
         *v = config;
         Ok(())
     })?;
     config::save_config().await?;
 
+    NewAuditLogModel::new(member.id, AuditAction::UpdateSettings, get_ip_address(&req))
+        .before(&before)?
+        .after(&after)?
+        .insert(&db)
+        .await?;
+
     Ok(web::Json(WrappingResponse::okay("ok")))
 }
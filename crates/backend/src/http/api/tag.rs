@@ -3,11 +3,15 @@ use common::{
     api::{DeletionResponse, WrappingResponse},
     BookId, TagId,
 };
-use common_local::api::{self, NewTagBody};
+use common_local::{
+    api::{self, NewTagBody},
+    edit::EditStatus,
+    item::edit::TagEdit,
+};
 
 use crate::{
     http::{JsonResponse, MemberCookie},
-    model::{BookTagModel, BookTagWithTagModel, NewTagModel, TagModel},
+    model::{BookTagModel, BookTagWithTagModel, NewEditModel, NewEditVoteModel, TagModel},
     WebResult,
 };
 
@@ -29,6 +33,7 @@ async fn create_new_tag(
     member: MemberCookie,
     db: web::Data<tokio_postgres::Client>,
 ) -> WebResult<JsonResponse<api::NewTagResponse>> {
+    let server_link_id = member.server_link_id();
     let member = member.fetch_or_error(&db).await?;
 
     if !member.permissions.has_editing_perms() {
@@ -39,11 +44,41 @@ async fn create_new_tag(
 
     let NewTagBody { name, type_of } = body.into_inner();
 
-    let model = NewTagModel { name, type_of };
+    let mut model = NewEditModel::from_tag_create(
+        member.id,
+        TagEdit {
+            name: Some(name),
+            type_of: Some(type_of),
+        },
+    )?;
 
-    Ok(web::Json(WrappingResponse::okay(
-        model.insert(&db).await?.into(),
-    )))
+    model.server_link_id = server_link_id;
+
+    // Admins create the tag instantly. Everyone else has to go through voting.
+    if member.permissions.is_admin() {
+        let mut model = model.insert(&db).await?;
+
+        model
+            .process_status_change(EditStatus::ForceAccepted, &db)
+            .await?;
+
+        let tag = match model.model_id {
+            Some(id) => TagModel::get_by_id(TagId::from(id), &db).await?,
+            None => None,
+        };
+
+        Ok(web::Json(WrappingResponse::okay(tag.map(|v| v.into()))))
+    } else {
+        model.vote_count += 1;
+
+        let model = model.insert(&db).await?;
+
+        NewEditVoteModel::create(model.id, member.id, true)
+            .insert(&db)
+            .await?;
+
+        Ok(web::Json(WrappingResponse::okay(None)))
+    }
 }
 
 #[get("/tags")]
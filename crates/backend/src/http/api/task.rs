@@ -1,92 +1,104 @@
-use futures::TryStreamExt;
+use actix_web::{get, post, web};
+use common::api::{ApiErrorResponse, QueryListResponse, WrappingResponse};
+use common_local::api;
 use tokio_postgres::Client;
 
-use crate::Result;
-
+use crate::{
+    http::{JsonResponse, MemberCookie},
+    model::TaskRunModel,
+    scheduler, WebResult,
+};
+
+#[get("/tasks")]
+async fn get_tasks(
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetTasksResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
+    }
 
+    let mut items = Vec::new();
 
+    for task in scheduler::TASKS {
+        items.push(scheduler::get_task_info(task, &db).await?);
+    }
 
-pub fn run_task() {
-    //
+    Ok(web::Json(WrappingResponse::okay(api::GetTasksResponse {
+        items,
+    })))
 }
 
+#[get("/task/{name}/runs")]
+async fn get_task_runs(
+    name: web::Path<String>,
+    query: web::Query<api::SimpleListQuery>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::GetTaskRunsResponse>> {
+    let member = member.fetch_or_error(&db).await?;
+
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
+    }
 
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(25);
+
+    let items = TaskRunModel::find_by_name(&name, offset, limit, &db)
+        .await?
+        .into_iter()
+        .map(|v| v.into())
+        .collect();
+
+    Ok(web::Json(WrappingResponse::okay(QueryListResponse {
+        offset,
+        limit,
+        total: TaskRunModel::count_by_name(&name, &db).await?,
+        items,
+    })))
+}
 
-// JOIN - Includes ALL, even if book_person is NULL
-
-// SELECT DISTINCT ON(id) id, cached, book_person.person_id
-// FROM book
-// LEFT OUTER JOIN book_person ON book_person.book_id = book.id
-// ORDER BY book.id ASC
-
-
-// JOIN - Includes only the ones which match book_person
-
-// SELECT DISTINCT ON(id) id, cached, book_person.person_id
-// FROM book
-// JOIN book_person ON book_person.book_id = book.id
-// ORDER BY book.id ASC
-
-// SELECT DISTINCT ON(book.id) book.id, book.cached, person.id, person.name
-// FROM book
-// JOIN book_person ON book_person.book_id = book.id
-// JOIN person ON person.id = book_person.person_id
-// ORDER BY book.id ASC
-
-
-
-// NON JOIN - Select ALL which has a person
-
-// SELECT DISTINCT ON(id) id, cached
-// FROM book
-// WHERE id IN (SELECT book_id FROM book_person)
-// ORDER BY book.id ASC
-
-
-// NON JOIN - Select ALL which does NOT have a person
+#[post("/task/{name}/run")]
+async fn run_task(
+    name: web::Path<String>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::RunTaskResponse>> {
+    let member = member.fetch_or_error(&db).await?;
 
-// SELECT DISTINCT ON(id) id, cached
-// FROM book
-// WHERE id NOT IN (SELECT book_id FROM book_person)
-// ORDER BY book.id ASC
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
+    }
 
+    let task = match scheduler::find_task(&name) {
+        Some(v) => v,
+        None => return Ok(web::Json(WrappingResponse::error("Unknown Task"))),
+    };
 
-pub async fn refresh_book_cache(db: &Client) -> Result<()> {
-    let values = db.query(
-        r#"
-            SELECT DISTINCT ON(book.id) book.id, cached, book_person.person_id, person.name
-            FROM book
-            JOIN book_person ON book_person.book_id = book.id
-            JOIN person ON person.id = book_person.person_id
-            ORDER BY book.id ASC
-        "#,
-        &[]
-    ).await?;
+    match scheduler::run_task(task, Some(member.id), db).await? {
+        Some(run) => Ok(web::Json(WrappingResponse::okay(run.into()))),
+        None => Ok(web::Json(WrappingResponse::error(
+            "Task is already running!",
+        ))),
+    }
+}
 
-    for row in values {
-        let book_id: i32 = row.get(0);
-        let book_cache = common_local::MetadataItemCached::from_string(&row.get::<_, String>(1));
-        let person_id: i32 = row.get(2);
-        let person_name: String = row.get(3);
+#[post("/task/{name}/cancel")]
+async fn cancel_task(
+    name: web::Path<String>,
+    member: MemberCookie,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<bool>> {
+    let member = member.fetch_or_error(&db).await?;
 
-        db.execute(
-            "UPDATE book SET cached = $2 WHERE id = $1",
-            params![
-                book_id,
-                book_cache
-                    .author(person_name)
-                    .author_id(common::PersonId::from(person_id as usize))
-                    .as_string()
-            ]
-        ).await?;
+    if !member.permissions.is_admin() {
+        return Err(ApiErrorResponse::new("Admin perms needed").into());
     }
 
-    Ok(())
+    Ok(web::Json(WrappingResponse::okay(scheduler::cancel_task(
+        &name,
+    ))))
 }
-
-
-fn slice_iter<'a>(
-    s: &'a [&'a (dyn tokio_postgres::types::ToSql + Sync)],
-) -> impl ExactSizeIterator<Item = &'a dyn tokio_postgres::types::ToSql> + 'a {
-    s.iter().map(|s| *s as _)
-}
\ No newline at end of file
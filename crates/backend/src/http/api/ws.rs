@@ -0,0 +1,259 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix::{
+    fut::wrap_future, Actor, ActorContext, ActorFutureExt, AsyncContext, Handler, Message,
+    Recipient, StreamHandler,
+};
+use actix_session::SessionExt;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+use common::MemberId;
+use common_local::{
+    ws::{UniqueId, WebsocketNotification, WebsocketRequest, WebsocketResponse, WebsocketTopic},
+    ServerLinkId,
+};
+use lazy_static::lazy_static;
+use tokio_postgres::Client;
+
+use crate::{
+    http::{get_bearer_token, session::get_login_id, MemberCookie},
+    model::{ApiTokenModel, MemberModel, MemberSessionModel, ServerLinkGrantModel},
+    Result, WebResult,
+};
+
+/// How often we ping the client.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// How long we wait for the client to respond before closing the connection.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+/// How often we check that the login which opened the connection is still valid.
+const LOGIN_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    static ref CONNECTIONS: Mutex<HashMap<UniqueId, Connection>> = Mutex::default();
+}
+
+struct Connection {
+    recipient: Recipient<Notify>,
+    topics: HashSet<WebsocketTopic>,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+struct Notify(WebsocketNotification);
+
+/// Sends a notification to every connection subscribed to its topic.
+pub fn send_notification(value: WebsocketNotification) {
+    let topic = value.topic();
+
+    for conn in CONNECTIONS.lock().unwrap().values() {
+        if conn.topics.contains(&topic) {
+            conn.recipient.do_send(Notify(value.clone()));
+        }
+    }
+}
+
+#[get("/ws")]
+async fn ws_index(
+    req: HttpRequest,
+    stream: web::Payload,
+    member: MemberCookie,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<HttpResponse> {
+    let login = if let Some(id) = member.server_link_id() {
+        Login::Grant(id)
+    } else if member.is_api_token() {
+        Login::ApiToken(get_bearer_token(&req).unwrap_or_default())
+    } else {
+        Login::Session(get_login_id(&req.get_session())?)
+    };
+
+    let member = member.fetch_or_error(&db).await?;
+
+    Ok(ws::start(
+        WebsocketConnection {
+            id: UniqueId::new(),
+            last_heartbeat: Instant::now(),
+            member_id: member.id,
+            is_admin: member.permissions.is_admin(),
+            login,
+            db,
+        },
+        &req,
+        stream,
+    )?)
+}
+
+/// What the connection was opened with.
+#[derive(Clone)]
+enum Login {
+    /// The login ID of the cookie session.
+    Session(Option<String>),
+    ApiToken(String),
+    Grant(ServerLinkId),
+}
+
+impl Login {
+    /// Returns false if the login was removed, or the member was removed or suspended.
+    async fn is_valid(&self, member_id: MemberId, db: &Client) -> Result<bool> {
+        match MemberModel::get_by_id(member_id, db).await? {
+            Some(member) if member.get_suspension().is_none() => (),
+            _ => return Ok(false),
+        }
+
+        Ok(match self {
+            Self::Session(Some(login_id)) => {
+                MemberSessionModel::exists_valid_by_login_id(member_id, login_id, db).await?
+            }
+
+            // Logged in before sessions were stored.
+            Self::Session(None) => true,
+
+            Self::ApiToken(token) => ApiTokenModel::find_valid_by_token(token, db)
+                .await?
+                .map_or(false, |v| v.member_id == member_id),
+
+            Self::Grant(server_link_id) => {
+                ServerLinkGrantModel::get_by_server_link_and_member(*server_link_id, member_id, db)
+                    .await?
+                    .is_some()
+            }
+        })
+    }
+}
+
+struct WebsocketConnection {
+    id: UniqueId,
+    last_heartbeat: Instant,
+
+    member_id: MemberId,
+    /// Only Admins can subscribe to [`WebsocketTopic::Tasks`].
+    is_admin: bool,
+    login: Login,
+
+    db: web::Data<tokio_postgres::Client>,
+}
+
+impl WebsocketConnection {
+    fn send(&self, value: &WebsocketResponse, ctx: &mut ws::WebsocketContext<Self>) {
+        match serde_json::to_string(value) {
+            Ok(v) => ctx.text(v),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    fn update_topics(&self, func: impl FnOnce(&mut HashSet<WebsocketTopic>)) {
+        if let Some(conn) = CONNECTIONS.lock().unwrap().get_mut(&self.id) {
+            func(&mut conn.topics);
+        }
+    }
+
+    /// Closes the connection once its login is no longer valid.
+    fn check_login(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        let member_id = self.member_id;
+        let login = self.login.clone();
+        let db = self.db.clone();
+
+        ctx.spawn(
+            wrap_future(async move { login.is_valid(member_id, &db).await }).map(
+                |result, _act, ctx: &mut ws::WebsocketContext<Self>| match result {
+                    Ok(true) => (),
+                    Ok(false) => ctx.stop(),
+                    // Keep the connection open if the database is temporarily unavailable.
+                    Err(e) => eprintln!("{e}"),
+                },
+            ),
+        );
+    }
+}
+
+impl Actor for WebsocketConnection {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        CONNECTIONS.lock().unwrap().insert(
+            self.id,
+            Connection {
+                recipient: ctx.address().recipient(),
+                topics: HashSet::new(),
+            },
+        );
+
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if Instant::now().duration_since(act.last_heartbeat) > CLIENT_TIMEOUT {
+                ctx.stop();
+            } else {
+                act.send(&WebsocketResponse::Ping, ctx);
+            }
+        });
+
+        ctx.run_interval(LOGIN_CHECK_INTERVAL, |act, ctx| act.check_login(ctx));
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        CONNECTIONS.lock().unwrap().remove(&self.id);
+    }
+}
+
+impl Handler<Notify> for WebsocketConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: Notify, ctx: &mut Self::Context) {
+        self.send(&WebsocketResponse::Notification(msg.0), ctx);
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WebsocketConnection {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let msg = match msg {
+            Ok(v) => v,
+            Err(_) => {
+                ctx.stop();
+                return;
+            }
+        };
+
+        match msg {
+            ws::Message::Ping(v) => {
+                self.last_heartbeat = Instant::now();
+                ctx.pong(&v);
+            }
+
+            ws::Message::Pong(_) => {
+                self.last_heartbeat = Instant::now();
+            }
+
+            ws::Message::Text(text) => {
+                self.last_heartbeat = Instant::now();
+
+                match serde_json::from_str::<WebsocketRequest>(&text) {
+                    Ok(WebsocketRequest::Ping) => self.send(&WebsocketResponse::Pong, ctx),
+                    Ok(WebsocketRequest::Pong) => (),
+
+                    // Only Admins receive task updates.
+                    Ok(WebsocketRequest::Subscribe(WebsocketTopic::Tasks)) if !self.is_admin => (),
+
+                    Ok(WebsocketRequest::Subscribe(topic)) => self.update_topics(|v| {
+                        v.insert(topic);
+                    }),
+
+                    Ok(WebsocketRequest::Unsubscribe(topic)) => self.update_topics(|v| {
+                        v.remove(&topic);
+                    }),
+
+                    Err(e) => eprintln!("{e}"),
+                }
+            }
+
+            ws::Message::Close(reason) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+// Email verification and password resets.
+// Both email a single-use link which expires. Only the hash of the token is stored.
+
+use actix_web::{http::header, web, HttpResponse};
+use chrono::{Duration, Utc};
+use common::{
+    api::{ApiErrorResponse, WrappingResponse},
+    MemberId,
+};
+use common_local::api;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Client;
+
+use crate::{
+    config::get_config,
+    email::send_email,
+    hash_token,
+    http::JsonResponse,
+    model::{
+        ApiTokenModel, MemberModel, MemberSessionModel, MemberTokenModel, MemberTokenType,
+        NewMemberTokenModel, ServerLinkGrantModel,
+    },
+    Error, Result, WebResult,
+};
+
+use super::{
+    password::{hash_password, validate_password},
+    passwordless::gen_sample_alphanumeric,
+};
+
+pub static VERIFY_EMAIL_PATH: &str = "/auth/verify";
+pub static PASSWORD_RESET_PATH: &str = "/auth/password/reset";
+pub static PASSWORD_RESET_CONFIRM_PATH: &str = "/auth/password/reset/confirm";
+
+/// Frontend page which the password reset link opens.
+static PASSWORD_RESET_PAGE: &str = "/reset-password";
+
+#[derive(Serialize, Deserialize)]
+pub struct TokenQuery {
+    pub token: String,
+}
+
+/// Returns the configured public address of the server. Used for links in emails.
+///
+/// The request's Host header isn't used since it's controlled by whoever sent the request.
+pub fn get_base_url() -> Option<String> {
+    let base_url = get_config().server.base_url?;
+    let base_url = base_url.trim().trim_end_matches('/');
+
+    (!base_url.is_empty()).then(|| base_url.to_string())
+}
+
+/// Emails a link which sets `email` as the member's verified email once opened.
+pub async fn send_verification_email(
+    member_id: MemberId,
+    email: &str,
+    base_url: &str,
+    db: &Client,
+) -> Result<()> {
+    let config = get_config();
+
+    let email_config = match config.email {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let token = gen_sample_alphanumeric(48, &mut rand::thread_rng());
+    let now = Utc::now();
+
+    NewMemberTokenModel {
+        member_id,
+        type_of: MemberTokenType::VerifyEmail,
+        token_hash: hash_token(&token),
+        email: Some(email.to_string()),
+        created_at: now,
+        expires_at: now + Duration::hours(config.account.verification_expiry_hours),
+    }
+    .insert(db)
+    .await?;
+
+    let url = format!(
+        "{base_url}{VERIFY_EMAIL_PATH}?{}",
+        serde_urlencoded::to_string(TokenQuery { token }).map_err(Error::from)?
+    );
+
+    send_email(
+        email,
+        &format!("{} - Verify your email", email_config.display_name),
+        format!("Open this link to verify your email: {url}"),
+        render_link_email(
+            &format!(
+                "Please confirm this is your email address for {}.",
+                email_config.display_name
+            ),
+            "Verify Email",
+            &url,
+        ),
+        &email_config,
+    )
+}
+
+pub async fn get_verify_email(
+    query: web::Query<TokenQuery>,
+    db: web::Data<Client>,
+) -> WebResult<HttpResponse> {
+    if let Some(token) =
+        MemberTokenModel::take_valid(&query.token, MemberTokenType::VerifyEmail, &db).await?
+    {
+        let member = MemberModel::get_by_id(token.member_id, &db).await?;
+
+        if let Some((mut member, email)) = member.zip(token.email) {
+            // The email could've been taken since the link was sent.
+            let is_taken = MemberModel::get_by_email(&email, &db)
+                .await?
+                .filter(|v| v.id != member.id)
+                .is_some();
+
+            if !is_taken {
+                member.email = Some(email);
+                member.email_verified_at = Some(Utc::now());
+                member.update(&db).await?;
+            }
+        }
+    }
+
+    Ok(HttpResponse::Found()
+        .append_header((header::LOCATION, "/"))
+        .finish())
+}
+
+pub async fn post_password_reset(
+    body: web::Json<api::PasswordResetBody>,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<String>> {
+    let config = get_config();
+
+    let (email_config, base_url) = match (config.email, get_base_url()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => {
+            return Err(ApiErrorResponse::new(
+                "Missing email from config OR missing server base_url",
+            )
+            .into())
+        }
+    };
+
+    // Always respond the same so it can't be used to find out which emails have an account.
+    let member = match MemberModel::get_by_email(body.email.trim(), &db).await? {
+        Some(v) if v.get_suspension().is_none() => v,
+        _ => return Ok(web::Json(WrappingResponse::okay(String::from("success")))),
+    };
+
+    let email = match member.email.clone() {
+        Some(v) => v,
+        None => return Ok(web::Json(WrappingResponse::okay(String::from("success")))),
+    };
+
+    let limit_since = Utc::now() - Duration::minutes(config.passwordless.limit_window_minutes);
+
+    if MemberTokenModel::count_by_member_id_since(
+        member.id,
+        MemberTokenType::ResetPassword,
+        limit_since,
+        &db,
+    )
+    .await?
+        >= config.passwordless.max_requests_per_email
+    {
+        return Err(ApiErrorResponse::new("Too many reset requests for this email").into());
+    }
+
+    let token = gen_sample_alphanumeric(48, &mut rand::thread_rng());
+    let now = Utc::now();
+
+    NewMemberTokenModel {
+        member_id: member.id,
+        type_of: MemberTokenType::ResetPassword,
+        token_hash: hash_token(&token),
+        email: None,
+        created_at: now,
+        expires_at: now + Duration::minutes(config.account.reset_expiry_minutes),
+    }
+    .insert(&db)
+    .await?;
+
+    let url = format!(
+        "{base_url}{PASSWORD_RESET_PAGE}?{}",
+        serde_urlencoded::to_string(TokenQuery { token }).map_err(Error::from)?
+    );
+
+    send_email(
+        &email,
+        &format!("{} - Reset your password", email_config.display_name),
+        format!("Open this link to reset your password: {url}"),
+        render_link_email(
+            "Someone requested a password reset for your account. Ignore this email if it wasn't you.",
+            "Reset Password",
+            &url,
+        ),
+        &email_config,
+    )?;
+
+    Ok(web::Json(WrappingResponse::okay(String::from("success"))))
+}
+
+pub async fn post_password_reset_confirm(
+    body: web::Json<api::PasswordResetConfirmBody>,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<String>> {
+    let api::PasswordResetConfirmBody { token, password } = body.into_inner();
+
+    validate_password(&password)?;
+
+    let token =
+        match MemberTokenModel::take_valid(&token, MemberTokenType::ResetPassword, &db).await? {
+            Some(v) => v,
+            None => return Err(ApiErrorResponse::new("Invalid or expired reset link").into()),
+        };
+
+    let mut member = match MemberModel::get_by_id(token.member_id, &db).await? {
+        Some(v) => v,
+        None => return Err(ApiErrorResponse::new("Unable to find member").into()),
+    };
+
+    member.password = Some(hash_password(&password)?);
+
+    // Opening the emailed link proves they own the email.
+    if member.email_verified_at.is_none() {
+        member.email_verified_at = Some(Utc::now());
+    }
+
+    member.update(&db).await?;
+
+    MemberTokenModel::remove_by_member_id(member.id, MemberTokenType::ResetPassword, &db).await?;
+
+    // Whoever had access to the account before the reset shouldn't keep it.
+    MemberSessionModel::remove_by_member_id(member.id, &db).await?;
+    ApiTokenModel::remove_by_member_id(member.id, &db).await?;
+    ServerLinkGrantModel::remove_by_member_id(member.id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(String::from("success"))))
+}
+
+fn render_link_email(message: &str, link_text: &str, url: &str) -> String {
+    format!(
+        r#"
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <meta http-equiv="Content-Type" content="text/html; charset=UTF-8">
+            </head>
+            <body style="margin: 0; padding: 20px; font-family: Verdana, sans-serif;">
+                <p style="font-size: 1.2em; line-height: 1.3;">{message}</p>
+
+                <p style="text-align: center;">
+                    <a
+                        style="display: inline-block; padding: 12px 26px; margin: 20px 0; border: 1px solid #7c7622; border-radius: 3px; color: #333132; text-decoration: none; text-transform: uppercase; letter-spacing: 1px;"
+                        href="{url}"
+                    >{link_text}</a>
+                </p>
+
+                <p>Or open this link:</p>
+
+                <p><a href="{url}">{url}</a></p>
+
+                <p>This link will expire.</p>
+            </body>
+        </html>
+        "#
+    )
+}
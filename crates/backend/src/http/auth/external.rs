@@ -35,10 +35,7 @@ pub async fn post_oauth_link(
         return Ok(HttpResponse::InternalServerError().body("Missing Server ID"));
     }
 
-    let mut rng = thread_rng();
-
-    let server_id = AlphanumericSpecials.sample_string(&mut rng, 32);
-    let public_id = Alphanumeric.sample_string(&mut rng, 40);
+    let (server_id, public_id) = gen_server_ids();
 
     NewServerLinkModel {
         server_owner_name: form.server_owner_name,
@@ -56,7 +53,7 @@ pub async fn post_oauth_link(
     .insert(&db)
     .await?;
 
-    // TODO: Also link account.
+    // Member accounts are linked separately. The server sends them to `/grant` to choose its scopes.
 
     let mut location_uri = Url::parse(&form.redirect_uri).unwrap();
     location_uri.set_query(Some(
@@ -104,6 +101,16 @@ pub async fn get_oauth_handshake(
     }
 }
 
+/// Returns a new `(server_id, public_id)` pair.
+pub fn gen_server_ids() -> (String, String) {
+    let mut rng = thread_rng();
+
+    (
+        AlphanumericSpecials.sample_string(&mut rng, 32),
+        Alphanumeric.sample_string(&mut rng, 40),
+    )
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AlphanumericSpecials;
 
@@ -8,20 +8,31 @@ use std::{
 use actix_identity::Identity;
 use actix_web::{
     body::MessageBody,
-    dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform, Extensions},
-    FromRequest, HttpRequest,
+    dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header,
+    web, FromRequest, HttpMessage, HttpRequest,
 };
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use common::{api::ApiErrorResponse, MemberId};
+use common_local::{api::LoginStatus, api_token::ApiTokenScopes, ServerLinkId};
 use futures::{future::LocalBoxFuture, FutureExt};
 use serde::{Deserialize, Serialize};
 use tokio_postgres::Client;
 
-use crate::{model::MemberModel, InternalError, Result, WebError};
+use crate::{
+    config::get_config,
+    model::{ApiTokenModel, MemberModel, MemberTotpModel},
+    InternalError, Result, WebError, WebResult,
+};
+
+use super::linked_server::authenticate_member_grant;
 
+pub mod account;
 pub mod external;
 pub mod password;
 pub mod passwordless;
+pub mod session;
+pub mod two_factor;
 
 #[derive(Serialize, Deserialize)]
 pub struct CookieAuth {
@@ -29,37 +40,117 @@ pub struct CookieAuth {
     pub stored_since: i64,
 }
 
+/// Logins older than the configured max age are ignored.
 pub fn get_auth_value(identity: &Identity) -> Option<CookieAuth> {
     let ident = identity.id().ok()?;
-    serde_json::from_str(&ident).ok()
+    let auth = serde_json::from_str::<CookieAuth>(&ident).ok()?;
+
+    let expired_before =
+        (Utc::now() - Duration::days(get_config().session.max_age_days)).timestamp_millis();
+
+    (auth.stored_since > expired_before).then_some(auth)
 }
 
-pub fn remember_member_auth(ext: &Extensions, member_id: MemberId) -> Result<()> {
+pub(crate) fn get_bearer_token(req: &HttpRequest) -> Option<String> {
+    let value = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+
+    value
+        .strip_prefix("Bearer ")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Called once the first login factor was accepted.
+///
+/// Members with two-factor enabled, or admins who are required to set it up, are only stored as
+/// pending. Their login is completed by [`two_factor`] once a code was verified.
+pub async fn remember_member_auth(
+    req: &HttpRequest,
+    member: &MemberModel,
+    db: &Client,
+) -> Result<LoginStatus> {
+    let status = if MemberTotpModel::is_enabled_for_member(member.id, db).await? {
+        LoginStatus::TwoFactorRequired
+    } else if get_config().account.require_admin_two_factor && member.permissions.is_admin() {
+        LoginStatus::TwoFactorSetupRequired
+    } else {
+        LoginStatus::Complete
+    };
+
+    if status.is_complete() {
+        complete_member_auth(req, member.id)?;
+    } else {
+        two_factor::set_pending(req, member.id)?;
+    }
+
+    Ok(status)
+}
+
+fn complete_member_auth(req: &HttpRequest, member_id: MemberId) -> Result<()> {
+    session::store_session_info(req, member_id)?;
+
     let value = serde_json::to_string(&CookieAuth {
         member_id,
         stored_since: Utc::now().timestamp_millis(),
     })?;
 
-    Identity::login(ext, value).expect("Ident Login Error");
+    Identity::login(&req.extensions(), value).expect("Ident Login Error");
 
     Ok(())
 }
 
-// Retrive Member from Identity
-pub struct MemberCookie(CookieAuth);
+// Retrive Member from Identity, an API Token or a Linked Server's grant
+pub struct MemberCookie {
+    member_id: MemberId,
+    /// Set if authenticated with an `Authorization: Bearer` API token or a grant.
+    token_scopes: Option<ApiTokenScopes>,
+    /// Set if a linked server acts on behalf of the member.
+    server_link_id: Option<ServerLinkId>,
+}
 
 impl MemberCookie {
     pub fn member_id(&self) -> MemberId {
-        self.0.member_id
+        self.member_id
+    }
+
+    pub fn is_api_token(&self) -> bool {
+        self.token_scopes.is_some()
+    }
+
+    pub fn server_link_id(&self) -> Option<ServerLinkId> {
+        self.server_link_id
     }
 
+    /// Errors if an API token or grant was used which wasn't given the scope.
+    ///
+    /// Used by endpoints which aren't already limited through the restricted permissions.
+    pub fn require_scope(&self, scope: ApiTokenScopes) -> WebResult<()> {
+        match self.token_scopes {
+            Some(scopes) if !scopes.intersects(scope | ApiTokenScopes::ADMIN) => {
+                Err(ApiErrorResponse::new("API token is missing the required scope").into())
+            }
+
+            _ => Ok(()),
+        }
+    }
+
+    /// The member's permissions are limited to the API token's scopes.
     pub async fn fetch(&self, client: &Client) -> Result<Option<MemberModel>> {
-        MemberModel::get_by_id(self.member_id(), client).await
+        let mut member = MemberModel::get_by_id(self.member_id(), client).await?;
+
+        if let (Some(member), Some(scopes)) = (member.as_mut(), self.token_scopes) {
+            member.permissions = scopes.restrict(member.permissions);
+        }
+
+        Ok(member)
     }
 
     pub async fn fetch_or_error(&self, client: &Client) -> Result<MemberModel> {
         match self.fetch(client).await? {
-            Some(v) => Ok(v),
+            Some(v) => match v.get_suspension() {
+                Some(suspension) => Err(InternalError::UserSuspended(suspension.reason).into()),
+                None => Ok(v),
+            },
             None => Err(InternalError::UserMissing.into()),
         }
     }
@@ -71,11 +162,52 @@ impl FromRequest for MemberCookie {
         Pin<Box<dyn std::future::Future<Output = std::result::Result<MemberCookie, WebError>>>>;
 
     fn from_request(req: &HttpRequest, pl: &mut Payload) -> Self::Future {
-        let fut = Identity::from_request(req, pl);
+        let req = req.clone();
+        let bearer = get_bearer_token(&req);
+        let db = req.app_data::<web::Data<Client>>().cloned();
+        let fut = Identity::from_request(&req, pl);
 
         Box::pin(async move {
-            if let Some(id) = get_auth_value(&fut.await?) {
-                Ok(MemberCookie(id))
+            if let Some(db) = db.as_ref() {
+                match authenticate_member_grant(&req, db).await? {
+                    Some(Ok(grant)) => {
+                        return Ok(MemberCookie {
+                            member_id: grant.member_id,
+                            token_scopes: Some(grant.scopes - ApiTokenScopes::ADMIN),
+                            server_link_id: Some(grant.server_link_id),
+                        });
+                    }
+
+                    Some(Err(e)) => return Err(WebError::ApiResponse(ApiErrorResponse::new(e))),
+
+                    None => (),
+                }
+            }
+
+            if let Some((token, db)) = bearer.zip(db) {
+                return match ApiTokenModel::find_valid_by_token(&token, &db).await? {
+                    Some(token) => {
+                        ApiTokenModel::update_last_used(token.id, &db).await?;
+
+                        Ok(MemberCookie {
+                            member_id: token.member_id,
+                            token_scopes: Some(token.scopes),
+                            server_link_id: None,
+                        })
+                    }
+
+                    None => Err(WebError::ApiResponse(ApiErrorResponse::new(
+                        "invalid api token",
+                    ))),
+                };
+            }
+
+            if let Some(auth) = get_auth_value(&fut.await?) {
+                Ok(MemberCookie {
+                    member_id: auth.member_id,
+                    token_scopes: None,
+                    server_link_id: None,
+                })
             } else {
                 Err(WebError::ApiResponse(ApiErrorResponse::new("unauthorized")))
             }
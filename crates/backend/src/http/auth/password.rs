@@ -1,13 +1,11 @@
-// TODO: Better security. Simple Proof of Concept.
-
 use actix_identity::Identity;
-use actix_web::HttpMessage;
 use actix_web::HttpRequest;
 use actix_web::web;
 
 use chrono::Utc;
 use common::api::ApiErrorResponse;
 use common::api::WrappingResponse;
+use common_local::api::LoginStatus;
 use common_local::Permissions;
 use rand::prelude::ThreadRng;
 use rand::Rng;
@@ -18,15 +16,30 @@ use crate::http::JsonResponse;
 use crate::model::MemberModel;
 use crate::model::NewMemberModel;
 use crate::Error;
+use crate::InternalError;
+use crate::Result;
 use crate::WebResult;
 
+use super::account::{get_base_url, send_verification_email};
+
 pub static PASSWORD_PATH: &str = "/auth/password";
+pub static PASSWORD_SIGNUP_PATH: &str = "/auth/password/signup";
+
+/// Keep in sync with the message in [`validate_password`].
+pub const MIN_PASSWORD_LENGTH: usize = 8;
 
 #[derive(Serialize, Deserialize)]
 pub struct PostPasswordCallback {
     pub email: String,
     pub password: String,
-    // TODO: Check for Login vs Signup
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostPasswordSignup {
+    /// Defaults to the start of the email.
+    pub name: Option<String>,
+    pub email: String,
+    pub password: String,
 }
 
 pub async fn post_password_oauth(
@@ -34,44 +47,118 @@ pub async fn post_password_oauth(
     query: web::Json<PostPasswordCallback>,
     identity: Option<Identity>,
     db: web::Data<tokio_postgres::Client>,
-) -> WebResult<JsonResponse<String>> {
+) -> WebResult<JsonResponse<LoginStatus>> {
     if identity.is_some() {
         return Err(ApiErrorResponse::new("Already logged in").into());
     }
 
     let PostPasswordCallback { email, password } = query.into_inner();
 
-    // Create or Update User.
-    let member = if let Some(value) = MemberModel::get_by_email(&email, &db).await? {
-        if bcrypt::verify(&password, value.password.as_ref().unwrap()).map_err(Error::from)? {
-            value
-        } else {
-            return Err(ApiErrorResponse::new("Unable to very password hash for account").into());
-        }
-    } else if !get_config().auth.new_users {
-        return Err(ApiErrorResponse::new("New user creation is disabled").into());
-    } else {
-        let hash = bcrypt::hash(&password, bcrypt::DEFAULT_COST).map_err(Error::from)?;
-
-        let new_member = NewMemberModel {
-            // TODO: Strip email
-            name: email.clone(),
-            email: Some(email),
-            password: Some(hash),
-            permissions: Permissions::basic(),
-            localsettings: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        };
-
-        new_member.insert(&db).await?
+    let member = match MemberModel::get_by_email(email.trim(), &db).await? {
+        Some(value) if verify_password(&password, &value)? => value,
+        _ => return Err(ApiErrorResponse::new("Invalid email or password").into()),
     };
 
-    super::remember_member_auth(&request.extensions(), member.id)?;
+    if let Some(suspension) = member.get_suspension() {
+        return Err(Error::from(InternalError::UserSuspended(suspension.reason)).into());
+    }
+
+    if get_config().account.require_verified_email && member.email_verified_at.is_none() {
+        return Err(ApiErrorResponse::new("Please verify your email before logging in").into());
+    }
+
+    let status = super::remember_member_auth(&request, &member, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(status)))
+}
+
+pub async fn post_password_signup(
+    request: HttpRequest,
+    query: web::Json<PostPasswordSignup>,
+    identity: Option<Identity>,
+    db: web::Data<tokio_postgres::Client>,
+) -> WebResult<JsonResponse<String>> {
+    if identity.is_some() {
+        return Err(ApiErrorResponse::new("Already logged in").into());
+    }
+
+    if !get_config().auth.new_users {
+        return Err(ApiErrorResponse::new("New user creation is disabled").into());
+    }
+
+    let PostPasswordSignup {
+        name,
+        email,
+        password,
+    } = query.into_inner();
+
+    let email = email.trim().to_string();
+
+    if !email.contains('@') {
+        return Err(ApiErrorResponse::new("Invalid email").into());
+    }
+
+    validate_password(&password)?;
+
+    if MemberModel::get_by_email(&email, &db).await?.is_some() {
+        return Err(ApiErrorResponse::new("An account with this email already exists").into());
+    }
+
+    let name = name
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| name_from_email(&email));
+
+    let member = NewMemberModel {
+        name,
+        email: Some(email.clone()),
+        password: Some(hash_password(&password)?),
+        permissions: Permissions::basic(),
+        localsettings: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        email_verified_at: None,
+    }
+    .insert(&db)
+    .await?;
+
+    if let Some(base_url) = get_base_url() {
+        send_verification_email(member.id, &email, &base_url, &db).await?;
+    }
+
+    if !get_config().account.require_verified_email {
+        // New members are never admins so this always completes.
+        super::remember_member_auth(&request, &member, &db).await?;
+    }
 
     Ok(web::Json(WrappingResponse::okay(String::from("success"))))
 }
 
+pub fn validate_password(value: &str) -> WebResult<()> {
+    if value.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(ApiErrorResponse::new("Password must be at least 8 characters").into());
+    }
+
+    Ok(())
+}
+
+pub fn hash_password(value: &str) -> Result<String> {
+    Ok(bcrypt::hash(value, bcrypt::DEFAULT_COST)?)
+}
+
+/// Returns false if the member doesn't have a password.
+pub fn verify_password(value: &str, member: &MemberModel) -> Result<bool> {
+    match member.password.as_deref() {
+        Some(hash) => Ok(bcrypt::verify(value, hash)?),
+        None => Ok(false),
+    }
+}
+
+/// Uses the part before the @ as a display name.
+pub fn name_from_email(email: &str) -> String {
+    email.split('@').next().unwrap_or(email).to_string()
+}
+
 pub fn gen_sample_alphanumeric(amount: usize, rng: &mut ThreadRng) -> String {
     rng.sample_iter(rand::distributions::Alphanumeric)
         .take(amount)
@@ -1,29 +1,32 @@
 // You supply an email. We email the link to authenticate with.
-
-// TODO: Better security. Simple Proof of Concept.
+// The link is single-use, expires and only works for the email it was sent to.
 
 use actix_identity::Identity;
 use actix_web::{http::header, HttpResponse};
-use actix_web::{web, HttpRequest, HttpMessage};
+use actix_web::{web, HttpRequest};
 use common::api::{ApiErrorResponse, WrappingResponse};
-use common_local::{ConfigEmail, Permissions};
+use common_local::{api::LoginStatus, ConfigEmail, Permissions};
 
 use crate::config::get_config;
-use crate::http::JsonResponse;
+use crate::email::send_email;
+use crate::http::{get_ip_address, JsonResponse};
 use crate::model::{AuthModel, MemberModel, NewMemberModel};
 use crate::{Error, Result, WebResult};
-use chrono::Utc;
-use lettre::message::header::ContentType;
-use lettre::message::{MultiPart, SinglePart};
-use lettre::transport::smtp::authentication::Credentials;
-use lettre::{Message, SmtpTransport, Transport};
+use chrono::{Duration, Utc};
 use rand::prelude::ThreadRng;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use super::account::get_base_url;
+use super::password::name_from_email;
+
 pub static PASSWORDLESS_PATH: &str = "/auth/passwordless";
 pub static PASSWORDLESS_PATH_CB: &str = "/auth/passwordless/response";
 
+/// Frontend pages which finish a login pending two-factor.
+static TWO_FACTOR_PAGE: &str = "/two-factor";
+static TWO_FACTOR_SETUP_PAGE: &str = "/two-factor?setup=true";
+
 #[derive(Serialize, Deserialize)]
 pub struct PostPasswordlessCallback {
     pub email: String,
@@ -41,57 +44,68 @@ pub async fn post_passwordless_oauth(
 
     let config = get_config();
 
-    let (email_config, host) = match (
-        config.email,
-        req.headers().get("host").and_then(|v| v.to_str().ok()),
-    ) {
+    let (email_config, base_url) = match (config.email, get_base_url()) {
         (Some(a), Some(b)) => (a, b),
         _ => {
-            return Err(
-                ApiErrorResponse::new("Missing email from config OR unable to get host").into(),
+            return Err(ApiErrorResponse::new(
+                "Missing email from config OR missing server base_url",
             )
+            .into())
         }
     };
 
-    let proto = config.server.is_secure.then(|| "https").unwrap_or("http");
+    let email = query.0.email.trim().to_string();
 
-    if !get_config().auth.new_users
-        && MemberModel::get_by_email(query.0.email.trim(), &db)
-            .await?
-            .is_none()
-    {
+    if !get_config().auth.new_users && MemberModel::get_by_email(&email, &db).await?.is_none() {
         return Err(ApiErrorResponse::new("New user creation is disabled").into());
     }
 
+    let ip_address = get_ip_address(&req);
+
+    // Throttle requests
+    let limit_since = Utc::now() - Duration::minutes(config.passwordless.limit_window_minutes);
+
+    if AuthModel::count_by_email_since(&email, limit_since, &db).await?
+        >= config.passwordless.max_requests_per_email
+    {
+        return Err(ApiErrorResponse::new("Too many login requests for this email").into());
+    }
+
+    if let Some(ip_address) = ip_address.as_deref() {
+        if AuthModel::count_by_ip_address_since(ip_address, limit_since, &db).await?
+            >= config.passwordless.max_requests_per_ip
+        {
+            return Err(ApiErrorResponse::new("Too many login requests").into());
+        }
+    }
+
     let oauth_token = gen_sample_alphanumeric(32, &mut rand::thread_rng());
 
     let auth_url = format!(
-        "{proto}://{host}{}?{}",
+        "{base_url}{}?{}",
         PASSWORDLESS_PATH_CB,
         serde_urlencoded::to_string(QueryCallback {
             oauth_token: oauth_token.clone(),
-            email: query.email.clone()
+            email: email.clone()
         })
         .map_err(Error::from)?
     );
 
-    let main_html = render_email(
-        proto,
-        host,
-        &email_config.display_name,
-        PASSWORDLESS_PATH_CB,
-    );
+    let main_html = render_email(&base_url, &email_config.display_name, PASSWORDLESS_PATH_CB);
+
+    let now = Utc::now();
 
     let auth = AuthModel {
         oauth_token,
-        // TODO:
-        oauth_token_secret: String::new(),
-        created_at: Utc::now(),
+        email: email.clone(),
+        ip_address,
+        created_at: now,
+        expires_at: now + Duration::minutes(config.passwordless.token_expiry_minutes),
     };
 
     auth.insert(&db).await?;
 
-    send_auth_email(query.0.email, auth_url, main_html, &email_config)?;
+    send_auth_email(email, auth_url, main_html, &email_config)?;
 
     Ok(web::Json(WrappingResponse::okay(String::from("success"))))
 }
@@ -116,30 +130,45 @@ pub async fn get_passwordless_oauth_callback(
 
     let QueryCallback { oauth_token, email } = query.into_inner();
 
-    if AuthModel::remove_by_oauth_token(&oauth_token, &db).await? {
+    let mut location = "/";
+
+    if AuthModel::remove_valid(&oauth_token, &email, &db).await? {
         // Create or Update User.
-        let member = if let Some(value) = MemberModel::get_by_email(&email, &db).await? {
+        // Opening the emailed link also verifies the email.
+        let member = if let Some(mut value) = MemberModel::get_by_email(&email, &db).await? {
+            if value.email_verified_at.is_none() {
+                value.email_verified_at = Some(Utc::now());
+                value.update(&db).await?;
+            }
+
             value
         } else {
             let new_member = NewMemberModel {
-                // TODO: Strip email
-                name: email.clone(),
+                name: name_from_email(&email),
                 email: Some(email),
                 password: None,
                 permissions: Permissions::basic(),
                 localsettings: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                email_verified_at: Some(Utc::now()),
             };
 
             new_member.insert(&db).await?
         };
 
-        super::remember_member_auth(&request.extensions(), member.id)?;
+        // Suspended members are sent back without a session.
+        if member.get_suspension().is_none() {
+            location = match super::remember_member_auth(&request, &member, &db).await? {
+                LoginStatus::Complete => "/",
+                LoginStatus::TwoFactorRequired => TWO_FACTOR_PAGE,
+                LoginStatus::TwoFactorSetupRequired => TWO_FACTOR_SETUP_PAGE,
+            };
+        }
     }
 
     Ok(HttpResponse::Found()
-        .append_header((header::LOCATION, "/"))
+        .append_header((header::LOCATION, location))
         .finish())
 }
 
@@ -149,45 +178,13 @@ pub fn send_auth_email(
     main_html: String,
     email_config: &ConfigEmail,
 ) -> Result<()> {
-    let email = Message::builder()
-        .from(
-            format!(
-                "{} <{}>",
-                email_config.display_name, email_config.sending_email
-            )
-            .parse()?,
-        )
-        .reply_to(email_config.sending_email.parse()?)
-        .to(sending_to_email.parse()?)
-        .subject(&email_config.subject_line)
-        .multipart(
-            MultiPart::alternative() // This is composed of two parts.
-                .singlepart(
-                    SinglePart::builder()
-                        .header(ContentType::TEXT_PLAIN)
-                        .body(alt_text),
-                )
-                .singlepart(
-                    SinglePart::builder()
-                        .header(ContentType::TEXT_HTML)
-                        .body(main_html),
-                ),
-        )?;
-
-    let creds = Credentials::new(
-        email_config.smtp_username.clone(),
-        email_config.smtp_password.clone(),
-    );
-
-    // Open a remote connection to gmail
-    let mailer = SmtpTransport::relay(&email_config.smtp_relay)?
-        .credentials(creds)
-        .build();
-
-    // Send the email
-    mailer.send(&email)?;
-
-    Ok(())
+    send_email(
+        &sending_to_email,
+        &email_config.subject_line,
+        alt_text,
+        main_html,
+        email_config,
+    )
 }
 
 pub fn gen_sample_alphanumeric(amount: usize, rng: &mut ThreadRng) -> String {
@@ -198,8 +195,7 @@ pub fn gen_sample_alphanumeric(amount: usize, rng: &mut ThreadRng) -> String {
 }
 
 fn render_email(
-    website_url_protocol: &str,
-    website_http_base_host: &str,
+    website_base_url: &str,
     email_display_name: &str,
     email_callback_url: &str,
 ) -> String {
@@ -229,7 +225,7 @@ fn render_email(
                                 <div>
                                     <p style="text-align: center; margin-bottom: 30px;">
                                         <img
-                                            src="{website_url_protocol}://{website_http_base_host}/brand.png"
+                                            src="{website_base_url}/brand.png"
                                             width="120"
                                             alt="{email_display_name}"
                                             style="-ms-interpolation-mode: bicubic; border: 0; height: auto; line-height: 100%; outline: none; text-decoration: none;"
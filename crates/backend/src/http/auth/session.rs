@@ -0,0 +1,193 @@
+// Sessions are stored in Postgres so members can see where they're logged in and revoke them.
+// Only the hash of the session key is stored. On login a `SessionInfo` is added to the session
+// state, which is copied into the session's columns whenever it's saved.
+
+use std::collections::HashMap;
+
+use actix_session::{
+    storage::{LoadError, SaveError, SessionKey, SessionStore, UpdateError},
+    Session, SessionExt,
+};
+use actix_web::{cookie::time::Duration, http::header, web, HttpRequest};
+use chrono::{DateTime, TimeZone, Utc};
+use common::MemberId;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Client;
+
+use crate::{
+    http::get_ip_address,
+    model::{MemberSessionLogin, MemberSessionModel},
+    Error, Result,
+};
+
+use super::passwordless::gen_sample_alphanumeric;
+
+static SESSION_INFO_KEY: &str = "session-info";
+
+const SESSION_KEY_LENGTH: usize = 64;
+const LOGIN_ID_LENGTH: usize = 32;
+
+#[derive(Serialize, Deserialize)]
+struct SessionInfo {
+    login_id: String,
+    member_id: MemberId,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: i64,
+}
+
+/// Called on login so the session can be found by its member.
+pub(super) fn store_session_info(req: &HttpRequest, member_id: MemberId) -> Result<()> {
+    let user_agent = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    let ip_address = get_ip_address(req);
+
+    req.get_session().insert(
+        SESSION_INFO_KEY,
+        SessionInfo {
+            login_id: gen_sample_alphanumeric(LOGIN_ID_LENGTH, &mut rand::thread_rng()),
+            member_id,
+            user_agent,
+            ip_address,
+            created_at: Utc::now().timestamp_millis(),
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Returns the login ID of the current session. Used to mark it in the session list.
+pub fn get_login_id(session: &Session) -> Result<Option<String>> {
+    Ok(session
+        .get::<SessionInfo>(SESSION_INFO_KEY)?
+        .map(|v| v.login_id))
+}
+
+pub struct PostgresSessionStore {
+    db: web::Data<Client>,
+}
+
+impl PostgresSessionStore {
+    pub fn new(db: web::Data<Client>) -> Self {
+        Self { db }
+    }
+
+    /// Inserts a new session, or updates the existing one. Returns the amount of rows changed.
+    async fn store(
+        &self,
+        key: &str,
+        state: HashMap<String, String>,
+        ttl: &Duration,
+        is_new: bool,
+    ) -> Result<u64> {
+        let login = state
+            .get(SESSION_INFO_KEY)
+            .and_then(|v| serde_json::from_str::<SessionInfo>(v).ok())
+            .map(|v| MemberSessionLogin {
+                member_id: v.member_id,
+                login_id: v.login_id,
+                user_agent: v.user_agent,
+                ip_address: v.ip_address,
+                created_at: Utc
+                    .timestamp_millis_opt(v.created_at)
+                    .single()
+                    .unwrap_or_else(Utc::now),
+            });
+
+        let state = serde_json::to_string(&state)?;
+
+        if is_new {
+            MemberSessionModel::save(key, &state, login, get_expires_at(ttl), &self.db).await
+        } else {
+            MemberSessionModel::update(key, &state, login, get_expires_at(ttl), &self.db).await
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SessionStore for PostgresSessionStore {
+    async fn load(
+        &self,
+        session_key: &SessionKey,
+    ) -> std::result::Result<Option<HashMap<String, String>>, LoadError> {
+        let session = MemberSessionModel::find_valid_by_key(session_key.as_ref(), &self.db)
+            .await
+            .map_err(|e| LoadError::Other(to_anyhow(e)))?;
+
+        match session {
+            Some(v) => Ok(Some(
+                serde_json::from_str(&v.state).map_err(|e| LoadError::Deserialization(e.into()))?,
+            )),
+
+            None => Ok(None),
+        }
+    }
+
+    async fn save(
+        &self,
+        session_state: HashMap<String, String>,
+        ttl: &Duration,
+    ) -> std::result::Result<SessionKey, SaveError> {
+        let key = gen_sample_alphanumeric(SESSION_KEY_LENGTH, &mut rand::thread_rng());
+
+        self.store(&key, session_state, ttl, true)
+            .await
+            .map_err(|e| SaveError::Other(to_anyhow(e)))?;
+
+        SessionKey::try_from(key).map_err(|e| SaveError::Other(e.into()))
+    }
+
+    async fn update(
+        &self,
+        session_key: SessionKey,
+        session_state: HashMap<String, String>,
+        ttl: &Duration,
+    ) -> std::result::Result<SessionKey, UpdateError> {
+        let updated = self
+            .store(session_key.as_ref(), session_state, ttl, false)
+            .await
+            .map_err(|e| UpdateError::Other(to_anyhow(e)))?;
+
+        // The session was revoked while the request was running. Don't bring it back.
+        if updated == 0 {
+            return Err(UpdateError::Other(anyhow::anyhow!(
+                "Session no longer exists"
+            )));
+        }
+
+        Ok(session_key)
+    }
+
+    async fn update_ttl(&self, session_key: &SessionKey, ttl: &Duration) -> anyhow::Result<()> {
+        let updated =
+            MemberSessionModel::update_expiry(session_key.as_ref(), get_expires_at(ttl), &self.db)
+                .await
+                .map_err(to_anyhow)?;
+
+        if updated == 0 {
+            anyhow::bail!("Session no longer exists");
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, session_key: &SessionKey) -> anyhow::Result<()> {
+        MemberSessionModel::remove_by_key(session_key.as_ref(), &self.db)
+            .await
+            .map_err(to_anyhow)?;
+
+        Ok(())
+    }
+}
+
+fn get_expires_at(ttl: &Duration) -> DateTime<Utc> {
+    Utc::now() + chrono::Duration::seconds(ttl.whole_seconds())
+}
+
+fn to_anyhow(value: Error) -> anyhow::Error {
+    anyhow::anyhow!("{value}")
+}
@@ -0,0 +1,293 @@
+// TOTP two-factor authentication.
+// Once the first factor is accepted the member is stored in the session as pending until a code
+// is verified. Managing two-factor is only possible with a session, not an API token.
+
+use actix_session::{Session, SessionExt};
+use actix_web::{web, HttpRequest};
+use chrono::{Duration, Utc};
+use common::{
+    api::{ApiErrorResponse, WrappingResponse},
+    MemberId,
+};
+use common_local::api::{self, LoginStatus};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Client;
+
+use crate::{
+    config::get_config,
+    http::JsonResponse,
+    model::{MemberModel, MemberRecoveryCodeModel, MemberTotpModel, NewMemberTotpModel},
+    Result, WebResult,
+};
+
+use super::{complete_member_auth, MemberCookie};
+
+pub static TWO_FACTOR_PATH: &str = "/auth/2fa";
+pub static TWO_FACTOR_SETUP_PATH: &str = "/auth/2fa/setup";
+pub static TWO_FACTOR_ENABLE_PATH: &str = "/auth/2fa/enable";
+pub static TWO_FACTOR_VERIFY_PATH: &str = "/auth/2fa/verify";
+pub static TWO_FACTOR_DISABLE_PATH: &str = "/auth/2fa/disable";
+pub static TWO_FACTOR_RECOVERY_PATH: &str = "/auth/2fa/recovery";
+
+static PENDING_SESSION_KEY: &str = "pending-two-factor";
+
+/// Minutes a member has to enter their code after the first factor.
+const PENDING_EXPIRY_MINUTES: i64 = 10;
+/// Invalid codes allowed before the first factor has to be entered again.
+const MAX_PENDING_ATTEMPTS: usize = 5;
+
+#[derive(Serialize, Deserialize)]
+struct PendingTwoFactor {
+    member_id: MemberId,
+    stored_since: i64,
+    attempts: usize,
+}
+
+pub(super) fn set_pending(req: &HttpRequest, member_id: MemberId) -> Result<()> {
+    req.get_session().insert(
+        PENDING_SESSION_KEY,
+        PendingTwoFactor {
+            member_id,
+            stored_since: Utc::now().timestamp_millis(),
+            attempts: 0,
+        },
+    )?;
+
+    Ok(())
+}
+
+fn get_pending(session: &Session) -> Result<Option<PendingTwoFactor>> {
+    let pending = session.get::<PendingTwoFactor>(PENDING_SESSION_KEY)?;
+
+    let expired_before =
+        (Utc::now() - Duration::minutes(PENDING_EXPIRY_MINUTES)).timestamp_millis();
+
+    match pending {
+        Some(v) if v.stored_since < expired_before => {
+            session.remove(PENDING_SESSION_KEY);
+
+            Ok(None)
+        }
+
+        v => Ok(v),
+    }
+}
+
+/// Returns the member which is logged in or, if allowed, pending two-factor.
+fn get_member_id(
+    member: Option<MemberCookie>,
+    session: &Session,
+    allow_pending: bool,
+) -> WebResult<(MemberId, bool)> {
+    match member {
+        Some(member) if member.is_api_token() => {
+            Err(ApiErrorResponse::new("Two-factor requires a session").into())
+        }
+
+        Some(member) => Ok((member.member_id(), false)),
+
+        None if allow_pending => match get_pending(session)? {
+            Some(pending) => Ok((pending.member_id, true)),
+            None => Err(ApiErrorResponse::new("unauthorized").into()),
+        },
+
+        None => Err(ApiErrorResponse::new("unauthorized").into()),
+    }
+}
+
+/// Accepts either a TOTP code or one of the member's recovery codes.
+async fn verify_code_or_recovery(
+    totp: &mut MemberTotpModel,
+    code: &str,
+    db: &Client,
+) -> Result<bool> {
+    Ok(totp.verify_code(code, db).await?
+        || MemberRecoveryCodeModel::take(totp.member_id, code, db).await?)
+}
+
+async fn find_enabled_totp(member_id: MemberId, db: &Client) -> WebResult<MemberTotpModel> {
+    match MemberTotpModel::find_by_member_id(member_id, db).await? {
+        Some(v) if v.is_enabled() => Ok(v),
+        _ => Err(ApiErrorResponse::new("Two-factor is not enabled").into()),
+    }
+}
+
+pub async fn get_two_factor_status(
+    member: MemberCookie,
+    session: Session,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::TwoFactorStatusResponse>> {
+    let (member_id, _) = get_member_id(Some(member), &session, false)?;
+
+    let enabled = MemberTotpModel::is_enabled_for_member(member_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::TwoFactorStatusResponse {
+            enabled,
+            recovery_codes_remaining: if enabled {
+                MemberRecoveryCodeModel::count_by_member_id(member_id, &db).await?
+            } else {
+                0
+            },
+        },
+    )))
+}
+
+/// Creates a new secret. It's only used once enabled with a code from it.
+pub async fn post_two_factor_setup(
+    member: Option<MemberCookie>,
+    session: Session,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::TwoFactorSetupResponse>> {
+    let (member_id, _) = get_member_id(member, &session, true)?;
+
+    if MemberTotpModel::is_enabled_for_member(member_id, &db).await? {
+        return Err(ApiErrorResponse::new("Two-factor is already enabled").into());
+    }
+
+    let member = match MemberModel::get_by_id(member_id, &db).await? {
+        Some(v) => v,
+        None => return Err(ApiErrorResponse::new("Unable to find member").into()),
+    };
+
+    let totp_model = NewMemberTotpModel::generate(member_id).insert(&db).await?;
+
+    let totp = totp_model.get_totp(member.email.unwrap_or(member.name))?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::TwoFactorSetupResponse {
+            secret: totp_model.secret,
+            otpauth_url: totp.get_url(),
+            qr_code: totp.get_qr_base64().ok(),
+        },
+    )))
+}
+
+/// Enables two-factor with a code from the secret created in setup. Completes a pending login.
+pub async fn post_two_factor_enable(
+    req: HttpRequest,
+    body: web::Json<api::TwoFactorCodeBody>,
+    member: Option<MemberCookie>,
+    session: Session,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::TwoFactorRecoveryCodesResponse>> {
+    let (member_id, is_pending) = get_member_id(member, &session, true)?;
+
+    let mut totp = match MemberTotpModel::find_by_member_id(member_id, &db).await? {
+        Some(v) if !v.is_enabled() => v,
+        _ => return Err(ApiErrorResponse::new("Two-factor setup wasn't started").into()),
+    };
+
+    if !totp.verify_code(&body.code, &db).await? {
+        if is_pending {
+            record_failed_attempt(&session)?;
+        }
+
+        return Err(ApiErrorResponse::new("Invalid code").into());
+    }
+
+    totp.set_enabled(&db).await?;
+
+    let recovery_codes = MemberRecoveryCodeModel::regenerate(member_id, &db).await?;
+
+    if is_pending {
+        session.remove(PENDING_SESSION_KEY);
+
+        complete_member_auth(&req, member_id)?;
+    }
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::TwoFactorRecoveryCodesResponse { recovery_codes },
+    )))
+}
+
+/// Completes a pending login.
+pub async fn post_two_factor_verify(
+    req: HttpRequest,
+    body: web::Json<api::TwoFactorCodeBody>,
+    session: Session,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<LoginStatus>> {
+    let (member_id, _) = get_member_id(None, &session, true)?;
+
+    let mut totp = find_enabled_totp(member_id, &db).await?;
+
+    if !verify_code_or_recovery(&mut totp, &body.code, &db).await? {
+        record_failed_attempt(&session)?;
+
+        return Err(ApiErrorResponse::new("Invalid code").into());
+    }
+
+    session.remove(PENDING_SESSION_KEY);
+
+    complete_member_auth(&req, member_id)?;
+
+    Ok(web::Json(WrappingResponse::okay(LoginStatus::Complete)))
+}
+
+pub async fn post_two_factor_disable(
+    body: web::Json<api::TwoFactorCodeBody>,
+    member: MemberCookie,
+    session: Session,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<String>> {
+    let (member_id, _) = get_member_id(Some(member), &session, false)?;
+
+    if get_config().account.require_admin_two_factor {
+        let is_admin = MemberModel::get_by_id(member_id, &db)
+            .await?
+            .map(|v| v.permissions.is_admin())
+            .unwrap_or_default();
+
+        if is_admin {
+            return Err(ApiErrorResponse::new("Two-factor is required for admins").into());
+        }
+    }
+
+    let mut totp = find_enabled_totp(member_id, &db).await?;
+
+    if !verify_code_or_recovery(&mut totp, &body.code, &db).await? {
+        return Err(ApiErrorResponse::new("Invalid code").into());
+    }
+
+    MemberTotpModel::remove_by_member_id(member_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(String::from("success"))))
+}
+
+/// Replaces the member's recovery codes.
+pub async fn post_two_factor_recovery(
+    body: web::Json<api::TwoFactorCodeBody>,
+    member: MemberCookie,
+    session: Session,
+    db: web::Data<Client>,
+) -> WebResult<JsonResponse<api::TwoFactorRecoveryCodesResponse>> {
+    let (member_id, _) = get_member_id(Some(member), &session, false)?;
+
+    let mut totp = find_enabled_totp(member_id, &db).await?;
+
+    if !verify_code_or_recovery(&mut totp, &body.code, &db).await? {
+        return Err(ApiErrorResponse::new("Invalid code").into());
+    }
+
+    let recovery_codes = MemberRecoveryCodeModel::regenerate(member_id, &db).await?;
+
+    Ok(web::Json(WrappingResponse::okay(
+        api::TwoFactorRecoveryCodesResponse { recovery_codes },
+    )))
+}
+
+/// The pending login is removed once too many invalid codes were entered.
+fn record_failed_attempt(session: &Session) -> Result<()> {
+    if let Some(mut pending) = get_pending(session)? {
+        pending.attempts += 1;
+
+        if pending.attempts >= MAX_PENDING_ATTEMPTS {
+            session.remove(PENDING_SESSION_KEY);
+        } else {
+            session.insert(PENDING_SESSION_KEY, pending)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,365 @@
+//! Authentication and rate limiting for the servers linked to us.
+//!
+//! A signed request passes the headers below. The signature is the hex encoded HMAC-SHA256,
+//! keyed with the `server_id`, of
+//! `"{METHOD}\n{path}\n{query_string}\n{timestamp}\n{nonce}\n{body}\n{member_id}"`
+//! where `body` is the hex encoded SHA-256 of the request body.
+//!
+//! Servers a member granted access to can act on their behalf by also passing their member ID.
+//! It's empty otherwise.
+
+use std::rc::Rc;
+
+use actix_web::{
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    web::Bytes,
+    HttpMessage, HttpRequest,
+};
+use chrono::Utc;
+use common::MemberId;
+use futures::future::{ready, LocalBoxFuture, Ready};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use tokio_postgres::Client;
+
+use crate::{
+    config::get_config,
+    model::{ServerLinkGrantModel, ServerLinkModel, ServerLinkNonceModel, ServerLinkQuotaModel},
+    WebError, WebResult,
+};
+
+pub const HEADER_PUBLIC_ID: &str = "X-Librarian-Public-Id";
+/// Unix timestamp in seconds.
+pub const HEADER_TIMESTAMP: &str = "X-Librarian-Timestamp";
+/// Has to be unique for every request.
+pub const HEADER_NONCE: &str = "X-Librarian-Nonce";
+pub const HEADER_SIGNATURE: &str = "X-Librarian-Signature";
+/// The member a signed request acts on behalf of.
+pub const HEADER_MEMBER_ID: &str = "X-Librarian-Member-Id";
+
+const MAX_NONCE_LENGTH: usize = 64;
+
+/// Returns the linked server making the request, or the reason it was rejected.
+///
+/// Falls back to the plain text `server_id` for unsigned requests unless signatures are required.
+/// Errors if the server exceeded its rate limits.
+pub async fn authenticate_server(
+    req: &HttpRequest,
+    server_id: Option<&str>,
+    db: &Client,
+) -> WebResult<std::result::Result<ServerLinkModel, &'static str>> {
+    let link = if let Some(public_id) = get_header(req, HEADER_PUBLIC_ID) {
+        let link = match ServerLinkModel::get_by_public_id(public_id, db).await? {
+            Some(v) => v,
+            None => return Ok(Err("Invalid Public ID")),
+        };
+
+        if let Err(e) = verify_signature(req, &link, db).await? {
+            return Ok(Err(e));
+        }
+
+        link
+    } else if get_config().linked_servers.require_signature {
+        return Ok(Err("Signature Required"));
+    } else {
+        let server_id = match server_id {
+            Some(v) => v,
+            None => return Ok(Err("Invalid Server ID")),
+        };
+
+        match ServerLinkModel::get_by_server_id(server_id, db).await? {
+            Some(v) => v,
+            None => return Ok(Err("Invalid Server ID")),
+        }
+    };
+
+    ServerLinkModel::update_last_seen(link.id, db).await?;
+
+    check_rate_limit(&link, db).await?;
+
+    Ok(Ok(link))
+}
+
+/// Returns the grant of the member the request acts on behalf of, or None if it isn't.
+///
+/// Only signed requests from verified servers are accepted.
+pub async fn authenticate_member_grant(
+    req: &HttpRequest,
+    db: &Client,
+) -> WebResult<Option<std::result::Result<ServerLinkGrantModel, &'static str>>> {
+    let member_id = match get_header(req, HEADER_MEMBER_ID) {
+        Some(v) => match v.trim().parse::<usize>() {
+            Ok(v) => MemberId::from(v),
+            Err(_) => return Ok(Some(Err("Invalid Member ID"))),
+        },
+        None => return Ok(None),
+    };
+
+    if get_header(req, HEADER_PUBLIC_ID).is_none() {
+        return Ok(Some(Err("Signature Required")));
+    }
+
+    let link = match authenticate_server(req, None, db).await? {
+        Ok(v) if v.verified => v,
+        Ok(_) => return Ok(Some(Err("Server is not verified"))),
+        Err(e) => return Ok(Some(Err(e))),
+    };
+
+    match ServerLinkGrantModel::get_by_server_link_and_member(link.id, member_id, db).await? {
+        Some(grant) if grant.member_id == member_id => {
+            ServerLinkGrantModel::update_last_used(grant.id, db).await?;
+
+            Ok(Some(Ok(grant)))
+        }
+
+        _ => Ok(Some(Err("Member has not granted this server access"))),
+    }
+}
+
+async fn verify_signature(
+    req: &HttpRequest,
+    link: &ServerLinkModel,
+    db: &Client,
+) -> WebResult<std::result::Result<(), &'static str>> {
+    let (timestamp, nonce, signature) = match (
+        get_header(req, HEADER_TIMESTAMP),
+        get_header(req, HEADER_NONCE),
+        get_header(req, HEADER_SIGNATURE).and_then(decode_hex),
+    ) {
+        (Some(a), Some(b), Some(c)) => (a, b, c),
+        _ => return Ok(Err("Invalid Signature")),
+    };
+
+    if nonce.is_empty() || nonce.len() > MAX_NONCE_LENGTH {
+        return Ok(Err("Invalid Nonce"));
+    }
+
+    let max_age = get_config().linked_servers.signature_max_age_secs;
+
+    match timestamp.parse::<i64>() {
+        Ok(v) if (Utc::now().timestamp() - v).abs() <= max_age => {}
+        _ => return Ok(Err("Expired Signature")),
+    }
+
+    let body_hash = match req.extensions().get::<SignedBodyHash>() {
+        Some(v) => v.0.clone(),
+        None => return Ok(Err("Invalid Signature")),
+    };
+
+    if !is_valid_signature(
+        &link.server_id,
+        &canonical_request(req, timestamp, nonce, &body_hash),
+        &signature,
+    ) {
+        return Ok(Err("Invalid Signature"));
+    }
+
+    // Only stored once the signature is valid so others can't use up a server's nonces.
+    if !ServerLinkNonceModel::insert(link.id, nonce, db).await? {
+        return Ok(Err("Reused Nonce"));
+    }
+
+    Ok(Ok(()))
+}
+
+/// The string a linked server signs for the request.
+fn canonical_request(req: &HttpRequest, timestamp: &str, nonce: &str, body_hash: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n{timestamp}\n{nonce}\n{body_hash}\n{}",
+        req.method(),
+        req.path(),
+        req.query_string(),
+        get_header(req, HEADER_MEMBER_ID).unwrap_or_default()
+    )
+}
+
+fn is_valid_signature(server_id: &str, message: &str, signature: &[u8]) -> bool {
+    let mut mac = match Hmac::<Sha256>::new_from_slice(server_id.as_bytes()) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+
+    mac.update(message.as_bytes());
+
+    mac.verify_slice(signature).is_ok()
+}
+
+async fn check_rate_limit(link: &ServerLinkModel, db: &Client) -> WebResult<()> {
+    let config = get_config().linked_servers;
+
+    let quota = ServerLinkQuotaModel::increment(link.id, db).await?;
+
+    let per_minute = quota
+        .requests_per_minute
+        .unwrap_or(config.requests_per_minute);
+    let per_day = quota.requests_per_day.unwrap_or(config.requests_per_day);
+
+    let retry_at = if per_day != 0 && quota.day_requests > per_day {
+        quota.day_ends_at()
+    } else if per_minute != 0 && quota.minute_requests > per_minute {
+        quota.minute_ends_at()
+    } else {
+        return Ok(());
+    };
+
+    ServerLinkQuotaModel::increment_throttled(link.id, db).await?;
+
+    Err(WebError::RateLimited(
+        (retry_at - Utc::now()).num_seconds().max(1),
+    ))
+}
+
+fn get_header<'a>(req: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    req.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 != 0 || !value.bytes().all(|v| v.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn encode_hex(value: &[u8]) -> String {
+    value.iter().map(|v| format!("{:02x}", v)).collect()
+}
+
+/// Hex encoded SHA-256 of the body a signed request was received with.
+#[derive(Clone)]
+struct SignedBodyHash(String);
+
+/// Hashes the body of signed requests so the signature can cover it.
+///
+/// The body is buffered and handed back to the request so handlers still receive it.
+pub struct SignedBody;
+
+impl<S, B> Transform<S, ServiceRequest> for SignedBody
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = SignedBodyMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<std::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(SignedBodyMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct SignedBodyMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for SignedBodyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, std::result::Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            if req.headers().contains_key(HEADER_SIGNATURE) {
+                let body = req.extract::<Bytes>().await?;
+
+                req.extensions_mut()
+                    .insert(SignedBodyHash(encode_hex(&Sha256::digest(&body))));
+                req.set_payload(Payload::from(body));
+            }
+
+            service.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::Method, test::TestRequest};
+
+    use super::*;
+
+    fn sign(server_id: &str, message: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(server_id.as_bytes()).unwrap();
+        mac.update(message.as_bytes());
+        encode_hex(&mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex(""), Some(Vec::new()));
+        assert_eq!(decode_hex("00ff7A"), Some(vec![0x00, 0xff, 0x7a]));
+        assert_eq!(decode_hex(&encode_hex(&[1, 2, 254])), Some(vec![1, 2, 254]));
+
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("+1"), None);
+        assert_eq!(decode_hex("é"), None);
+    }
+
+    #[test]
+    fn test_canonical_request() {
+        let req = TestRequest::default()
+            .method(Method::POST)
+            .uri("/edit/1?a=b")
+            .insert_header((HEADER_MEMBER_ID, "5"))
+            .to_http_request();
+
+        assert_eq!(
+            canonical_request(&req, "100", "nonce", "hash"),
+            "POST\n/edit/1\na=b\n100\nnonce\nhash\n5"
+        );
+
+        // Requests which don't act on behalf of a member sign an empty member ID.
+        let req = TestRequest::get().uri("/search/book").to_http_request();
+
+        assert_eq!(
+            canonical_request(&req, "100", "nonce", "hash"),
+            "GET\n/search/book\n\n100\nnonce\nhash\n"
+        );
+    }
+
+    #[test]
+    fn test_is_valid_signature() {
+        let message = "POST\n/edit/1\n\n100\nnonce\nhash\n5";
+        let signature = decode_hex(&sign("server-id", message)).unwrap();
+
+        assert!(is_valid_signature("server-id", message, &signature));
+
+        // Signed with another server's ID.
+        assert!(!is_valid_signature("other-id", message, &signature));
+
+        // Any change to the signed values, like the body or member ID, invalidates it.
+        assert!(!is_valid_signature(
+            "server-id",
+            "POST\n/edit/1\n\n100\nnonce\nhash\n6",
+            &signature
+        ));
+        assert!(!is_valid_signature(
+            "server-id",
+            "POST\n/edit/1\n\n100\nnonce\nother\n5",
+            &signature
+        ));
+
+        assert!(!is_valid_signature("server-id", message, &[]));
+        assert!(!is_valid_signature(
+            "server-id",
+            message,
+            &signature[..signature.len() - 1]
+        ));
+    }
+}
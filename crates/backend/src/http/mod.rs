@@ -1,26 +1,41 @@
 use std::time::Duration;
 
 use actix_identity::{Identity, IdentityMiddleware};
+use actix_session::config::{PersistentSession, TtlExtensionPolicy};
 use actix_session::SessionMiddleware;
-use actix_session::storage::CookieSessionStore;
-use actix_web::cookie::Key;
+use actix_web::cookie::{time, Key};
 use actix_web::http::header;
 use actix_web::middleware::Logger;
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use actix_web::{cookie::SameSite, web, App, HttpServer};
 use common::api::WrappingResponse;
 
 use crate::config::get_config;
+use crate::http::session::PostgresSessionStore;
 use crate::CliArgs;
 
 mod api;
 mod auth;
+mod linked_server;
 mod search;
-pub use self::api::api_route;
+pub use self::api::{api_route, ws::send_notification};
 pub use self::auth::*;
 
 pub type JsonResponse<V> = web::Json<WrappingResponse<V>>;
 
+/// Returns the client's IP address. Forwarded headers are only used behind a trusted proxy.
+pub fn get_ip_address(req: &HttpRequest) -> Option<String> {
+    let peer_ip = req.peer_addr()?.ip();
+
+    if get_config().proxy.trusted_proxies.contains(&peer_ip) {
+        req.connection_info()
+            .realip_remote_addr()
+            .map(|v| v.to_string())
+    } else {
+        Some(peer_ip.to_string())
+    }
+}
+
 // TODO: Convert to async closure (https://github.com/rust-lang/rust/issues/62290)
 async fn default_handler() -> impl actix_web::Responder {
     actix_files::NamedFile::open_async("./app/public/dist/index.html").await
@@ -42,17 +57,29 @@ pub async fn register_http_service(
         App::new()
             .app_data(db_data.clone())
             .wrap(Logger::default())
+            .wrap(linked_server::SignedBody)
             .wrap(
                 IdentityMiddleware::builder()
-                    .login_deadline(Some(Duration::from_secs(60 * 60 * 24 * 365)))
+                    .login_deadline(Some(Duration::from_secs(
+                        60 * 60 * 24 * get_config().session.max_age_days as u64,
+                    )))
                     .build(),
             )
             .wrap(
-                SessionMiddleware::builder(CookieSessionStore::default(), Key::from(get_config().auth.auth_key.as_bytes()))
-                    .cookie_name(String::from("librarian-auth"))
-                    .cookie_secure(false)
-                    .cookie_same_site(SameSite::Strict)
-                    .build(),
+                SessionMiddleware::builder(
+                    PostgresSessionStore::new(db_data.clone()),
+                    Key::from(get_config().auth.auth_key.as_bytes()),
+                )
+                .cookie_name(String::from("librarian-auth"))
+                .cookie_secure(false)
+                .cookie_same_site(SameSite::Strict)
+                // Extended on every request so idle sessions expire.
+                .session_lifecycle(
+                    PersistentSession::default()
+                        .session_ttl(time::Duration::days(get_config().session.idle_timeout_days))
+                        .session_ttl_extension_policy(TtlExtensionPolicy::OnEveryRequest),
+                )
+                .build(),
             )
             .service(search::public_search_book)
             .service(search::public_search_author)
@@ -64,6 +91,48 @@ pub async fn register_http_service(
                 password::PASSWORD_PATH,
                 web::post().to(password::post_password_oauth),
             )
+            .route(
+                password::PASSWORD_SIGNUP_PATH,
+                web::post().to(password::post_password_signup),
+            )
+            // Account
+            .route(
+                account::VERIFY_EMAIL_PATH,
+                web::get().to(account::get_verify_email),
+            )
+            .route(
+                account::PASSWORD_RESET_PATH,
+                web::post().to(account::post_password_reset),
+            )
+            .route(
+                account::PASSWORD_RESET_CONFIRM_PATH,
+                web::post().to(account::post_password_reset_confirm),
+            )
+            // Two-Factor
+            .route(
+                two_factor::TWO_FACTOR_PATH,
+                web::get().to(two_factor::get_two_factor_status),
+            )
+            .route(
+                two_factor::TWO_FACTOR_SETUP_PATH,
+                web::post().to(two_factor::post_two_factor_setup),
+            )
+            .route(
+                two_factor::TWO_FACTOR_ENABLE_PATH,
+                web::post().to(two_factor::post_two_factor_enable),
+            )
+            .route(
+                two_factor::TWO_FACTOR_VERIFY_PATH,
+                web::post().to(two_factor::post_two_factor_verify),
+            )
+            .route(
+                two_factor::TWO_FACTOR_DISABLE_PATH,
+                web::post().to(two_factor::post_two_factor_disable),
+            )
+            .route(
+                two_factor::TWO_FACTOR_RECOVERY_PATH,
+                web::post().to(two_factor::post_two_factor_recovery),
+            )
             // Passwordless
             .route(
                 passwordless::PASSWORDLESS_PATH,
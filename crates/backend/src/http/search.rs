@@ -1,30 +1,31 @@
 use actix_web::{get, web, HttpRequest};
 use common::{
     api::{
-        librarian::{GetSearchQuery, PublicSearchResponse, PublicSearchType},
+        librarian::{PublicSearchResponse, PublicSearchType},
         QueryListResponse, WrappingResponse,
     },
     BookId, PersonId,
 };
-use common_local::api::{OrderBy, QueryType};
+use common_local::api::{OrderBy, PublicSearchQuery, QueryType};
 
 use crate::{
     model::{
         BookModel, NewSearchGroupModel, NewSearchItemServerModel, PersonAltModel, PersonModel,
-        ServerLinkModel,
     },
     Error, WebResult,
 };
 
+use super::linked_server::authenticate_server;
+
 #[get("/search/book")]
 pub async fn public_search_book(
     req: HttpRequest,
-    query: web::Query<GetSearchQuery>,
+    query: web::Query<PublicSearchQuery>,
     db: web::Data<tokio_postgres::Client>,
 ) -> WebResult<web::Json<PublicSearchResponse>> {
     const ID_CHECK: &str = "id:";
 
-    let GetSearchQuery {
+    let PublicSearchQuery {
         query,
         offset,
         limit,
@@ -33,9 +34,9 @@ pub async fn public_search_book(
         with_isbn,
     } = query.into_inner();
 
-    let sever_link_model = match ServerLinkModel::get_by_server_id(&server_id, &db).await? {
-        Some(v) => v,
-        None => return Ok(web::Json(WrappingResponse::error("Invalid Server ID"))),
+    let sever_link_model = match authenticate_server(&req, server_id.as_deref(), &db).await? {
+        Ok(v) => v,
+        Err(e) => return Ok(web::Json(WrappingResponse::error(e))),
     };
 
     let host = format!("//{}", req.headers().get("host").unwrap().to_str().unwrap());
@@ -47,8 +48,11 @@ pub async fn public_search_book(
                 .map_err(Error::from)?,
         );
 
-        if let Some(model) = BookModel::get_by_id(book_id, &db).await? {
-            let author_ids = PersonModel::get_all_by_book_id(book_id, &db)
+        if let Some(model) = BookModel::get_by_id_or_redirect(book_id, &db)
+            .await?
+            .filter(|v| v.deleted_at.is_none())
+        {
+            let author_ids = PersonModel::get_all_by_book_id(model.id, &db)
                 .await?
                 .into_iter()
                 .map(|v| *v.id)
@@ -124,14 +128,14 @@ pub async fn public_search_book(
 #[get("/search/author")]
 pub async fn public_search_author(
     req: HttpRequest,
-    query: web::Query<GetSearchQuery>,
+    query: web::Query<PublicSearchQuery>,
     db: web::Data<tokio_postgres::Client>,
 ) -> WebResult<web::Json<PublicSearchResponse>> {
     const ID_CHECK: &str = "id:";
 
-    let sever_link_model = match ServerLinkModel::get_by_server_id(&query.server_id, &db).await? {
-        Some(v) => v,
-        None => return Ok(web::Json(WrappingResponse::error("Invalid Server ID"))),
+    let sever_link_model = match authenticate_server(&req, query.server_id.as_deref(), &db).await? {
+        Ok(v) => v,
+        Err(e) => return Ok(web::Json(WrappingResponse::error(e))),
     };
 
     let host = format!("//{}", req.headers().get("host").unwrap().to_str().unwrap());
@@ -143,7 +147,7 @@ pub async fn public_search_author(
                 .map_err(Error::from)?,
         );
 
-        if let Some(model) = PersonModel::get_by_id(book_id, &db).await? {
+        if let Some(model) = PersonModel::get_by_id_or_redirect(book_id, &db).await? {
             let other_names = PersonAltModel::find_all_by_person_id(model.id, &db)
                 .await?
                 .into_iter()
@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{
+    api_token::{ApiTokenScopes, SharedApiToken},
+    ApiTokenId,
+};
+use tokio_postgres::Client;
+
+use crate::{hash_token, Result};
+
+use super::{row_int_to_usize, AdvRow, TableRow};
+
+pub struct NewApiTokenModel {
+    pub member_id: MemberId,
+
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: ApiTokenScopes,
+
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A named token which authenticates as the member with the `Authorization: Bearer` header.
+///
+/// Only the SHA-256 hash of the token is stored.
+#[derive(Debug, Clone)]
+pub struct ApiTokenModel {
+    pub id: ApiTokenId,
+
+    pub member_id: MemberId,
+
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: ApiTokenScopes,
+
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableRow for ApiTokenModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next()?,
+
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+
+            name: row.next()?,
+            token_hash: row.next()?,
+            scopes: row.next()?,
+
+            last_used_at: row.next()?,
+            expires_at: row.next()?,
+            created_at: row.next()?,
+        })
+    }
+}
+
+impl From<ApiTokenModel> for SharedApiToken {
+    fn from(value: ApiTokenModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            scopes: value.scopes,
+            last_used_at: value.last_used_at,
+            expires_at: value.expires_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl NewApiTokenModel {
+    pub async fn insert(self, db: &Client) -> Result<ApiTokenModel> {
+        let row = db.query_one(
+            "INSERT INTO api_token (member_id, name, token_hash, scopes, expires_at, created_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
+            params![
+                *self.member_id as i32,
+                &self.name,
+                &self.token_hash,
+                self.scopes,
+                self.expires_at,
+                self.created_at
+            ],
+        ).await?;
+
+        Ok(ApiTokenModel {
+            id: ApiTokenId::from(row_int_to_usize(row)?),
+            member_id: self.member_id,
+            name: self.name,
+            token_hash: self.token_hash,
+            scopes: self.scopes,
+            last_used_at: None,
+            expires_at: self.expires_at,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl ApiTokenModel {
+    pub async fn find_by_member_id(member_id: MemberId, db: &Client) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM api_token WHERE member_id = $1 ORDER BY id DESC",
+                params![*member_id as i32],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    /// Finds an unexpired token from the plain token value.
+    pub async fn find_valid_by_token(value: &str, db: &Client) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM api_token WHERE token_hash = $1 AND (expires_at IS NULL OR expires_at > NOW())",
+            params![hash_token(value)],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    pub async fn update_last_used(id: ApiTokenId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE api_token SET last_used_at = $2 WHERE id = $1",
+                params![id, Utc::now()],
+            )
+            .await?)
+    }
+
+    pub async fn remove(id: ApiTokenId, member_id: MemberId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM api_token WHERE id = $1 AND member_id = $2",
+                params![id, *member_id as i32],
+            )
+            .await?)
+    }
+
+    pub async fn remove_by_member_id(member_id: MemberId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM api_token WHERE member_id = $1",
+                params![*member_id as i32],
+            )
+            .await?)
+    }
+}
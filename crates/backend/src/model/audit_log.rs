@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{
+    api::AuditLogQuery,
+    audit::{AuditAction, SharedAuditLog},
+    AuditLogId,
+};
+use serde::Serialize;
+use tokio_postgres::{types::ToSql, Client};
+
+use crate::Result;
+
+use super::{row_bigint_to_usize, AdvRow, TableRow};
+
+pub struct NewAuditLogModel {
+    pub member_id: MemberId,
+
+    pub action: AuditAction,
+    pub target_id: Option<String>,
+
+    pub before: Option<String>,
+    pub after: Option<String>,
+
+    pub ip_address: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditLogModel {
+    pub id: AuditLogId,
+
+    pub member_id: MemberId,
+
+    pub action: AuditAction,
+    pub target_id: Option<String>,
+
+    pub before: Option<String>,
+    pub after: Option<String>,
+
+    pub ip_address: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableRow for AuditLogModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next()?,
+
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+
+            action: row.next()?,
+            target_id: row.next()?,
+
+            before: row.next()?,
+            after: row.next()?,
+
+            ip_address: row.next()?,
+
+            created_at: row.next()?,
+        })
+    }
+}
+
+impl From<AuditLogModel> for SharedAuditLog {
+    fn from(value: AuditLogModel) -> Self {
+        Self {
+            id: value.id,
+            member_id: value.member_id,
+            action: value.action,
+            target_id: value.target_id,
+            before: value.before,
+            after: value.after,
+            ip_address: value.ip_address,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl NewAuditLogModel {
+    pub fn new(member_id: MemberId, action: AuditAction, ip_address: Option<String>) -> Self {
+        Self {
+            member_id,
+            action,
+            target_id: None,
+            before: None,
+            after: None,
+            ip_address,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn target(mut self, id: impl ToString) -> Self {
+        self.target_id = Some(id.to_string());
+        self
+    }
+
+    pub fn before<V: Serialize>(mut self, value: &V) -> Result<Self> {
+        self.before = Some(serde_json::to_string(value)?);
+        Ok(self)
+    }
+
+    pub fn after<V: Serialize>(mut self, value: &V) -> Result<Self> {
+        self.after = Some(serde_json::to_string(value)?);
+        Ok(self)
+    }
+
+    pub async fn insert(self, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "INSERT INTO audit_log (member_id, action, target_id, before, after, ip_address, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                params![
+                    *self.member_id as i32,
+                    self.action,
+                    self.target_id,
+                    self.before,
+                    self.after,
+                    self.ip_address,
+                    self.created_at
+                ],
+            )
+            .await?)
+    }
+}
+
+impl AuditLogModel {
+    fn gen_filter_query(
+        query: &AuditLogQuery,
+        parameters: &mut Vec<Box<dyn ToSql + Sync>>,
+    ) -> String {
+        let base_param_len = parameters.len();
+
+        let mut sql = String::from("SELECT * FROM audit_log");
+
+        let mut sql_queries = Vec::new();
+
+        if let Some(id) = query.member_id {
+            sql_queries.push("member_id = ??");
+            parameters.push(Box::new(*id as i32) as Box<dyn ToSql + Sync>);
+        }
+
+        if let Some(action) = query.action {
+            sql_queries.push("action = ??");
+            parameters.push(Box::new(action) as Box<dyn ToSql + Sync>);
+        }
+
+        if let Some(id) = query.target_id.clone() {
+            sql_queries.push("target_id = ??");
+            parameters.push(Box::new(id) as Box<dyn ToSql + Sync>);
+        }
+
+        if !sql_queries.is_empty() {
+            let sql_query = sql_queries
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    format!(
+                        "({})",
+                        v.replace("??", &format!("${}", base_param_len + 1 + i))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+
+            sql += " WHERE ";
+            sql += &sql_query;
+        }
+
+        sql
+    }
+
+    pub async fn find_by_filter(query: &AuditLogQuery, db: &Client) -> Result<Vec<Self>> {
+        let mut parameters = vec![
+            Box::new(query.limit.unwrap_or(25) as i64) as Box<dyn ToSql + Sync>,
+            Box::new(query.offset.unwrap_or(0) as i64) as Box<dyn ToSql + Sync>,
+        ];
+
+        let sql =
+            Self::gen_filter_query(query, &mut parameters) + " ORDER BY id DESC LIMIT $1 OFFSET $2";
+
+        let values = db
+            .query(&sql, &super::boxed_to_dyn_vec(&parameters))
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn count_by_filter(query: &AuditLogQuery, db: &Client) -> Result<usize> {
+        let mut parameters = Vec::new();
+
+        let sql =
+            Self::gen_filter_query(query, &mut parameters).replace("SELECT *", "SELECT COUNT(*)");
+
+        row_bigint_to_usize(
+            db.query_one(&sql, &super::boxed_to_dyn_vec(&parameters))
+                .await?,
+        )
+    }
+}
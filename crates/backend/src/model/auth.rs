@@ -3,30 +3,85 @@ use tokio_postgres::Client;
 
 use crate::Result;
 
+use super::row_bigint_to_usize;
+
+/// A single-use passwordless login token. Only valid for the email it was sent to.
 pub struct AuthModel {
     pub oauth_token: String,
-    pub oauth_token_secret: String,
+
+    pub email: String,
+    pub ip_address: Option<String>,
+
     pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 impl AuthModel {
     pub async fn insert(&self, client: &Client) -> Result<()> {
         client.execute(
-            "INSERT INTO auth (oauth_token, oauth_token_secret, created_at) VALUES ($1, $2, $3)",
+            "INSERT INTO auth (oauth_token, email, ip_address, created_at, expires_at) VALUES ($1, $2, $3, $4, $5)",
             params![
                 &self.oauth_token,
-                &self.oauth_token_secret,
-                self.created_at
+                &self.email,
+                &self.ip_address,
+                self.created_at,
+                self.expires_at
             ],
         ).await?;
 
         Ok(())
     }
 
-    pub async fn remove_by_oauth_token(value: &str, client: &Client) -> Result<bool> {
+    /// Removes the token if it's unexpired and was issued for the email. Returns true if it was.
+    pub async fn remove_valid(oauth_token: &str, email: &str, client: &Client) -> Result<bool> {
         Ok(client
-            .execute("DELETE FROM auth WHERE oauth_token = $1", params![value])
+            .execute(
+                "DELETE FROM auth WHERE oauth_token = $1 AND email = $2 AND expires_at > NOW()",
+                params![oauth_token, email],
+            )
             .await?
             != 0)
     }
+
+    pub async fn count_by_email_since(
+        email: &str,
+        since: DateTime<Utc>,
+        client: &Client,
+    ) -> Result<usize> {
+        row_bigint_to_usize(
+            client
+                .query_one(
+                    "SELECT COUNT(*) FROM auth WHERE email = $1 AND created_at > $2",
+                    params![email, since],
+                )
+                .await?,
+        )
+    }
+
+    pub async fn count_by_ip_address_since(
+        ip_address: &str,
+        since: DateTime<Utc>,
+        client: &Client,
+    ) -> Result<usize> {
+        row_bigint_to_usize(
+            client
+                .query_one(
+                    "SELECT COUNT(*) FROM auth WHERE ip_address = $1 AND created_at > $2",
+                    params![ip_address, since],
+                )
+                .await?,
+        )
+    }
+
+    /// Removes expired tokens which were created before `created_before`.
+    ///
+    /// They're kept until then so they still count towards the request limits.
+    pub async fn remove_expired(created_before: DateTime<Utc>, client: &Client) -> Result<u64> {
+        Ok(client
+            .execute(
+                "DELETE FROM auth WHERE expires_at <= NOW() AND created_at < $1",
+                params![created_before],
+            )
+            .await?)
+    }
 }
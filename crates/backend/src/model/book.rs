@@ -1,10 +1,11 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use common::{
     api::librarian::{PartialBook, PublicBook},
-    get_language_id, get_language_name, BookId, PersonId, ThumbnailStore,
+    get_language_id, get_language_name, BookId, ImageType, PersonId, ThumbnailStore,
 };
 use common_local::{
     api::{OrderBy, QueryType},
+    edit::EditType,
     util::{serialize_datetime, serialize_datetime_opt, serialize_naivedate_opt},
     DisplayMetaItem, MetadataItemCached,
 };
@@ -14,7 +15,10 @@ use tokio_postgres::types::ToSql;
 
 use crate::Result;
 
-use super::{row_bigint_to_usize, row_int_to_usize, AdvRow, TableRow, BookIsbnModel};
+use super::{
+    row_bigint_to_usize, row_int_to_usize, AdvRow, BookIsbnModel, ImageLinkModel,
+    ModelRedirectModel, TableRow, UploadedImageModel,
+};
 
 const FIELDS: &str = "id, title, clean_title, description, rating, thumb_url, cached, is_public, edition_count, available_at, language, created_at, updated_at, deleted_at";
 
@@ -173,7 +177,17 @@ impl BookModel {
     }
 
     pub async fn get_book_count(db: &tokio_postgres::Client) -> Result<usize> {
-        row_bigint_to_usize(db.query_one(r#"SELECT COUNT(*) FROM book"#, &[]).await?)
+        row_bigint_to_usize(
+            db.query_one(r#"SELECT COUNT(*) FROM book WHERE deleted_at IS NULL"#, &[])
+                .await?,
+        )
+    }
+
+    pub async fn get_deleted_count(db: &tokio_postgres::Client) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one(r#"SELECT COUNT(*) FROM book WHERE deleted_at IS NOT NULL"#, &[])
+                .await?,
+        )
     }
 
     pub async fn add_or_update_book(&mut self, db: &tokio_postgres::Client) -> Result<()> {
@@ -249,6 +263,72 @@ impl BookModel {
             .transpose()
     }
 
+    /// Returns the book or, if it was merged into another, the book it was merged into.
+    pub async fn get_by_id_or_redirect(
+        id: BookId,
+        db: &tokio_postgres::Client,
+    ) -> Result<Option<Self>> {
+        if let Some(model) = Self::get_by_id(id, db).await? {
+            return Ok(Some(model));
+        }
+
+        match ModelRedirectModel::find_by_old_id(EditType::Book, *id, db).await? {
+            Some(redirect) => Self::get_by_id(BookId::from(redirect.new_id), db).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Moves everything linked to `source_id` over to `target_id`, fills in any empty fields from the source,
+    /// leaves a redirect and then removes the source book.
+    pub async fn merge_into(
+        source_id: BookId,
+        target_id: BookId,
+        db: &tokio_postgres::Client,
+    ) -> Result<()> {
+        let (s, t) = (*source_id, *target_id);
+        let image_type = ImageType::Book.as_num();
+
+        // NOTE: Sent as a single batch so it's executed inside an implicit transaction.
+        // No BEGIN/COMMIT since the client is shared. Only integers are formatted into it.
+        let sql = format!(
+            r#"
+            INSERT INTO book_person (book_id, person_id, info)
+                SELECT {t}, person_id, info FROM book_person WHERE book_id = {s} ON CONFLICT DO NOTHING;
+
+            INSERT INTO book_tag (book_id, tag_id, idx, created_at)
+                SELECT {t}, tag_id, idx, created_at FROM book_tag WHERE book_id = {s} ON CONFLICT DO NOTHING;
+
+            UPDATE book_isbn SET book_id = {t} WHERE book_id = {s};
+
+            INSERT INTO collection_item (collection_id, book_id, idx)
+                SELECT collection_id, {t}, idx FROM collection_item WHERE book_id = {s} ON CONFLICT DO NOTHING;
+
+            INSERT INTO image_link (image_id, link_id, type_of)
+                SELECT image_id, {t}, type_of FROM image_link WHERE link_id = {s} AND type_of = {image_type} ON CONFLICT DO NOTHING;
+            DELETE FROM image_link WHERE link_id = {s} AND type_of = {image_type};
+
+            UPDATE book AS tb SET
+                title = COALESCE(tb.title, sb.title),
+                clean_title = COALESCE(tb.clean_title, sb.clean_title),
+                description = COALESCE(tb.description, sb.description),
+                thumb_url = COALESCE(tb.thumb_url, sb.thumb_url),
+                cached = COALESCE(tb.cached, sb.cached),
+                available_at = COALESCE(tb.available_at, sb.available_at),
+                updated_at = NOW()
+            FROM book AS sb WHERE tb.id = {t} AND sb.id = {s};
+
+            {redirect}
+
+            DELETE FROM book WHERE id = {s};
+            "#,
+            redirect = ModelRedirectModel::create_batch_sql(EditType::Book, s, t),
+        );
+
+        db.batch_execute(&sql).await?;
+
+        Ok(())
+    }
+
     pub async fn exists_by_isbn(value: &str, db: &tokio_postgres::Client) -> Result<bool> {
         Ok(db
             .query_one(
@@ -265,6 +345,77 @@ impl BookModel {
             .await?)
     }
 
+    /// Removes the book from the database along with any images which are no longer linked.
+    pub async fn purge_by_id(id: BookId, db: &tokio_postgres::Client) -> Result<u64> {
+        let image_links = ImageLinkModel::find_by_link_id(*id, ImageType::Book, db).await?;
+
+        let amount = Self::remove_by_id(id, db).await?;
+
+        // Remove remaining images
+        for link in image_links {
+            let image_id = link.image_id;
+
+            link.remove(db).await?;
+
+            // Check how many links there are for the image
+            let count = ImageLinkModel::count_by_image_id(image_id, db).await?;
+
+            if count == 0 {
+                // If we have no more links then remove it.
+                UploadedImageModel::remove_by_id(image_id, db).await?;
+            }
+        }
+
+        Ok(amount)
+    }
+
+    pub async fn soft_delete_by_id(id: BookId, db: &tokio_postgres::Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE book SET deleted_at = $2 WHERE id = $1 AND deleted_at IS NULL",
+                params![*id as i32, Utc::now()],
+            )
+            .await?)
+    }
+
+    pub async fn restore_by_id(id: BookId, db: &tokio_postgres::Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE book SET deleted_at = NULL, updated_at = $2 WHERE id = $1",
+                params![*id as i32, Utc::now()],
+            )
+            .await?)
+    }
+
+    pub async fn find_deleted(
+        offset: usize,
+        limit: usize,
+        db: &tokio_postgres::Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                &format!("SELECT {FIELDS} FROM book WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC LIMIT $1 OFFSET $2"),
+                params![limit as i64, offset as i64],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn find_deleted_before(
+        value: DateTime<Utc>,
+        db: &tokio_postgres::Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                &format!("SELECT {FIELDS} FROM book WHERE deleted_at < $1"),
+                params![value],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
     pub async fn get_book_by(
         offset: usize,
         limit: usize,
@@ -274,7 +425,7 @@ impl BookModel {
         db: &tokio_postgres::Client,
     ) -> Result<Vec<Self>> {
         let inner_query = if let Some(pid) = person_id {
-            format!("WHERE id IN (SELECT book_id FROM book_person WHERE person_id = {pid})")
+            format!("AND id IN (SELECT book_id FROM book_person WHERE person_id = {pid})")
         } else {
             String::new()
         };
@@ -282,7 +433,7 @@ impl BookModel {
         let values = db
             .query(
                 &format!(
-                    "SELECT {FIELDS} FROM book {} ORDER BY id {} LIMIT $1 OFFSET $2",
+                    "SELECT {FIELDS} FROM book WHERE deleted_at IS NULL {} ORDER BY id {} LIMIT $1 OFFSET $2",
                     inner_query,
                     order.into_string()
                 ),
@@ -301,7 +452,6 @@ impl BookModel {
         let base_param_len = parameters.len();
 
         let mut sql = String::from("SELECT * FROM book WHERE ");
-        let orig_len = sql.len();
 
         let mut sql_queries = Vec::new();
 
@@ -353,21 +503,24 @@ impl BookModel {
             }
         }
 
+        // Never return soft-deleted books. Pushed last since it has no parameter.
+        sql_queries.push(String::from("deleted_at IS NULL"));
+
         let sql_query = sql_queries
             .into_iter()
             .enumerate()
-            .map(|(i, v)| v.replace("??", &format!("${}", base_param_len + 1 + i)))
+            .map(|(i, v)| {
+                format!(
+                    "({})",
+                    v.replace("??", &format!("${}", base_param_len + 1 + i))
+                )
+            })
             .collect::<Vec<_>>()
             .join(" AND ");
 
         sql += &sql_query;
 
-        if sql.len() == orig_len {
-            // If sql is still unmodified
-            String::from("SELECT * FROM BOOK ")
-        } else {
-            sql
-        }
+        sql
     }
 
     pub async fn search_book_list(
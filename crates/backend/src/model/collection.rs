@@ -65,7 +65,7 @@ impl CollectionModel {
         db: &tokio_postgres::Client,
     ) -> Result<Vec<BookModel>> {
         let values = db.query(
-            "SELECT * FROM book WHERE id IN (SELECT book_id FROM collection_item WHERE collection_id = $1)",
+            "SELECT * FROM book WHERE deleted_at IS NULL AND id IN (SELECT book_id FROM collection_item WHERE collection_id = $1)",
             params![ id ]
         ).await?;
 
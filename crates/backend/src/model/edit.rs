@@ -1,21 +1,28 @@
-use std::str::FromStr;
+use std::{fmt::Write, str::FromStr};
 
-use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 use common::{BookId, MemberId, PersonId, TagId};
-use common_local::{edit::*, item::edit::*, EditId};
+use common_local::{
+    api::{EditListQuery, EditListSort, OrderBy, UpdateCollectionModel},
+    edit::*,
+    item::edit::*,
+    ws::WebsocketNotification,
+    CollectionId, DisplayMetaItem, EditId, ServerLinkId,
+};
 
 mod edit_comment;
 mod edit_vote;
 
 pub use edit_comment::*;
 pub use edit_vote::*;
-use tokio_postgres::Client;
+use tokio_postgres::{types::ToSql, Client};
 
-use crate::{edit_translate, Result, InternalError};
+use crate::{config::get_config, edit_translate, http::send_notification, InternalError, Result};
 
 use super::{
-    row_bigint_to_usize, row_int_to_usize, AdvRow, BookModel, BookPersonModel, BookTagModel,
-    ImageLinkModel, MemberModel, PersonAltModel, PersonModel, TableRow, TagModel, BookIsbnModel,
+    notify_edit_outcome, notify_watchers_of_edit, row_bigint_to_usize, row_int_to_usize, AdvRow,
+    BookIsbnModel, BookModel, BookPersonModel, BookTagModel, CollectionModel, ImageLinkModel,
+    MemberModel, NewTagModel, PersonAltModel, PersonModel, TableRow, TagModel,
 };
 
 #[derive(Debug)]
@@ -38,6 +45,11 @@ pub struct NewEditModel {
 
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    /// Shared by every edit created from the same mass edit. The id of the first edit in it.
+    pub batch_id: Option<EditId>,
+    /// The linked server which submitted it on behalf of the member.
+    pub server_link_id: Option<ServerLinkId>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +75,9 @@ pub struct EditModel {
 
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    pub batch_id: Option<EditId>,
+    pub server_link_id: Option<ServerLinkId>,
 }
 
 impl TableRow for EditModel {
@@ -88,10 +103,23 @@ impl TableRow for EditModel {
 
             created_at: row.next()?,
             updated_at: row.next()?,
+
+            batch_id: row.next_opt()?,
+            server_link_id: row.next_opt()?,
         })
     }
 }
 
+/// When voting on a new edit of this type closes.
+fn voting_expires_at(type_of: EditType, now: DateTime<Utc>) -> DateTime<Utc> {
+    now + Duration::days(get_config().voting.expiry_days.get(type_of))
+}
+
+/// Converts a unix timestamp in seconds from a list filter. None if it's out of range.
+pub fn timestamp_to_date(secs: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(secs, 0).single()
+}
+
 impl NewEditModel {
     pub async fn from_book_modify(
         member_id: MemberId,
@@ -114,9 +142,148 @@ impl NewEditModel {
                 &new_edit_data_from_book(current, updated, db).await?,
             )?,
             ended_at: None,
-            expires_at: Some(now + Duration::days(7)),
+            expires_at: Some(voting_expires_at(EditType::Book, now)),
+            created_at: now,
+            updated_at: now,
+            batch_id: None,
+            server_link_id: None,
+        })
+    }
+
+    /// Whether a Modify edit contains no changes, ie: every value matched the current model.
+    pub fn is_modify_empty(&self) -> Result<bool> {
+        if self.operation != EditOperation::Modify {
+            return Ok(false);
+        }
+
+        Ok(match self.type_of {
+            EditType::Book => serde_json::from_str::<BookEditData>(&self.data)?
+                .new
+                .map_or(true, |v| v.is_empty()),
+            EditType::Person => serde_json::from_str::<PersonEditData>(&self.data)?
+                .new
+                .map_or(true, |v| v.is_empty()),
+            EditType::Tag | EditType::Collection => false,
+        })
+    }
+
+    pub fn from_book_delete(member_id: MemberId, current: BookModel) -> Result<Self> {
+        let now = Utc::now();
+
+        Ok(Self {
+            type_of: EditType::Book,
+            operation: EditOperation::Delete,
+            status: EditStatus::Pending,
+            member_id,
+            model_id: Some(*current.id),
+            is_applied: false,
+            vote_count: 0,
+            data: convert_data_to_string(
+                EditType::Book,
+                &EditData::Book(BookEditData {
+                    current: Some(current.into()),
+                    new: None,
+                    old: None,
+                    updated: None,
+                    conflicts: None,
+                }),
+            )?,
+            ended_at: None,
+            expires_at: Some(voting_expires_at(EditType::Book, now)),
+            created_at: now,
+            updated_at: now,
+            batch_id: None,
+            server_link_id: None,
+        })
+    }
+
+    pub fn from_book_merge(
+        member_id: MemberId,
+        source: BookModel,
+        target: BookModel,
+    ) -> Result<Self> {
+        let now = Utc::now();
+
+        let source_id = source.id;
+        let target_id = target.id;
+
+        let mut preview = DisplayMetaItem::from(target);
+
+        preview.title = preview.title.or(source.title);
+        preview.clean_title = preview.clean_title.or(source.clean_title);
+        preview.description = preview.description.or(source.description);
+        preview.available_at = preview.available_at.or(source.available_at);
+
+        if preview.thumb_path.is_none() {
+            preview.thumb_path = source.thumb_path;
+        }
+
+        Ok(Self {
+            type_of: EditType::Book,
+            operation: EditOperation::Merge,
+            status: EditStatus::Pending,
+            member_id,
+            model_id: Some(*target_id),
+            is_applied: false,
+            vote_count: 0,
+            data: convert_data_to_string(
+                EditType::Book,
+                &EditData::BookMerge(BookMergeData {
+                    source_id,
+                    target_id,
+                    preview: Some(preview),
+                }),
+            )?,
+            ended_at: None,
+            expires_at: Some(voting_expires_at(EditType::Book, now)),
+            created_at: now,
+            updated_at: now,
+            batch_id: None,
+            server_link_id: None,
+        })
+    }
+
+    pub fn from_person_merge(
+        member_id: MemberId,
+        source: PersonModel,
+        target: PersonModel,
+    ) -> Result<Self> {
+        let now = Utc::now();
+
+        let source_id = source.id;
+        let target_id = target.id;
+
+        let mut preview = target.into_public_person(None);
+
+        preview.description = preview.description.or(source.description);
+        preview.birth_date = preview.birth_date.or(source.birth_date);
+
+        if preview.thumb_url.is_none() {
+            preview.thumb_url = source.thumb_url;
+        }
+
+        Ok(Self {
+            type_of: EditType::Person,
+            operation: EditOperation::Merge,
+            status: EditStatus::Pending,
+            member_id,
+            model_id: Some(*target_id),
+            is_applied: false,
+            vote_count: 0,
+            data: convert_data_to_string(
+                EditType::Person,
+                &EditData::PersonMerge(PersonMergeData {
+                    source_id,
+                    target_id,
+                    preview: Some(preview),
+                }),
+            )?,
+            ended_at: None,
+            expires_at: Some(voting_expires_at(EditType::Person, now)),
             created_at: now,
             updated_at: now,
+            batch_id: None,
+            server_link_id: None,
         })
     }
 
@@ -140,9 +307,69 @@ impl NewEditModel {
                 &new_edit_data_from_person(current, updated).await?,
             )?,
             ended_at: None,
-            expires_at: Some(now + Duration::days(7)),
+            expires_at: Some(voting_expires_at(EditType::Person, now)),
+            created_at: now,
+            updated_at: now,
+            batch_id: None,
+            server_link_id: None,
+        })
+    }
+
+    pub fn from_tag_create(member_id: MemberId, new: TagEdit) -> Result<Self> {
+        let now = Utc::now();
+
+        Ok(Self {
+            type_of: EditType::Tag,
+            operation: EditOperation::Create,
+            status: EditStatus::Pending,
+            member_id,
+            model_id: None,
+            is_applied: false,
+            vote_count: 0,
+            data: convert_data_to_string(
+                EditType::Tag,
+                &EditData::Tag(TagEditData {
+                    current: None,
+                    new: Some(new),
+                    old: None,
+                    updated: None,
+                    conflicts: None,
+                }),
+            )?,
+            ended_at: None,
+            expires_at: Some(voting_expires_at(EditType::Tag, now)),
+            created_at: now,
+            updated_at: now,
+            batch_id: None,
+            server_link_id: None,
+        })
+    }
+
+    pub async fn from_collection_modify(
+        member_id: MemberId,
+        current: CollectionModel,
+        updated: UpdateCollectionModel,
+    ) -> Result<Self> {
+        let now = Utc::now();
+
+        Ok(Self {
+            type_of: EditType::Collection,
+            operation: EditOperation::Modify,
+            status: EditStatus::Pending,
+            member_id,
+            model_id: Some(*current.id),
+            is_applied: false,
+            vote_count: 0,
+            data: convert_data_to_string(
+                EditType::Collection,
+                &new_edit_data_from_collection(current, updated).await?,
+            )?,
+            ended_at: None,
+            expires_at: Some(voting_expires_at(EditType::Collection, now)),
             created_at: now,
             updated_at: now,
+            batch_id: None,
+            server_link_id: None,
         })
     }
 
@@ -153,9 +380,9 @@ impl NewEditModel {
             INSERT INTO edit (
                 type_of, operation, status,
                 member_id, model_id, is_applied, vote_count, data,
-                ended_at, expires_at, created_at, updated_at
+                ended_at, expires_at, created_at, updated_at, batch_id, server_link_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING id"#,
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) RETURNING id"#,
                 params![
                     self.type_of,
                     self.operation,
@@ -169,12 +396,16 @@ impl NewEditModel {
                     self.expires_at,
                     self.created_at,
                     self.updated_at,
+                    self.batch_id,
+                    self.server_link_id,
                 ],
             )
             .await?;
 
-        Ok(EditModel {
-            id: EditId::from(row_int_to_usize(row)?),
+        let id = EditId::from(row_int_to_usize(row)?);
+
+        let model = EditModel {
+            id,
 
             type_of: self.type_of,
             operation: self.operation,
@@ -192,7 +423,16 @@ impl NewEditModel {
             expires_at: self.expires_at,
             created_at: self.created_at,
             updated_at: self.updated_at,
-        })
+
+            batch_id: self.batch_id,
+            server_link_id: self.server_link_id,
+        };
+
+        notify_watchers_of_edit(&model, db).await?;
+
+        send_notification(WebsocketNotification::EditCreated(id));
+
+        Ok(model)
     }
 }
 
@@ -215,52 +455,232 @@ impl EditModel {
             .transpose()
     }
 
-    pub async fn find_by_status(
-        offset: usize,
-        limit: usize,
-        status: Option<EditStatus>,
-        is_expired: Option<bool>,
-        db: &Client,
-    ) -> Result<Vec<Self>> {
-        let mut expired_str = String::new();
+    fn gen_filter_query(
+        query: &EditListQuery,
+        member_id: Option<MemberId>,
+        parameters: &mut Vec<Box<dyn ToSql + Sync>>,
+    ) -> String {
+        let base_param_len = parameters.len();
 
-        if let Some(expired) = is_expired {
-            let now = Utc::now();
+        let mut sql = String::from("SELECT * FROM edit");
 
-            if expired {
-                expired_str = format!("AND expires_at < {now}");
-            } else {
-                expired_str = format!("AND expires_at > {now}");
-            }
+        let mut sql_queries = Vec::new();
+
+        if let Some(type_of) = query.type_of {
+            sql_queries.push("type_of = ??");
+            parameters.push(Box::new(type_of) as Box<dyn ToSql + Sync>);
         }
 
-        if let Some(status) = status {
-            let values = db.query(
-                &format!("SELECT * FROM edit WHERE status = $1 {expired_str} ORDER BY id DESC LIMIT $2 OFFSET $3"),
-                params![ status, limit as i64, offset as i64 ]
-            ).await?;
+        if let Some(operation) = query.operation {
+            sql_queries.push("operation = ??");
+            parameters.push(Box::new(operation) as Box<dyn ToSql + Sync>);
+        }
 
-            values.into_iter().map(Self::from_row).collect()
-        } else {
-            if !expired_str.is_empty() {
-                expired_str.insert_str(0, "WHERE ");
-            }
+        if let Some(status) = query.status {
+            sql_queries.push("status = ??");
+            parameters.push(Box::new(status) as Box<dyn ToSql + Sync>);
+        }
 
-            let values = db
-                .query(
-                    &format!(
-                        "SELECT * FROM edit {expired_str} ORDER BY id DESC LIMIT $1 OFFSET $2"
-                    ),
-                    params![limit as i64, offset as i64],
-                )
-                .await?;
+        if let Some(id) = query.member_id {
+            sql_queries.push("member_id = ??");
+            parameters.push(Box::new(*id as i32) as Box<dyn ToSql + Sync>);
+        }
+
+        if let Some(id) = query.model_id {
+            sql_queries.push("model_id = ??");
+            parameters.push(Box::new(id as i32) as Box<dyn ToSql + Sync>);
+        }
+
+        if let Some(date) = query.created_after.and_then(timestamp_to_date) {
+            sql_queries.push("created_at >= ??");
+            parameters.push(Box::new(date) as Box<dyn ToSql + Sync>);
+        }
 
-            values.into_iter().map(Self::from_row).collect()
+        if let Some(date) = query.created_before.and_then(timestamp_to_date) {
+            sql_queries.push("created_at < ??");
+            parameters.push(Box::new(date) as Box<dyn ToSql + Sync>);
         }
+
+        // Ignored if we don't know who is asking.
+        if let Some(id) = member_id.filter(|_| query.not_voted) {
+            sql_queries.push("id NOT IN (SELECT edit_id FROM edit_vote WHERE member_id = ??)");
+            parameters.push(Box::new(*id as i32) as Box<dyn ToSql + Sync>);
+        }
+
+        if !sql_queries.is_empty() {
+            let sql_query = sql_queries
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    format!(
+                        "({})",
+                        v.replace("??", &format!("${}", base_param_len + 1 + i))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+
+            sql += " WHERE ";
+            sql += &sql_query;
+        }
+
+        sql
+    }
+
+    /// `member_id` is the member requesting the list. Used for the `not_voted` filter.
+    pub async fn find_by_filter(
+        query: &EditListQuery,
+        member_id: Option<MemberId>,
+        db: &Client,
+    ) -> Result<Vec<Self>> {
+        let mut parameters = vec![
+            Box::new(query.limit.unwrap_or(25) as i64) as Box<dyn ToSql + Sync>,
+            Box::new(query.offset.unwrap_or(0) as i64) as Box<dyn ToSql + Sync>,
+        ];
+
+        let mut sql = Self::gen_filter_query(query, member_id, &mut parameters);
+
+        let sort = query.sort.unwrap_or(EditListSort::Created);
+
+        // Soonest to expire first. Everything else is newest or highest first.
+        let order = query.order.unwrap_or(match sort {
+            EditListSort::Expires => OrderBy::Asc,
+            EditListSort::Created | EditListSort::Votes => OrderBy::Desc,
+        });
+
+        let column = match sort {
+            EditListSort::Created => "id",
+            EditListSort::Expires => "expires_at",
+            EditListSort::Votes => "vote_count",
+        };
+
+        let _ = write!(
+            &mut sql,
+            " ORDER BY {column} {} NULLS LAST, id DESC LIMIT $1 OFFSET $2",
+            order.into_string()
+        );
+
+        let values = db
+            .query(&sql, &super::boxed_to_dyn_vec(&parameters))
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn count_by_filter(
+        query: &EditListQuery,
+        member_id: Option<MemberId>,
+        db: &Client,
+    ) -> Result<usize> {
+        let mut parameters = Vec::new();
+
+        let sql = Self::gen_filter_query(query, member_id, &mut parameters)
+            .replace("SELECT *", "SELECT COUNT(*)");
+
+        row_bigint_to_usize(
+            db.query_one(&sql, &super::boxed_to_dyn_vec(&parameters))
+                .await?,
+        )
+    }
+
+    /// Accepted edits for a specific model, oldest first.
+    pub async fn find_accepted_by_model(
+        type_of: EditType,
+        model_id: usize,
+        offset: usize,
+        limit: usize,
+        db: &Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM edit WHERE type_of = $1 AND model_id = $2 AND status IN ($3, $4) ORDER BY ended_at ASC, id ASC LIMIT $5 OFFSET $6",
+                params![
+                    type_of,
+                    model_id as i32,
+                    EditStatus::Accepted,
+                    EditStatus::ForceAccepted,
+                    limit as i64,
+                    offset as i64
+                ],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn count_accepted_by_model(
+        type_of: EditType,
+        model_id: usize,
+        db: &Client,
+    ) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one(
+                "SELECT COUNT(*) FROM edit WHERE type_of = $1 AND model_id = $2 AND status IN ($3, $4)",
+                params![
+                    type_of,
+                    model_id as i32,
+                    EditStatus::Accepted,
+                    EditStatus::ForceAccepted
+                ],
+            )
+            .await?,
+        )
+    }
+
+    pub async fn exists_pending_by_model(
+        type_of: EditType,
+        operation: EditOperation,
+        model_id: usize,
+        db: &Client,
+    ) -> Result<bool> {
+        Ok(db
+            .query_one(
+                "SELECT EXISTS(SELECT id FROM edit WHERE type_of = $1 AND operation = $2 AND model_id = $3 AND status = $4)",
+                params![type_of, operation, model_id as i32, EditStatus::Pending],
+            )
+            .await?
+            .try_get(0)?)
+    }
+
+    /// Whether a pending merge would conflict with merging `source_id` into `target_id`.
+    ///
+    /// The source can't be part of another pending merge and the target can't be pending removal.
+    pub async fn exists_pending_merge(
+        type_of: EditType,
+        source_id: usize,
+        target_id: usize,
+        db: &Client,
+    ) -> Result<bool> {
+        Ok(db
+            .query_one(
+                r#"SELECT EXISTS(
+                    SELECT id FROM edit WHERE type_of = $1 AND operation = $2 AND status = $3 AND (
+                        (data::jsonb->>'source_id')::INT IN ($4, $5)
+                        OR (data::jsonb->>'target_id')::INT = $4
+                    )
+                )"#,
+                params![
+                    type_of,
+                    EditOperation::Merge,
+                    EditStatus::Pending,
+                    source_id as i32,
+                    target_id as i32
+                ],
+            )
+            .await?
+            .try_get(0)?)
     }
 
-    pub async fn get_count(db: &Client) -> Result<usize> {
-        row_bigint_to_usize(db.query_one("SELECT COUNT(*) FROM edit", &[]).await?)
+    /// Every edit created by the same mass edit, including the first one.
+    pub async fn find_by_batch_id(batch_id: EditId, db: &Client) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM edit WHERE batch_id = $1 ORDER BY id ASC",
+                params![batch_id],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
     }
 
     pub async fn update_by_id(id: EditId, edit: UpdateEditModel, db: &Client) -> Result<u64> {
@@ -322,16 +742,23 @@ impl EditModel {
             EditType::Book => ModelIdGroup::Book(BookId::from(id)),
             EditType::Person => ModelIdGroup::Person(PersonId::from(id)),
             EditType::Tag => ModelIdGroup::Tag(TagId::from(id)),
-            EditType::Collection => todo!(),
+            EditType::Collection => ModelIdGroup::Collection(CollectionId::from(id)),
         })
     }
 
     pub fn parse_data(&self) -> Result<EditData> {
         Ok(match self.type_of {
+            EditType::Book if self.operation == EditOperation::Merge => {
+                EditData::BookMerge(serde_json::from_str(&self.data)?)
+            }
+            EditType::Person if self.operation == EditOperation::Merge => {
+                EditData::PersonMerge(serde_json::from_str(&self.data)?)
+            }
+
             EditType::Book => EditData::Book(serde_json::from_str(&self.data)?),
             EditType::Person => EditData::Person(serde_json::from_str(&self.data)?),
-            EditType::Tag => EditData::Tag,
-            EditType::Collection => EditData::Collection,
+            EditType::Tag => EditData::Tag(serde_json::from_str(&self.data)?),
+            EditType::Collection => EditData::Collection(serde_json::from_str(&self.data)?),
         })
     }
 
@@ -344,10 +771,16 @@ impl EditModel {
             match (self.type_of, value) {
                 (EditType::Book, EditData::Book(v)) => self.data = serde_json::to_string(&v)?,
                 (EditType::Person, EditData::Person(v)) => self.data = serde_json::to_string(&v)?,
-                (EditType::Tag, EditData::Tag) => (),
-                (EditType::Collection, EditData::Collection) => (),
+                (EditType::Book, EditData::BookMerge(v)) => self.data = serde_json::to_string(&v)?,
+                (EditType::Person, EditData::PersonMerge(v)) => {
+                    self.data = serde_json::to_string(&v)?
+                }
+                (EditType::Tag, EditData::Tag(v)) => self.data = serde_json::to_string(&v)?,
+                (EditType::Collection, EditData::Collection(v)) => {
+                    self.data = serde_json::to_string(&v)?
+                }
 
-                _ => panic!("save_data"),
+                _ => return Err(InternalError::EditDataMismatch.into()),
             }
 
             db.execute(
@@ -366,6 +799,109 @@ impl EditModel {
         Ok(())
     }
 
+    pub async fn update_data(&mut self, value: EditData, db: &Client) -> Result<()> {
+        self.data = convert_data_to_string(self.type_of, &value)?;
+        self.updated_at = Utc::now();
+
+        db.execute(
+            "UPDATE edit SET data = $2, updated_at = $3 WHERE id = $1",
+            params![self.id, &self.data, self.updated_at],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Re-creates the edit against the current model.
+    ///
+    /// The `old` values and `current` model are refreshed which clears any conflicts.
+    pub async fn rebase(&mut self, db: &Client) -> Result<()> {
+        let data = match (self.parse_data()?, self.get_model_id()) {
+            (EditData::Book(data), Some(ModelIdGroup::Book(book_id))) => {
+                let book_model = BookModel::get_by_id(book_id, db)
+                    .await?
+                    .ok_or(InternalError::ItemMissing)?;
+
+                let mut value =
+                    new_edit_data_from_book(book_model.clone(), data.new.unwrap_or_default(), db)
+                        .await?;
+
+                if let EditData::Book(v) = &mut value {
+                    v.current = Some(book_model.into());
+                }
+
+                value
+            }
+
+            (EditData::Person(data), Some(ModelIdGroup::Person(person_id))) => {
+                let person_model = PersonModel::get_by_id(person_id, db)
+                    .await?
+                    .ok_or(InternalError::ItemMissing)?;
+
+                let mut value =
+                    new_edit_data_from_person(person_model.clone(), data.new.unwrap_or_default())
+                        .await?;
+
+                if let EditData::Person(v) = &mut value {
+                    v.current = Some(person_model.into_public_person(None));
+                }
+
+                value
+            }
+
+            _ => return Ok(()),
+        };
+
+        self.update_data(data, db).await
+    }
+
+    /// Used once an [`EditOperation::Create`] is accepted and the model now exists.
+    pub async fn update_model_id(&mut self, model_id: usize, db: &Client) -> Result<()> {
+        self.model_id = Some(model_id);
+
+        db.execute(
+            "UPDATE edit SET model_id = $2 WHERE id = $1",
+            params![self.id, model_id as i32],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Puts a finished edit back up for voting with a new expiration.
+    pub async fn reopen(&mut self, db: &Client) -> Result<()> {
+        self.status = EditStatus::Pending;
+        self.ended_at = None;
+        self.expires_at = Some(voting_expires_at(self.type_of, Utc::now()));
+
+        db.execute(
+            "UPDATE edit SET status = $2, ended_at = NULL, expires_at = $3 WHERE id = $1",
+            params![self.id, self.status, self.expires_at],
+        )
+        .await?;
+
+        MemberModel::update_reputation(Some(self.member_id), db).await?;
+
+        send_notification(WebsocketNotification::EditStatusChanged {
+            id: self.id,
+            status: self.status,
+        });
+
+        Ok(())
+    }
+
+    pub async fn update_batch_id(&mut self, batch_id: EditId, db: &Client) -> Result<()> {
+        self.batch_id = Some(batch_id);
+
+        db.execute(
+            "UPDATE edit SET batch_id = $2 WHERE id = $1",
+            params![self.id, batch_id],
+        )
+        .await?;
+
+        Ok(())
+    }
+
     pub fn into_shared_edit(self, member: Option<MemberModel>) -> Result<SharedEditModel> {
         let data = self.parse_data()?;
 
@@ -384,6 +920,9 @@ impl EditModel {
             expires_at: self.expires_at,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            batch_id: self.batch_id,
+            server_link_id: self.server_link_id,
+            diff: None,
         })
     }
 
@@ -413,10 +952,18 @@ impl EditModel {
                         }
                     }
 
-                    EditOperation::Create => todo!(),
-                    EditOperation::Delete => todo!(),
-                    EditOperation::Merge => todo!(),
-                },
+                    EditOperation::Delete => {
+                        BookModel::soft_delete_by_id(BookId::from(self.model_id.unwrap()), db)
+                            .await?;
+
+                        self.update_end_data_and_status(None, db).await?;
+                    }
+
+                    // Merges are parsed as EditData::BookMerge.
+                    EditOperation::Create | EditOperation::Merge => {
+                        return Err(InternalError::UnsupportedEditOperation.into())
+                    }
+                },
 
                 EditData::Person(mut person_data) => match self.operation {
                     EditOperation::Modify => {
@@ -439,17 +986,110 @@ impl EditModel {
                         }
                     }
 
-                    EditOperation::Create => todo!(),
-                    EditOperation::Delete => todo!(),
-                    EditOperation::Merge => todo!(),
+                    // Merges are parsed as EditData::PersonMerge.
+                    EditOperation::Create | EditOperation::Delete | EditOperation::Merge => {
+                        return Err(InternalError::UnsupportedEditOperation.into())
+                    }
+                },
+
+                EditData::BookMerge(mut merge_data) => {
+                    accept_register_book_merge(&mut merge_data, db).await?;
+
+                    self.update_end_data_and_status(Some(EditData::BookMerge(merge_data)), db)
+                        .await?;
+                }
+
+                EditData::PersonMerge(mut merge_data) => {
+                    accept_register_person_merge(&mut merge_data, db).await?;
+
+                    self.update_end_data_and_status(Some(EditData::PersonMerge(merge_data)), db)
+                        .await?;
+                }
+
+                EditData::Tag(mut tag_data) => match self.operation {
+                    EditOperation::Create => {
+                        let tag_model = accept_register_tag_create(&mut tag_data, db).await?;
+
+                        self.update_model_id(*tag_model.id, db).await?;
+
+                        self.update_end_data_and_status(Some(EditData::Tag(tag_data)), db)
+                            .await?;
+                    }
+
+                    EditOperation::Modify => {
+                        if let Some(tag_model) =
+                            TagModel::get_by_id(TagId::from(self.model_id.unwrap()), db).await?
+                        {
+                            accept_register_tag_data_overwrites(tag_model, &mut tag_data, db)
+                                .await?;
+
+                            self.update_end_data_and_status(Some(EditData::Tag(tag_data)), db)
+                                .await?;
+                        }
+                    }
+
+                    EditOperation::Delete | EditOperation::Merge => {
+                        return Err(InternalError::UnsupportedEditOperation.into())
+                    }
+                },
+
+                EditData::Collection(mut coll_data) => match self.operation {
+                    EditOperation::Modify => {
+                        if let Some(coll_model) = CollectionModel::find_by_id(
+                            CollectionId::from(self.model_id.unwrap()),
+                            db,
+                        )
+                        .await?
+                        {
+                            accept_register_collection_data_overwrites(
+                                coll_model,
+                                &mut coll_data,
+                                db,
+                            )
+                            .await?;
+
+                            self.update_end_data_and_status(
+                                Some(EditData::Collection(coll_data)),
+                                db,
+                            )
+                            .await?;
+                        }
+                    }
+
+                    EditOperation::Create | EditOperation::Delete | EditOperation::Merge => {
+                        return Err(InternalError::UnsupportedEditOperation.into())
+                    }
                 },
-                EditData::Tag => todo!(),
-                EditData::Collection => todo!(),
             }
         } else {
             self.update_end_data_and_status(None, db).await?;
         }
 
+        if !self.status.is_pending() {
+            MemberModel::update_reputation(Some(self.member_id), db).await?;
+
+            notify_edit_outcome(self, db).await?;
+        }
+
+        send_notification(WebsocketNotification::EditStatusChanged {
+            id: self.id,
+            status: self.status,
+        });
+
+        if self.status.is_accepted() {
+            match (self.type_of, self.model_id) {
+                (EditType::Book, Some(id)) => {
+                    send_notification(WebsocketNotification::BookUpdated(BookId::from(id)))
+                }
+
+                (EditType::Person, Some(id)) => {
+                    send_notification(WebsocketNotification::PersonUpdated(PersonId::from(id)))
+                }
+
+                _ => (),
+            }
+        }
+
         Ok(())
     }
 }
@@ -461,18 +1101,12 @@ pub async fn new_edit_data_from_book(
 ) -> Result<EditData> {
     // TODO: Cleaner, less complicated way?
 
-    let (current_people, curr_people_info) = if updated.added_people.is_some()
-        || updated.removed_people.is_some()
-        || updated.updated_people.is_some()
-    {
+    let curr_people_info = if updated.updated_people.is_some() {
         let bp = BookPersonModel::get_all_by_book_id(current.id, db).await?;
 
-        (
-            Some(bp.iter().map(|v| v.person_id).collect()),
-            Some(bp.into_iter().map(|v| (v.person_id, v.info)).collect()),
-        )
+        Some(bp.into_iter().map(|v| (v.person_id, v.info)).collect())
     } else {
-        (None, None)
+        None
     };
 
     let (title_old, title) = edit_translate::cmp_opt_string(current.title, updated.title);
@@ -492,12 +1126,11 @@ pub async fn new_edit_data_from_book(
         edit_translate::cmp_opt_partial_eq(Some(current.language), updated.language);
     let (display_person_id_old, display_person_id) =
         edit_translate::cmp_opt_partial_eq(current.cached.author_id, updated.display_person_id);
+    let (publisher_old, publisher) =
+        edit_translate::cmp_opt_string(current.cached.publisher, updated.publisher);
 
     let (updated_people_old, updated_people) =
         edit_translate::cmp_opt_partial_eq(curr_people_info, updated.updated_people);
-    // TODO: Will need to fix
-    let (removed_people_old, removed_people) =
-        edit_translate::cmp_opt_partial_eq(current_people, updated.removed_people);
 
 
     let isbn_models = BookIsbnModel::get_all(current.id, db).await?;
@@ -528,14 +1161,15 @@ pub async fn new_edit_data_from_book(
         available_at,
         language,
         display_person_id,
-        publisher: None, // TODO
+        publisher,
         added_isbns: updated.added_isbns,
         removed_isbns: updated.removed_isbns,
         updated_people,
+        // Already the difference from the current people.
         added_people: updated.added_people,
-        removed_people,
-        added_tags: None,
-        removed_tags: None,
+        removed_people: updated.removed_people,
+        added_tags: updated.added_tags,
+        removed_tags: updated.removed_tags,
         added_images: None,
         removed_images: None,
     };
@@ -549,12 +1183,12 @@ pub async fn new_edit_data_from_book(
         available_at: available_at_old,
         language: language_old,
         display_person_id: display_person_id_old,
-        publisher: None,
+        publisher: publisher_old,
         added_isbns: None,
         removed_isbns: None,
         updated_people: updated_people_old,
         added_people: None,
-        removed_people: removed_people_old,
+        removed_people: None,
         added_tags: None,
         removed_tags: None,
         added_images: None,
@@ -566,6 +1200,7 @@ pub async fn new_edit_data_from_book(
         new: Some(new).filter(|v| !v.is_empty()),
         old: Some(old).filter(|v| !v.is_empty()),
         updated: None,
+        conflicts: None,
     }))
 }
 
@@ -604,6 +1239,40 @@ pub async fn new_edit_data_from_person(
         new: Some(new).filter(|v| !v.is_empty()),
         old: Some(old).filter(|v| !v.is_empty()),
         updated: None,
+        conflicts: None,
+    }))
+}
+
+pub async fn new_edit_data_from_collection(
+    current: CollectionModel,
+    updated: UpdateCollectionModel,
+) -> Result<EditData> {
+    let (name_old, name) = edit_translate::cmp_opt_string(Some(current.name), updated.name);
+    // An empty description is used to unset the current one.
+    let (description_old, description) = edit_translate::cmp_opt_string(
+        current.description,
+        updated.description.map(|v| v.unwrap_or_default()),
+    );
+
+    let new = CollectionEdit {
+        name,
+        description,
+        added_books: updated.added_books.filter(|v| !v.is_empty()),
+    };
+
+    let old = CollectionEdit {
+        name: name_old,
+        description: description_old,
+        added_books: None,
+    };
+
+    Ok(EditData::Collection(CollectionEditData {
+        current: None,
+        // Keep an empty new value if we're only unsetting values.
+        new: Some(new).filter(|v| !v.is_empty() || !old.is_empty()),
+        old: Some(old).filter(|v| !v.is_empty()),
+        updated: None,
+        conflicts: None,
     }))
 }
 
@@ -612,11 +1281,77 @@ pub fn convert_data_to_string(type_of: EditType, value: &EditData) -> Result<Str
     Ok(match (type_of, value) {
         (EditType::Book, EditData::Book(book)) => serde_json::to_string(&book)?,
         (EditType::Person, EditData::Person(book)) => serde_json::to_string(&book)?,
+        (EditType::Book, EditData::BookMerge(merge)) => serde_json::to_string(&merge)?,
+        (EditType::Person, EditData::PersonMerge(merge)) => serde_json::to_string(&merge)?,
+        (EditType::Tag, EditData::Tag(tag)) => serde_json::to_string(&tag)?,
+        (EditType::Collection, EditData::Collection(coll)) => serde_json::to_string(&coll)?,
 
-        v => todo!("convert_data_to_string: {:?}", v),
+        _ => return Err(InternalError::EditDataMismatch.into()),
     })
 }
 
+/// Creates the values needed to undo an accepted book edit.
+///
+/// Only fields which were actually updated by the edit are restored.
+pub fn revert_book_edit_data(edit: &BookEditData) -> Option<BookEdit> {
+    let (old, new, updated) = match (&edit.old, &edit.new, &edit.updated) {
+        (old, Some(new), Some(updated)) => (old.clone().unwrap_or_default(), new, updated),
+        _ => return None,
+    };
+
+    // An empty string will unset the value.
+    let revert_string =
+        |is_updated: bool, value: Option<String>| is_updated.then(|| value.unwrap_or_default());
+
+    let value = BookEdit {
+        title: revert_string(updated.title, old.title),
+        clean_title: revert_string(updated.clean_title, old.clean_title),
+        description: revert_string(updated.description, old.description),
+        rating: old.rating.filter(|_| updated.rating),
+        is_public: old.is_public.filter(|_| updated.is_public),
+        available_at: old.available_at.filter(|_| updated.available_at),
+        language: old.language.filter(|_| updated.language),
+        display_person_id: old.display_person_id.filter(|_| updated.display_person_id),
+        publisher: old.publisher.filter(|_| updated.publisher),
+        updated_people: old.updated_people.filter(|_| updated.updated_people),
+        added_people: new
+            .removed_people
+            .clone()
+            .filter(|_| updated.removed_people),
+        removed_people: new.added_people.clone().filter(|_| updated.added_people),
+        added_tags: new.removed_tags.clone().filter(|_| updated.removed_tags),
+        removed_tags: new.added_tags.clone().filter(|_| updated.added_tags),
+        added_images: None,
+        removed_images: None,
+        added_isbns: new.removed_isbns.clone().filter(|_| updated.removed_isbns),
+        removed_isbns: new.added_isbns.clone().filter(|_| updated.added_isbns),
+    };
+
+    Some(value).filter(|v| !v.is_empty())
+}
+
+/// Creates the values needed to undo an accepted person edit.
+///
+/// Only fields which were actually updated by the edit are restored.
+pub fn revert_person_edit_data(edit: &PersonEditData) -> Option<PersonEdit> {
+    let (old, updated) = match (&edit.old, &edit.updated) {
+        (old, Some(updated)) => (old.clone().unwrap_or_default(), updated),
+        _ => return None,
+    };
+
+    let value = PersonEdit {
+        name: old.name.filter(|_| updated.name),
+        description: updated
+            .description
+            .then(|| old.description.unwrap_or_default()),
+        birth_date: old.birth_date.filter(|_| updated.birth_date),
+        added_images: None,
+        removed_images: None,
+    };
+
+    Some(value).filter(|v| !v.is_empty())
+}
+
 /// Update: BookModel
 ///
 /// Link: Tags, People, Images
@@ -630,63 +1365,21 @@ pub async fn accept_register_book_data_overwrites(
         _ => return Ok(()),
     };
 
-    let mut book_edits = UpdatedBookEdit::default();
+    let (mut book_edits, book_conflicts) =
+        merge_book_edit_fields(&mut book_model, old, new.clone());
 
-    // Update Book
-    cmp_opt_old_and_new_return(
-        &mut book_edits.title,
-        &mut book_model.title,
-        old.title,
-        new.title,
-    );
-    cmp_opt_old_and_new_return(
-        &mut book_edits.clean_title,
-        &mut book_model.clean_title,
-        old.clean_title,
-        new.clean_title,
-    );
-    cmp_opt_old_and_new_return(
-        &mut book_edits.description,
-        &mut book_model.description,
-        old.description,
-        new.description,
-    );
-    cmp_opt_old_and_new_return(
-        &mut book_edits.available_at,
-        &mut book_model
-            .available_at
-            .map(|v| v.and_hms(0, 0, 0).timestamp()),
-        old.available_at,
-        new.available_at,
-    );
-    cmp_old_and_new_return(
-        &mut book_edits.language,
-        &mut book_model.language,
-        old.language,
-        new.language,
-    );
-    cmp_old_and_new_return(
-        &mut book_edits.rating,
-        &mut book_model.rating,
-        old.rating,
-        new.rating,
-    );
-    cmp_old_and_new_return(
-        &mut book_edits.is_public,
-        &mut book_model.is_public,
-        old.is_public,
-        new.is_public,
-    );
+    // The lists are always applied so we only need to record which ones were present.
+    fn has_values<V>(value: &Option<Vec<V>>) -> bool {
+        value.as_ref().map_or(false, |v| !v.is_empty())
+    }
 
-    cmp_old_and_new_return(
-        &mut book_edits.display_person_id,
-        book_model
-            .cached
-            .author_id
-            .get_or_insert_with(Default::default),
-        old.display_person_id,
-        new.display_person_id,
-    );
+    book_edits.added_tags = has_values(&new.added_tags);
+    book_edits.removed_tags = has_values(&new.removed_tags);
+    book_edits.added_people = has_values(&new.added_people);
+    book_edits.removed_people = has_values(&new.removed_people);
+    book_edits.added_isbns = has_values(&new.added_isbns);
+    book_edits.removed_isbns = has_values(&new.removed_isbns);
+    book_edits.updated_people = has_values(&new.updated_people);
 
     // If we updated display id then we'll also update the author name.
     if book_edits.display_person_id {
@@ -697,9 +1390,8 @@ pub async fn accept_register_book_data_overwrites(
         }
     }
 
-    // TODO: publisher
-
     edit.updated = Some(book_edits).filter(|v| !v.is_empty());
+    edit.conflicts = Some(book_conflicts).filter(|v| !v.is_empty());
 
     book_model.update_book(db).await?;
 
@@ -801,54 +1493,349 @@ pub async fn accept_register_person_data_overwrites(
         _ => return Ok(()),
     };
 
+    let (person_edits, person_conflicts) = merge_person_edit_fields(&mut person_model, old, new);
+
+    let update_name = person_edits.name;
+
+    edit.updated = Some(person_edits).filter(|v| !v.is_empty());
+    edit.conflicts = Some(person_conflicts).filter(|v| !v.is_empty());
+
+    person_model.update(db).await?;
+
+    // Update Book Name
+    if update_name {
+        BookPersonModel::update_book_caches(person_model.id, Some(person_model.name.clone()), db)
+            .await?;
+        // If the Person Alt Table contains the name + id then we'll remove it.
+        // TODO: Possibly add previous name to table.
+        PersonAltModel {
+            person_id: person_model.id,
+            name: person_model.name,
+        }
+        .remove(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the fields which were updated and the ones which conflicted with the current model.
+fn merge_book_edit_fields(
+    book_model: &mut BookModel,
+    old: BookEdit,
+    new: BookEdit,
+) -> (UpdatedBookEdit, UpdatedBookEdit) {
+    let mut book_edits = UpdatedBookEdit::default();
+    let mut book_conflicts = UpdatedBookEdit::default();
+
+    cmp_opt_old_and_new_return(
+        &mut book_edits.title,
+        &mut book_conflicts.title,
+        &mut book_model.title,
+        old.title,
+        new.title,
+    );
+    cmp_opt_old_and_new_return(
+        &mut book_edits.clean_title,
+        &mut book_conflicts.clean_title,
+        &mut book_model.clean_title,
+        old.clean_title,
+        new.clean_title,
+    );
+    cmp_opt_old_and_new_return(
+        &mut book_edits.description,
+        &mut book_conflicts.description,
+        &mut book_model.description,
+        old.description,
+        new.description,
+    );
+
+    // The edit stores the date as a timestamp.
+    let mut available_at = book_model
+        .available_at
+        .map(|v| v.and_hms(0, 0, 0).timestamp());
+
+    cmp_opt_old_and_new_return(
+        &mut book_edits.available_at,
+        &mut book_conflicts.available_at,
+        &mut available_at,
+        old.available_at,
+        new.available_at,
+    );
+
+    book_model.available_at = available_at
+        .and_then(timestamp_to_date)
+        .map(|v| v.naive_utc().date());
+
+    cmp_old_and_new_return(
+        &mut book_edits.language,
+        &mut book_conflicts.language,
+        &mut book_model.language,
+        old.language,
+        new.language,
+    );
+    cmp_old_and_new_return(
+        &mut book_edits.rating,
+        &mut book_conflicts.rating,
+        &mut book_model.rating,
+        old.rating,
+        new.rating,
+    );
+    cmp_old_and_new_return(
+        &mut book_edits.is_public,
+        &mut book_conflicts.is_public,
+        &mut book_model.is_public,
+        old.is_public,
+        new.is_public,
+    );
+
+    cmp_old_and_new_return(
+        &mut book_edits.display_person_id,
+        &mut book_conflicts.display_person_id,
+        book_model
+            .cached
+            .author_id
+            .get_or_insert_with(Default::default),
+        old.display_person_id,
+        new.display_person_id,
+    );
+    cmp_opt_old_and_new_return(
+        &mut book_edits.publisher,
+        &mut book_conflicts.publisher,
+        &mut book_model.cached.publisher,
+        old.publisher,
+        new.publisher,
+    );
+
+    (book_edits, book_conflicts)
+}
+
+/// Returns the fields which were updated and the ones which conflicted with the current model.
+fn merge_person_edit_fields(
+    person_model: &mut PersonModel,
+    old: PersonEdit,
+    new: PersonEdit,
+) -> (UpdatedPersonEdit, UpdatedPersonEdit) {
     let mut person_edits = UpdatedPersonEdit::default();
+    let mut person_conflicts = UpdatedPersonEdit::default();
 
-    // Update Person
     cmp_old_and_new_return(
         &mut person_edits.name,
+        &mut person_conflicts.name,
         &mut person_model.name,
         old.name,
         new.name,
     );
     cmp_opt_old_and_new_return(
         &mut person_edits.description,
+        &mut person_conflicts.description,
         &mut person_model.description,
         old.description,
         new.description,
     );
     cmp_opt_old_and_new_return(
         &mut person_edits.birth_date,
+        &mut person_conflicts.birth_date,
         &mut person_model.birth_date,
         old.birth_date.and_then(|v| NaiveDate::from_str(&v).ok()),
         new.birth_date.and_then(|v| NaiveDate::from_str(&v).ok()),
     );
 
-    let update_name = person_edits.name;
+    (person_edits, person_conflicts)
+}
 
-    edit.updated = Some(person_edits).filter(|v| !v.is_empty());
+/// Checks a pending edit against the current model without applying it.
+pub fn find_book_conflicts(book_model: &BookModel, edit: &BookEditData) -> Option<UpdatedBookEdit> {
+    let new = edit.new.clone()?;
 
-    person_model.update(db).await?;
+    let (_, conflicts) = merge_book_edit_fields(
+        &mut book_model.clone(),
+        edit.old.clone().unwrap_or_default(),
+        new,
+    );
 
-    // Update Book Name
-    if update_name {
-        BookPersonModel::update_book_caches(person_model.id, Some(person_model.name.clone()), db)
-            .await?;
-        // If the Person Alt Table contains the name + id then we'll remove it.
-        // TODO: Possibly add previous name to table.
-        PersonAltModel {
-            person_id: person_model.id,
-            name: person_model.name,
+    Some(conflicts).filter(|v| !v.is_empty())
+}
+
+/// Checks a pending edit against the current model without applying it.
+pub fn find_person_conflicts(
+    person_model: &PersonModel,
+    edit: &PersonEditData,
+) -> Option<UpdatedPersonEdit> {
+    let new = edit.new.clone()?;
+
+    let (_, conflicts) = merge_person_edit_fields(
+        &mut person_model.clone(),
+        edit.old.clone().unwrap_or_default(),
+        new,
+    );
+
+    Some(conflicts).filter(|v| !v.is_empty())
+}
+
+pub async fn accept_register_tag_create(edit: &mut TagEditData, db: &Client) -> Result<TagModel> {
+    let (name, type_of) = match edit.new.clone() {
+        Some(TagEdit {
+            name: Some(name),
+            type_of: Some(type_of),
+        }) => (name, type_of),
+        _ => return Err(InternalError::ItemMissing.into()),
+    };
+
+    let tag_model = NewTagModel { name, type_of }.insert(db).await?;
+
+    edit.updated = Some(UpdatedTagEdit {
+        name: true,
+        type_of: true,
+    });
+
+    Ok(tag_model)
+}
+
+pub async fn accept_register_tag_data_overwrites(
+    mut tag_model: TagModel,
+    edit: &mut TagEditData,
+    db: &Client,
+) -> Result<()> {
+    let (old, new) = match (edit.old.clone().unwrap_or_default(), edit.new.clone()) {
+        (a, Some(b)) => (a, b),
+        _ => return Ok(()),
+    };
+
+    let mut tag_edits = UpdatedTagEdit::default();
+    let mut tag_conflicts = UpdatedTagEdit::default();
+
+    cmp_old_and_new_return(
+        &mut tag_edits.name,
+        &mut tag_conflicts.name,
+        &mut tag_model.name,
+        old.name,
+        new.name,
+    );
+
+    // TagType has no default so we can't unset it.
+    let mut type_of = Some(tag_model.type_of.clone());
+    cmp_opt_old_and_new_return(
+        &mut tag_edits.type_of,
+        &mut tag_conflicts.type_of,
+        &mut type_of,
+        old.type_of,
+        new.type_of,
+    );
+
+    if let Some(type_of) = type_of {
+        tag_model.type_of = type_of;
+    }
+
+    edit.updated = Some(tag_edits).filter(|v| !v.is_empty());
+    edit.conflicts = Some(tag_conflicts).filter(|v| !v.is_empty());
+
+    tag_model.updated_at = Utc::now();
+    tag_model.update(db).await?;
+
+    Ok(())
+}
+
+pub async fn accept_register_collection_data_overwrites(
+    coll_model: CollectionModel,
+    edit: &mut CollectionEditData,
+    db: &Client,
+) -> Result<()> {
+    let (old, new) = match (edit.old.clone().unwrap_or_default(), edit.new.clone()) {
+        (a, Some(b)) => (a, b),
+        _ => return Ok(()),
+    };
+
+    let mut coll_edits = UpdatedCollectionEdit::default();
+    let mut coll_conflicts = UpdatedCollectionEdit::default();
+
+    let mut name = coll_model.name;
+    let mut description = coll_model.description;
+
+    cmp_old_and_new_return(
+        &mut coll_edits.name,
+        &mut coll_conflicts.name,
+        &mut name,
+        old.name,
+        new.name,
+    );
+    cmp_opt_old_and_new_return(
+        &mut coll_edits.description,
+        &mut coll_conflicts.description,
+        &mut description,
+        old.description,
+        new.description,
+    );
+
+    let mut added_books = Vec::new();
+
+    if let Some(values) = new.added_books {
+        for book_id in values {
+            if BookModel::get_by_id(book_id, db).await?.is_some() {
+                added_books.push(book_id);
+            }
         }
-        .remove(db)
-        .await?;
     }
 
+    coll_edits.added_books = !added_books.is_empty();
+
+    CollectionModel::update_by_id(
+        coll_model.id,
+        UpdateCollectionModel {
+            name: Some(name).filter(|_| coll_edits.name),
+            description: Some(description).filter(|_| coll_edits.description),
+            added_books: Some(added_books).filter(|v| !v.is_empty()),
+        },
+        db,
+    )
+    .await?;
+
+    edit.updated = Some(coll_edits).filter(|v| !v.is_empty());
+    edit.conflicts = Some(coll_conflicts).filter(|v| !v.is_empty());
+
+    Ok(())
+}
+
+pub async fn accept_register_book_merge(edit: &mut BookMergeData, db: &Client) -> Result<()> {
+    if BookModel::get_by_id(edit.source_id, db).await?.is_none()
+        || BookModel::get_by_id(edit.target_id, db).await?.is_none()
+    {
+        return Err(InternalError::ItemMissing.into());
+    }
+
+    BookModel::merge_into(edit.source_id, edit.target_id, db).await?;
+
+    // Replace the preview with the actual result.
+    edit.preview = BookModel::get_by_id(edit.target_id, db)
+        .await?
+        .map(|v| v.into());
+
+    Ok(())
+}
+
+pub async fn accept_register_person_merge(edit: &mut PersonMergeData, db: &Client) -> Result<()> {
+    if PersonModel::get_by_id(edit.source_id, db).await?.is_none()
+        || PersonModel::get_by_id(edit.target_id, db).await?.is_none()
+    {
+        return Err(InternalError::ItemMissing.into());
+    }
+
+    PersonModel::merge_into(edit.source_id, edit.target_id, db).await?;
+
+    // Replace the preview with the actual result.
+    edit.preview = PersonModel::get_by_id(edit.target_id, db)
+        .await?
+        .map(|v| v.into_public_person(None));
+
     Ok(())
 }
 
 /// Returns the new value if current and old are equal.
+///
+/// If the current value changed since the edit was made it's flagged as conflicted and left alone.
 fn cmp_old_and_new_return<V: PartialEq + Default>(
     edited: &mut bool,
+    conflicted: &mut bool,
     current: &mut V,
     old: Option<V>,
     new: Option<V>,
@@ -859,13 +1846,19 @@ fn cmp_old_and_new_return<V: PartialEq + Default>(
             if *current == old {
                 *current = new;
                 *edited = true;
+            } else if *current != new {
+                *conflicted = true;
             }
         }
 
         // If we are just inserting a new value.
         (None, Some(new)) => {
-            *current = new;
-            *edited = true;
+            if *current == V::default() {
+                *current = new;
+                *edited = true;
+            } else if *current != new {
+                *conflicted = true;
+            }
         }
 
         // If we're unsetting a value
@@ -874,6 +1867,8 @@ fn cmp_old_and_new_return<V: PartialEq + Default>(
                 // TODO: Determine if we should keep.
                 *current = V::default();
                 *edited = true;
+            } else if *current != V::default() {
+                *conflicted = true;
             }
         }
 
@@ -882,14 +1877,21 @@ fn cmp_old_and_new_return<V: PartialEq + Default>(
 }
 
 /// Returns the new value if current and old are equal.
+///
+/// If the current value changed since the edit was made it's flagged as conflicted and left alone.
 fn cmp_opt_old_and_new_return<V: PartialEq>(
     edited: &mut bool,
+    conflicted: &mut bool,
     current: &mut Option<V>,
     old: Option<V>,
     new: Option<V>,
 ) {
-    if (old.is_some() || new.is_some()) && *current == old {
-        *current = new;
-        *edited = true;
+    if old.is_some() || new.is_some() {
+        if *current == old {
+            *current = new;
+            *edited = true;
+        } else if *current != new {
+            *conflicted = true;
+        }
     }
 }
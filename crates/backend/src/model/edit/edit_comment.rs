@@ -1,10 +1,10 @@
 use chrono::{DateTime, Utc};
 use common::MemberId;
-use common_local::{EditCommentId, EditId};
+use common_local::{item::edit::SharedEditCommentModel, EditCommentId, EditId};
 use tokio_postgres::Client;
 
 use crate::{
-    model::{row_bigint_to_usize, row_int_to_usize, AdvRow, TableRow},
+    model::{row_bigint_to_usize, row_int_to_usize, AdvRow, MemberModel, TableRow},
     Result,
 };
 
@@ -16,6 +16,9 @@ pub struct NewEditCommentModel {
     pub deleted: bool,
 
     pub created_at: DateTime<Utc>,
+
+    /// The comment this is replying to.
+    pub parent_id: Option<EditCommentId>,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +32,9 @@ pub struct EditCommentModel {
     pub deleted: bool,
 
     pub created_at: DateTime<Utc>,
+
+    /// The comment this is replying to.
+    pub parent_id: Option<EditCommentId>,
 }
 
 impl TableRow for EditCommentModel {
@@ -43,18 +49,26 @@ impl TableRow for EditCommentModel {
             deleted: row.next()?,
 
             created_at: row.next()?,
+
+            parent_id: row.next()?,
         })
     }
 }
 
 impl NewEditCommentModel {
-    pub fn new(edit_id: EditId, member_id: MemberId, text: String) -> Self {
+    pub fn new(
+        edit_id: EditId,
+        member_id: MemberId,
+        text: String,
+        parent_id: Option<EditCommentId>,
+    ) -> Self {
         Self {
             edit_id,
             member_id,
             text,
             deleted: false,
             created_at: Utc::now(),
+            parent_id,
         }
     }
 
@@ -65,15 +79,16 @@ impl NewEditCommentModel {
             INSERT INTO edit_comment (
                 edit_id, member_id,
                 text, deleted,
-                created_at
+                created_at, parent_id
             )
-            VALUES ($1, $2, $3, $4, $5) RETURNING id"#,
+            VALUES ($1, $2, $3, $4, $5, $6) RETURNING id"#,
                 params![
                     self.edit_id,
                     *self.member_id as i32,
                     self.text,
                     self.deleted,
                     self.created_at,
+                    self.parent_id,
                 ],
             )
             .await?;
@@ -88,6 +103,8 @@ impl NewEditCommentModel {
             deleted: self.deleted,
 
             created_at: self.created_at,
+
+            parent_id: self.parent_id,
         })
     }
 }
@@ -102,7 +119,7 @@ impl EditCommentModel {
     ) -> Result<Vec<Self>> {
         if let Some(deleted) = deleted {
             let conn = client.query(
-                "SELECT * FROM edit_comment WHERE edit_id = $1 AND deleted = $2 LIMIT $3 OFFSET $4",
+                "SELECT * FROM edit_comment WHERE edit_id = $1 AND deleted = $2 ORDER BY id ASC LIMIT $3 OFFSET $4",
                 params![ edit_id, deleted, limit as i64, offset as i64 ]
             ).await?;
 
@@ -113,7 +130,7 @@ impl EditCommentModel {
         } else {
             let conn = client
                 .query(
-                    "SELECT * FROM edit_comment WHERE edit_id = $1 LIMIT $2 OFFSET $3",
+                    "SELECT * FROM edit_comment WHERE edit_id = $1 ORDER BY id ASC LIMIT $2 OFFSET $3",
                     params![edit_id, limit as i64, offset as i64],
                 )
                 .await?;
@@ -125,14 +142,59 @@ impl EditCommentModel {
         }
     }
 
-    pub async fn get_count(edit_id: EditId, client: &Client) -> Result<usize> {
-        row_bigint_to_usize(
-            client
-                .query_one(
-                    r#"SELECT COUNT(*) FROM edit_comment WHERE edit_id = $1"#,
-                    params![edit_id],
-                )
-                .await?,
-        )
+    pub async fn get_by_id(id: EditCommentId, client: &Client) -> Result<Option<Self>> {
+        client
+            .query_opt("SELECT * FROM edit_comment WHERE id = $1", params![id])
+            .await?
+            .map(Self::from_row)
+            .transpose()
+    }
+
+    pub async fn get_count(
+        edit_id: EditId,
+        deleted: Option<bool>,
+        client: &Client,
+    ) -> Result<usize> {
+        if let Some(deleted) = deleted {
+            row_bigint_to_usize(
+                client
+                    .query_one(
+                        r#"SELECT COUNT(*) FROM edit_comment WHERE edit_id = $1 AND deleted = $2"#,
+                        params![edit_id, deleted],
+                    )
+                    .await?,
+            )
+        } else {
+            row_bigint_to_usize(
+                client
+                    .query_one(
+                        r#"SELECT COUNT(*) FROM edit_comment WHERE edit_id = $1"#,
+                        params![edit_id],
+                    )
+                    .await?,
+            )
+        }
+    }
+
+    /// Comments are never removed, only flagged as deleted.
+    pub async fn soft_delete_by_id(id: EditCommentId, client: &Client) -> Result<u64> {
+        Ok(client
+            .execute(
+                "UPDATE edit_comment SET deleted = true WHERE id = $1",
+                params![id],
+            )
+            .await?)
+    }
+
+    pub fn into_shared_comment(self, member: Option<MemberModel>) -> SharedEditCommentModel {
+        SharedEditCommentModel {
+            id: self.id,
+            edit_id: self.edit_id,
+            member: member.map(|v| v.into()),
+            text: self.text,
+            deleted: self.deleted,
+            created_at: self.created_at,
+            parent_id: self.parent_id,
+        }
     }
 }
@@ -1,10 +1,11 @@
 use chrono::{DateTime, Utc};
 use common::MemberId;
-use common_local::{item::edit::SharedEditVoteModel, EditId, EditVoteId};
+use common_local::{edit::EditStatus, item::edit::SharedEditVoteModel, EditId, EditVoteId};
 use tokio_postgres::Client;
 
 use crate::{
-    model::{row_bigint_to_usize, row_int_to_usize, AdvRow, TableRow},
+    config::ConfigVoting,
+    model::{row_bigint_to_usize, row_int_to_usize, AdvRow, MemberModel, TableRow},
     Result,
 };
 
@@ -30,6 +31,58 @@ pub struct NewEditVoteModel {
     pub created_at: DateTime<Utc>,
 }
 
+/// Weighted votes for a single edit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VoteTally {
+    pub voters: usize,
+
+    pub approvals: usize,
+    pub rejections: usize,
+}
+
+impl VoteTally {
+    pub fn add(&mut self, vote: bool, weight: usize) {
+        self.voters += 1;
+
+        if vote {
+            self.approvals += weight;
+        } else {
+            self.rejections += weight;
+        }
+    }
+
+    /// Returns the status the edit should move to, if any.
+    ///
+    /// Before expiry an edit is only decided once the early decision weight is reached.
+    pub fn decide(&self, policy: &ConfigVoting, expired: bool) -> Option<EditStatus> {
+        let total = self.approvals + self.rejections;
+
+        let reached_early = policy
+            .early_decision_weight
+            .map(|weight| self.approvals >= weight || self.rejections >= weight)
+            .unwrap_or_default();
+
+        if !expired && !reached_early {
+            return None;
+        }
+
+        if self.voters < policy.min_voters || total == 0 {
+            // Keep waiting for more votes until it expires.
+            return if expired {
+                Some(EditStatus::Rejected)
+            } else {
+                None
+            };
+        }
+
+        if self.approvals as f32 / total as f32 >= policy.approval_ratio {
+            Some(EditStatus::Accepted)
+        } else {
+            Some(EditStatus::Rejected)
+        }
+    }
+}
+
 impl TableRow for EditVoteModel {
     fn create(row: &mut AdvRow) -> Result<Self> {
         Ok(Self {
@@ -148,6 +201,40 @@ impl EditVoteModel {
         conn.into_iter().map(Self::from_row).collect()
     }
 
+    /// Sums the votes for an edit. The author's own vote isn't counted.
+    pub async fn tally(
+        edit_id: EditId,
+        policy: &ConfigVoting,
+        client: &Client,
+    ) -> Result<VoteTally> {
+        let rows = client
+            .query(
+                r#"SELECT member.*, edit_vote.vote FROM edit_vote
+                JOIN edit ON edit.id = edit_vote.edit_id
+                JOIN member ON member.id = edit_vote.member_id
+                WHERE edit_vote.edit_id = $1 AND edit_vote.member_id != edit.member_id"#,
+                params![edit_id],
+            )
+            .await?;
+
+        let mut tally = VoteTally::default();
+
+        for row in rows {
+            let vote: bool = row.try_get(row.len() - 1)?;
+            let member = MemberModel::from_row(row)?;
+
+            tally.add(
+                vote,
+                policy.vote_weight(
+                    member.permissions.has_force_vote_perms(),
+                    member.is_trusted(),
+                ),
+            );
+        }
+
+        Ok(tally)
+    }
+
     pub async fn count_by_edit_id(edit_id: EditId, client: &Client) -> Result<usize> {
         row_bigint_to_usize(
             client
@@ -159,3 +246,91 @@ impl EditVoteModel {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(votes: &[(bool, usize)]) -> VoteTally {
+        let mut tally = VoteTally::default();
+
+        for &(vote, weight) in votes {
+            tally.add(vote, weight);
+        }
+
+        tally
+    }
+
+    #[test]
+    fn test_vote_weight() {
+        let policy = ConfigVoting::default();
+
+        assert_eq!(policy.vote_weight(false, false), 1);
+        assert_eq!(policy.vote_weight(true, false), policy.trusted_vote_weight);
+        assert_eq!(policy.vote_weight(false, true), policy.trusted_vote_weight);
+    }
+
+    #[test]
+    fn test_decide_without_votes() {
+        let policy = ConfigVoting::default();
+
+        // Nobody but the author voted so it's never accepted.
+        assert_eq!(tally(&[]).decide(&policy, false), None);
+        assert_eq!(tally(&[]).decide(&policy, true), Some(EditStatus::Rejected));
+
+        let policy = ConfigVoting {
+            min_voters: 3,
+            ..ConfigVoting::default()
+        };
+
+        assert_eq!(
+            tally(&[(true, 1), (true, 1)]).decide(&policy, true),
+            Some(EditStatus::Rejected)
+        );
+    }
+
+    #[test]
+    fn test_decide_on_expiry() {
+        let policy = ConfigVoting::default();
+
+        assert_eq!(tally(&[(true, 1)]).decide(&policy, false), None);
+        assert_eq!(
+            tally(&[(true, 1)]).decide(&policy, true),
+            Some(EditStatus::Accepted)
+        );
+
+        // Ties meet the approval ratio.
+        assert_eq!(
+            tally(&[(true, 1), (false, 1)]).decide(&policy, true),
+            Some(EditStatus::Accepted)
+        );
+
+        // A trusted rejection outweighs two approvals.
+        assert_eq!(
+            tally(&[(true, 1), (true, 1), (false, 3)]).decide(&policy, true),
+            Some(EditStatus::Rejected)
+        );
+    }
+
+    #[test]
+    fn test_decide_early() {
+        let policy = ConfigVoting::default();
+
+        assert_eq!(
+            tally(&[(true, 3), (true, 1), (true, 1)]).decide(&policy, false),
+            Some(EditStatus::Accepted)
+        );
+        assert_eq!(
+            tally(&[(false, 3), (false, 3)]).decide(&policy, false),
+            Some(EditStatus::Rejected)
+        );
+        assert_eq!(tally(&[(true, 3), (true, 1)]).decide(&policy, false), None);
+
+        let policy = ConfigVoting {
+            early_decision_weight: None,
+            ..ConfigVoting::default()
+        };
+
+        assert_eq!(tally(&[(true, 3), (true, 3)]).decide(&policy, false), None);
+    }
+}
@@ -1,11 +1,16 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use common::MemberId;
-use common_local::{item::member::MemberSettings, util::serialize_datetime, Permissions};
+use common_local::{
+    edit::EditStatus,
+    item::member::MemberSettings,
+    util::{serialize_datetime, serialize_datetime_opt},
+    MemberSuspension, Permissions,
+};
 use lazy_static::lazy_static;
 use serde::Serialize;
 use tokio_postgres::Client;
 
-use crate::Result;
+use crate::{config::get_config, Result};
 
 use super::{row_bigint_to_usize, row_int_to_usize, AdvRow, TableRow};
 
@@ -13,6 +18,9 @@ lazy_static! {
     pub static ref SYSTEM_MEMBER_ID: MemberId = MemberId::from(0);
 }
 
+/// Name of the member which edits, comments and votes are given to once their author is deleted.
+pub const DELETED_MEMBER_NAME: &str = "Deleted Member";
+
 pub struct NewMemberModel {
     pub name: String,
     pub email: Option<String>,
@@ -23,6 +31,8 @@ pub struct NewMemberModel {
 
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -41,6 +51,15 @@ pub struct MemberModel {
 
     #[serde(serialize_with = "serialize_datetime")]
     pub updated_at: DateTime<Utc>,
+
+    pub reputation: i32,
+
+    pub suspension_reason: Option<String>,
+    #[serde(serialize_with = "serialize_datetime_opt")]
+    pub suspended_until: Option<DateTime<Utc>>,
+
+    #[serde(serialize_with = "serialize_datetime_opt")]
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 impl TableRow for MemberModel {
@@ -54,16 +73,23 @@ impl TableRow for MemberModel {
             localsettings: row.next()?,
             created_at: row.next()?,
             updated_at: row.next()?,
+            reputation: row.next()?,
+            suspension_reason: row.next()?,
+            suspended_until: row.next()?,
+            email_verified_at: row.next()?,
         })
     }
 }
 
 impl From<MemberModel> for common_local::Member {
     fn from(value: MemberModel) -> common_local::Member {
+        let suspension = value.get_suspension();
+
         common_local::Member {
             id: value.id,
             name: value.name,
             email: value.email,
+            email_verified: value.email_verified_at.is_some(),
             permissions: value.permissions,
             localsettings: value
                 .localsettings
@@ -71,6 +97,8 @@ impl From<MemberModel> for common_local::Member {
                 .unwrap_or_default(),
             created_at: value.created_at,
             updated_at: value.updated_at,
+            reputation: value.reputation,
+            suspension,
         }
     }
 }
@@ -78,10 +106,10 @@ impl From<MemberModel> for common_local::Member {
 impl NewMemberModel {
     pub async fn insert(self, db: &Client) -> Result<MemberModel> {
         let row = db.query_one(
-            "INSERT INTO member (name, email, password, permissions, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
+            "INSERT INTO member (name, email, password, permissions, created_at, updated_at, email_verified_at) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
             params![
                 &self.name, self.email.as_ref(), self.password.as_ref(), self.permissions,
-                self.created_at, self.updated_at
+                self.created_at, self.updated_at, self.email_verified_at
             ]
         ).await?;
 
@@ -94,6 +122,10 @@ impl NewMemberModel {
             localsettings: self.localsettings,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            reputation: 0,
+            suspension_reason: None,
+            suspended_until: None,
+            email_verified_at: self.email_verified_at,
         })
     }
 }
@@ -139,7 +171,10 @@ impl MemberModel {
                 password = $4,
                 permissions = $5,
                 localsettings = $6,
-                updated_at = $7
+                updated_at = $7,
+                suspension_reason = $8,
+                suspended_until = $9,
+                email_verified_at = $10
             WHERE id = $1"#,
             params![
                 *self.id as i32,
@@ -149,6 +184,9 @@ impl MemberModel {
                 &self.permissions,
                 &self.localsettings,
                 self.updated_at,
+                &self.suspension_reason,
+                self.suspended_until,
+                self.email_verified_at,
             ],
         )
         .await?;
@@ -156,9 +194,194 @@ impl MemberModel {
         Ok(())
     }
 
+    /// Returns the suspension if it hasn't expired yet.
+    pub fn get_suspension(&self) -> Option<MemberSuspension> {
+        let reason = self.suspension_reason.clone()?;
+
+        if self.suspended_until.filter(|v| *v <= Utc::now()).is_some() {
+            return None;
+        }
+
+        Some(MemberSuspension {
+            reason,
+            until: self.suspended_until,
+        })
+    }
+
+    pub fn is_deleted_placeholder(&self) -> bool {
+        self.id != *SYSTEM_MEMBER_ID
+            && self.name == DELETED_MEMBER_NAME
+            && self.email.is_none()
+            && self.password.is_none()
+    }
+
+    /// Returns the member which deleted members' contributions are given to, creating it if needed.
+    pub async fn find_or_create_deleted_placeholder(db: &Client) -> Result<Self> {
+        let found = db
+            .query_opt(
+                "SELECT * FROM member WHERE id != 0 AND name = $1 AND email IS NULL AND password IS NULL ORDER BY id LIMIT 1",
+                params![DELETED_MEMBER_NAME],
+            )
+            .await?;
+
+        if let Some(row) = found {
+            return Self::from_row(row);
+        }
+
+        let now = Utc::now();
+
+        NewMemberModel {
+            name: DELETED_MEMBER_NAME.to_string(),
+            email: None,
+            password: None,
+            permissions: Permissions::empty(),
+            localsettings: None,
+            created_at: now,
+            updated_at: now,
+            email_verified_at: None,
+        }
+        .insert(db)
+        .await
+    }
+
+    /// Gives the member's edits, comments and votes to the deleted placeholder and removes the member.
+    ///
+    /// Their sessions, API tokens and linked servers are removed with them.
+    pub async fn delete_and_reattribute(self, db: &Client) -> Result<()> {
+        let placeholder = Self::find_or_create_deleted_placeholder(db).await?;
+
+        let (s, t) = (*self.id, *placeholder.id);
+
+        // NOTE: Sent as a single batch so it's executed inside an implicit transaction.
+        // No BEGIN/COMMIT since the client is shared. Only integers are formatted into it.
+        let sql = format!(
+            r#"
+            UPDATE edit SET member_id = {t} WHERE member_id = {s};
+
+            UPDATE edit_comment SET member_id = {t} WHERE member_id = {s};
+
+            -- The placeholder can only hold one vote per edit. The rest are removed with the member.
+            UPDATE edit_vote SET member_id = {t}
+                WHERE member_id = {s} AND edit_id NOT IN (SELECT edit_id FROM edit_vote WHERE member_id = {t});
+
+            DELETE FROM member_session WHERE member_id = {s};
+            DELETE FROM api_token WHERE member_id = {s};
+            DELETE FROM server_link WHERE member_id = {s};
+
+            DELETE FROM member WHERE id = {s};
+            "#
+        );
+
+        db.batch_execute(&sql).await?;
+
+        Ok(())
+    }
+
+    /// Scrubs the member's personal details while keeping their contributions attributed to them.
+    ///
+    /// The member is suspended indefinitely so existing sessions can no longer be used.
+    pub async fn anonymize(&mut self, db: &Client) -> Result<()> {
+        self.name = format!("Anonymous #{}", *self.id);
+        self.email = None;
+        self.email_verified_at = None;
+        self.password = None;
+        self.permissions = Permissions::empty();
+        self.localsettings = None;
+        self.suspension_reason = Some(String::from("Account deleted"));
+        self.suspended_until = None;
+
+        self.update(db).await
+    }
+
+    /// Whether this member's low-risk edits can skip voting.
+    pub fn is_trusted(&self) -> bool {
+        let config = get_config().reputation;
+
+        config
+            .trusted_threshold
+            .map(|threshold| {
+                self.reputation >= threshold
+                    && self.created_at + Duration::days(config.trusted_min_age_days) <= Utc::now()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Recalculates the reputation of one member, or every member if None.
+    ///
+    /// Accepted edits and votes which matched the outcome raise it. Rejected edits and
+    /// votes against the outcome lower it. Each month of account age adds a point, up to a year.
+    pub async fn update_reputation(member_id: Option<MemberId>, db: &Client) -> Result<u64> {
+        let sql = r#"
+            UPDATE member SET reputation = (
+                2 * (SELECT COUNT(*) FROM edit WHERE edit.member_id = member.id AND edit.status IN ($1, $2))
+                - 3 * (SELECT COUNT(*) FROM edit WHERE edit.member_id = member.id AND edit.status IN ($3, $4))
+                + (
+                    SELECT COUNT(*) FROM edit_vote JOIN edit ON edit.id = edit_vote.edit_id
+                    WHERE edit_vote.member_id = member.id AND edit.member_id != member.id AND (
+                        (edit.status IN ($1, $2) AND edit_vote.vote) OR
+                        (edit.status IN ($3, $4) AND NOT edit_vote.vote)
+                    )
+                )
+                - (
+                    SELECT COUNT(*) FROM edit_vote JOIN edit ON edit.id = edit_vote.edit_id
+                    WHERE edit_vote.member_id = member.id AND edit.member_id != member.id AND (
+                        (edit.status IN ($1, $2) AND NOT edit_vote.vote) OR
+                        (edit.status IN ($3, $4) AND edit_vote.vote)
+                    )
+                )
+                + LEAST(12, FLOOR(EXTRACT(EPOCH FROM NOW() - member.created_at) / 2592000))
+            )::INT"#;
+
+        Ok(if let Some(member_id) = member_id {
+            db.execute(
+                &format!("{sql} WHERE id = $5"),
+                params![
+                    EditStatus::Accepted,
+                    EditStatus::ForceAccepted,
+                    EditStatus::Rejected,
+                    EditStatus::ForceRejected,
+                    *member_id as i32
+                ],
+            )
+            .await?
+        } else {
+            db.execute(
+                &format!("{sql} WHERE id != 0"),
+                params![
+                    EditStatus::Accepted,
+                    EditStatus::ForceAccepted,
+                    EditStatus::Rejected,
+                    EditStatus::ForceRejected
+                ],
+            )
+            .await?
+        })
+    }
+
+    pub fn get_settings(&self) -> MemberSettings {
+        self.localsettings
+            .as_deref()
+            .and_then(|v| serde_json::from_str(v).ok())
+            .unwrap_or_default()
+    }
+
     pub fn set_settings(&mut self, value: MemberSettings) -> Result<()> {
         self.localsettings = Some(serde_json::to_string(&value)?);
 
         Ok(())
     }
+
+    /// Only saves the settings. Members loaded through an API token have restricted permissions
+    /// which mustn't be saved.
+    pub async fn update_settings(&mut self, db: &Client) -> Result<()> {
+        self.updated_at = Utc::now();
+
+        db.execute(
+            "UPDATE member SET localsettings = $2, updated_at = $3 WHERE id = $1",
+            params![*self.id as i32, &self.localsettings, self.updated_at],
+        )
+        .await?;
+
+        Ok(())
+    }
 }
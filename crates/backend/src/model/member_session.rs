@@ -0,0 +1,229 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use tokio_postgres::Client;
+
+use crate::{hash_token, Result};
+
+use super::{AdvRow, TableRow};
+
+/// A server-side session. Only the hash of the session key is stored.
+#[derive(Debug, Clone)]
+pub struct MemberSessionModel {
+    pub id: usize,
+
+    pub key_hash: String,
+    /// JSON encoded session state.
+    pub state: String,
+
+    pub member_id: Option<MemberId>,
+    /// Random ID set on login. Identifies the session to its member without exposing the key.
+    pub login_id: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TableRow for MemberSessionModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next::<i32>()? as usize,
+
+            key_hash: row.next()?,
+            state: row.next()?,
+
+            member_id: row
+                .next::<Option<i32>>()?
+                .map(|v| MemberId::from(v as usize)),
+            login_id: row.next()?,
+            user_agent: row.next()?,
+            ip_address: row.next()?,
+
+            created_at: row.next()?,
+            last_seen_at: row.next()?,
+            expires_at: row.next()?,
+        })
+    }
+}
+
+/// The login details of a session. Taken from the session state when it's saved.
+pub struct MemberSessionLogin {
+    pub member_id: MemberId,
+    pub login_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MemberSessionLogin {
+    #[allow(clippy::type_complexity)]
+    fn into_columns(
+        value: Option<Self>,
+        now: DateTime<Utc>,
+    ) -> (
+        Option<i32>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        DateTime<Utc>,
+    ) {
+        match value {
+            Some(v) => (
+                Some(*v.member_id as i32),
+                Some(v.login_id),
+                v.user_agent,
+                v.ip_address,
+                v.created_at,
+            ),
+            None => (None, None, None, None, now),
+        }
+    }
+}
+
+impl MemberSessionModel {
+    pub async fn save(
+        key: &str,
+        state: &str,
+        login: Option<MemberSessionLogin>,
+        expires_at: DateTime<Utc>,
+        db: &Client,
+    ) -> Result<u64> {
+        let now = Utc::now();
+
+        let (member_id, login_id, user_agent, ip_address, created_at) =
+            MemberSessionLogin::into_columns(login, now);
+
+        Ok(db.execute(
+            r#"INSERT INTO member_session (key_hash, state, member_id, login_id, user_agent, ip_address, created_at, last_seen_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+            params![
+                hash_token(key), state, member_id, login_id, user_agent, ip_address,
+                created_at, now, expires_at
+            ],
+        ).await?)
+    }
+
+    /// Replaces the state of an existing session. Returns 0 if it was removed in the meantime.
+    pub async fn update(
+        key: &str,
+        state: &str,
+        login: Option<MemberSessionLogin>,
+        expires_at: DateTime<Utc>,
+        db: &Client,
+    ) -> Result<u64> {
+        let now = Utc::now();
+
+        let (member_id, login_id, user_agent, ip_address, created_at) =
+            MemberSessionLogin::into_columns(login, now);
+
+        Ok(db.execute(
+            r#"UPDATE member_session SET state = $2, member_id = $3, login_id = $4, user_agent = $5, ip_address = $6, created_at = $7, last_seen_at = $8, expires_at = $9
+            WHERE key_hash = $1"#,
+            params![
+                hash_token(key), state, member_id, login_id, user_agent, ip_address,
+                created_at, now, expires_at
+            ],
+        ).await?)
+    }
+
+    pub async fn find_valid_by_key(key: &str, db: &Client) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM member_session WHERE key_hash = $1 AND expires_at > NOW()",
+            params![hash_token(key)],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    pub async fn exists_valid_by_login_id(
+        member_id: MemberId,
+        login_id: &str,
+        db: &Client,
+    ) -> Result<bool> {
+        Ok(db
+            .query_one(
+                "SELECT EXISTS(SELECT id FROM member_session WHERE member_id = $1 AND login_id = $2 AND expires_at > NOW())",
+                params![*member_id as i32, login_id],
+            )
+            .await?
+            .try_get(0)?)
+    }
+
+    pub async fn find_by_member_id(id: MemberId, db: &Client) -> Result<Vec<Self>> {
+        db.query(
+            "SELECT * FROM member_session WHERE member_id = $1 AND expires_at > NOW() ORDER BY last_seen_at DESC",
+            params![*id as i32],
+        )
+        .await?
+        .into_iter()
+        .map(Self::from_row)
+        .collect()
+    }
+
+    /// Extends the session. `last_seen_at` is only moved forward once a minute.
+    ///
+    /// Returns 0 if the session was removed in the meantime.
+    pub async fn update_expiry(key: &str, expires_at: DateTime<Utc>, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                r#"UPDATE member_session SET expires_at = $2,
+                last_seen_at = CASE WHEN last_seen_at < NOW() - INTERVAL '1 minute' THEN NOW() ELSE last_seen_at END
+                WHERE key_hash = $1"#,
+                params![hash_token(key), expires_at],
+            )
+            .await?)
+    }
+
+    pub async fn remove_by_key(key: &str, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_session WHERE key_hash = $1",
+                params![hash_token(key)],
+            )
+            .await?)
+    }
+
+    pub async fn remove_by_id(member_id: MemberId, id: usize, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_session WHERE member_id = $1 AND id = $2",
+                params![*member_id as i32, id as i32],
+            )
+            .await?)
+    }
+
+    pub async fn remove_by_member_id(id: MemberId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_session WHERE member_id = $1",
+                params![*id as i32],
+            )
+            .await?)
+    }
+
+    /// Removes every session of the member but the one with the login ID.
+    pub async fn remove_others_by_member_id(
+        id: MemberId,
+        login_id: Option<&str>,
+        db: &Client,
+    ) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_session WHERE member_id = $1 AND login_id IS DISTINCT FROM $2",
+                params![*id as i32, login_id],
+            )
+            .await?)
+    }
+
+    pub async fn remove_expired(db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_session WHERE expires_at <= NOW()",
+                params![],
+            )
+            .await?)
+    }
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use tokio_postgres::Client;
+
+use crate::{hash_token, Result};
+
+use super::{row_bigint_to_usize, AdvRow, TableRow};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberTokenType {
+    /// Verifies `email` and sets it as the member's email.
+    VerifyEmail,
+    ResetPassword,
+}
+
+impl MemberTokenType {
+    fn to_i16(self) -> i16 {
+        match self {
+            Self::VerifyEmail => 0,
+            Self::ResetPassword => 1,
+        }
+    }
+
+    fn from_i16(value: i16) -> Self {
+        match value {
+            1 => Self::ResetPassword,
+            _ => Self::VerifyEmail,
+        }
+    }
+}
+
+pub struct NewMemberTokenModel {
+    pub member_id: MemberId,
+
+    pub type_of: MemberTokenType,
+    pub token_hash: String,
+    pub email: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// An emailed single-use token. Only the hash of the token is stored.
+#[derive(Debug, Clone)]
+pub struct MemberTokenModel {
+    pub id: usize,
+
+    pub member_id: MemberId,
+
+    pub type_of: MemberTokenType,
+    pub token_hash: String,
+    pub email: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TableRow for MemberTokenModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next::<i32>()? as usize,
+
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+
+            type_of: MemberTokenType::from_i16(row.next()?),
+            token_hash: row.next()?,
+            email: row.next()?,
+
+            created_at: row.next()?,
+            expires_at: row.next()?,
+        })
+    }
+}
+
+impl NewMemberTokenModel {
+    pub async fn insert(self, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "INSERT INTO member_token (member_id, type_of, token_hash, email, created_at, expires_at) VALUES ($1, $2, $3, $4, $5, $6)",
+                params![
+                    *self.member_id as i32,
+                    self.type_of.to_i16(),
+                    &self.token_hash,
+                    &self.email,
+                    self.created_at,
+                    self.expires_at
+                ],
+            )
+            .await?)
+    }
+}
+
+impl MemberTokenModel {
+    /// Removes and returns the token if it's unexpired.
+    pub async fn take_valid(
+        value: &str,
+        type_of: MemberTokenType,
+        db: &Client,
+    ) -> Result<Option<Self>> {
+        db.query_opt(
+            "DELETE FROM member_token WHERE token_hash = $1 AND type_of = $2 AND expires_at > NOW() RETURNING *",
+            params![hash_token(value), type_of.to_i16()],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    pub async fn count_by_member_id_since(
+        member_id: MemberId,
+        type_of: MemberTokenType,
+        since: DateTime<Utc>,
+        db: &Client,
+    ) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one(
+                "SELECT COUNT(*) FROM member_token WHERE member_id = $1 AND type_of = $2 AND created_at > $3",
+                params![*member_id as i32, type_of.to_i16(), since],
+            )
+            .await?,
+        )
+    }
+
+    /// Removes the member's other tokens of the type. Used once one of them was used.
+    pub async fn remove_by_member_id(
+        member_id: MemberId,
+        type_of: MemberTokenType,
+        db: &Client,
+    ) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_token WHERE member_id = $1 AND type_of = $2",
+                params![*member_id as i32, type_of.to_i16()],
+            )
+            .await?)
+    }
+
+    /// Removes expired tokens which were created before `created_before`.
+    pub async fn remove_expired(created_before: DateTime<Utc>, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_token WHERE expires_at <= NOW() AND created_at < $1",
+                params![created_before],
+            )
+            .await?)
+    }
+}
@@ -0,0 +1,345 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use rand::Rng;
+use tokio_postgres::Client;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::{hash_token, Result};
+
+use super::{row_bigint_to_usize, AdvRow, TableRow};
+
+/// Shown in authenticator apps next to the account name.
+pub const TOTP_ISSUER: &str = "Librarian";
+
+const TOTP_DIGITS: usize = 6;
+const TOTP_STEP_SECONDS: u64 = 30;
+
+pub const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+pub struct NewMemberTotpModel {
+    pub member_id: MemberId,
+
+    /// Base32 encoded.
+    pub secret: String,
+
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MemberTotpModel {
+    pub member_id: MemberId,
+
+    /// Base32 encoded.
+    pub secret: String,
+    pub last_used_step: i64,
+
+    /// Unset until the member verified their first code.
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableRow for MemberTotpModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+
+            secret: row.next()?,
+            last_used_step: row.next()?,
+
+            enabled_at: row.next()?,
+            created_at: row.next()?,
+        })
+    }
+}
+
+impl NewMemberTotpModel {
+    pub fn generate(member_id: MemberId) -> Self {
+        Self {
+            member_id,
+            secret: Secret::generate_secret().to_encoded().to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Replaces a previous secret which was never enabled.
+    pub async fn insert(self, db: &Client) -> Result<MemberTotpModel> {
+        db.execute(
+            r#"INSERT INTO member_totp (member_id, secret, created_at) VALUES ($1, $2, $3)
+            ON CONFLICT (member_id) DO UPDATE SET secret = $2, last_used_step = 0, enabled_at = NULL, created_at = $3"#,
+            params![*self.member_id as i32, &self.secret, self.created_at],
+        )
+        .await?;
+
+        Ok(MemberTotpModel {
+            member_id: self.member_id,
+            secret: self.secret,
+            last_used_step: 0,
+            enabled_at: None,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl MemberTotpModel {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+
+    pub fn get_totp(&self, account_name: String) -> Result<TOTP> {
+        Ok(TOTP::new(
+            Algorithm::SHA1,
+            TOTP_DIGITS,
+            1,
+            TOTP_STEP_SECONDS,
+            Secret::Encoded(self.secret.clone()).to_bytes()?,
+            Some(TOTP_ISSUER.to_string()),
+            account_name,
+        )?)
+    }
+
+    /// Accepts the code of the current, previous or next time step.
+    ///
+    /// A step is only accepted once so an observed code can't be replayed.
+    pub async fn verify_code(&mut self, code: &str, db: &Client) -> Result<bool> {
+        let totp = self.get_totp(self.member_id.to_string())?;
+        let code = code.trim();
+
+        let current_step =
+            SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() / TOTP_STEP_SECONDS;
+
+        if let Some(step) = find_valid_step(&totp, code, current_step, self.last_used_step) {
+            db.execute(
+                "UPDATE member_totp SET last_used_step = $2 WHERE member_id = $1",
+                params![*self.member_id as i32, step as i64],
+            )
+            .await?;
+
+            self.last_used_step = step as i64;
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub async fn set_enabled(&mut self, db: &Client) -> Result<()> {
+        let now = Utc::now();
+
+        db.execute(
+            "UPDATE member_totp SET enabled_at = $2 WHERE member_id = $1",
+            params![*self.member_id as i32, now],
+        )
+        .await?;
+
+        self.enabled_at = Some(now);
+
+        Ok(())
+    }
+
+    pub async fn find_by_member_id(id: MemberId, db: &Client) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM member_totp WHERE member_id = $1",
+            params![*id as i32],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    pub async fn is_enabled_for_member(id: MemberId, db: &Client) -> Result<bool> {
+        Ok(Self::find_by_member_id(id, db)
+            .await?
+            .map(|v| v.is_enabled())
+            .unwrap_or_default())
+    }
+
+    /// Also removes the member's recovery codes.
+    pub async fn remove_by_member_id(id: MemberId, db: &Client) -> Result<u64> {
+        MemberRecoveryCodeModel::remove_by_member_id(id, db).await?;
+
+        Ok(db
+            .execute(
+                "DELETE FROM member_totp WHERE member_id = $1",
+                params![*id as i32],
+            )
+            .await?)
+    }
+}
+
+/// Single-use codes which can be used instead of a TOTP code. Only the hash of the code is stored.
+pub struct MemberRecoveryCodeModel;
+
+impl MemberRecoveryCodeModel {
+    /// Replaces the member's recovery codes. Returns the new codes.
+    pub async fn regenerate(member_id: MemberId, db: &Client) -> Result<Vec<String>> {
+        Self::remove_by_member_id(member_id, db).await?;
+
+        let codes = {
+            let mut rng = rand::thread_rng();
+
+            (0..RECOVERY_CODE_COUNT)
+                .map(|_| gen_recovery_code(&mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        let now = Utc::now();
+
+        for code in &codes {
+            db.execute(
+                "INSERT INTO member_recovery_code (member_id, code_hash, created_at) VALUES ($1, $2, $3)",
+                params![*member_id as i32, hash_token(&normalize_code(code)), now],
+            )
+            .await?;
+        }
+
+        Ok(codes)
+    }
+
+    /// Removes the code if it belongs to the member. Returns true if it did.
+    pub async fn take(member_id: MemberId, code: &str, db: &Client) -> Result<bool> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_recovery_code WHERE member_id = $1 AND code_hash = $2",
+                params![*member_id as i32, hash_token(&normalize_code(code))],
+            )
+            .await?
+            != 0)
+    }
+
+    pub async fn count_by_member_id(member_id: MemberId, db: &Client) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one(
+                "SELECT COUNT(*) FROM member_recovery_code WHERE member_id = $1",
+                params![*member_id as i32],
+            )
+            .await?,
+        )
+    }
+
+    pub async fn remove_by_member_id(member_id: MemberId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM member_recovery_code WHERE member_id = $1",
+                params![*member_id as i32],
+            )
+            .await?)
+    }
+}
+
+/// Returns the step of the current, previous or next time step which the code matches.
+///
+/// Steps at or before the last used one are skipped.
+fn find_valid_step(totp: &TOTP, code: &str, current_step: u64, last_used_step: i64) -> Option<u64> {
+    for step in [
+        current_step.saturating_sub(1),
+        current_step,
+        current_step + 1,
+    ] {
+        if step as i64 > last_used_step && totp.generate(step * TOTP_STEP_SECONDS) == code {
+            return Some(step);
+        }
+    }
+
+    None
+}
+
+/// A lowercase alphanumeric code split in half by a dash. e.g. `a1b2c-d3e4f`
+fn gen_recovery_code(rng: &mut impl Rng) -> String {
+    let value = rng
+        .sample_iter(rand::distributions::Alphanumeric)
+        .take(RECOVERY_CODE_LENGTH)
+        .map(|v| char::from(v).to_ascii_lowercase())
+        .collect::<String>();
+
+    let (left, right) = value.split_at(RECOVERY_CODE_LENGTH / 2);
+
+    format!("{left}-{right}")
+}
+
+/// Codes are accepted regardless of case and dashes.
+fn normalize_code(value: &str) -> String {
+    value
+        .chars()
+        .filter(|v| v.is_ascii_alphanumeric())
+        .map(|v| v.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_totp() -> TOTP {
+        TOTP::new(
+            Algorithm::SHA1,
+            TOTP_DIGITS,
+            1,
+            TOTP_STEP_SECONDS,
+            b"librarian-test-secret-value".to_vec(),
+            Some(TOTP_ISSUER.to_string()),
+            String::from("1"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_valid_step() {
+        let totp = test_totp();
+        let current_step = 1_000_000;
+
+        let code_at = |step: u64| totp.generate(step * TOTP_STEP_SECONDS);
+
+        // Codes of the neighbouring steps are accepted for clock drift.
+        for step in [current_step - 1, current_step, current_step + 1] {
+            assert_eq!(
+                find_valid_step(&totp, &code_at(step), current_step, 0),
+                Some(step)
+            );
+        }
+
+        // Anything further away isn't.
+        assert_eq!(
+            find_valid_step(&totp, &code_at(current_step - 2), current_step, 0),
+            None
+        );
+        assert_eq!(
+            find_valid_step(&totp, &code_at(current_step + 2), current_step, 0),
+            None
+        );
+
+        // A code can't be used again once its step was used.
+        assert_eq!(
+            find_valid_step(
+                &totp,
+                &code_at(current_step),
+                current_step,
+                current_step as i64
+            ),
+            None
+        );
+
+        assert_eq!(find_valid_step(&totp, "", current_step, 0), None);
+    }
+
+    #[test]
+    fn test_recovery_code() {
+        let code = gen_recovery_code(&mut rand::thread_rng());
+
+        assert_eq!(code.len(), RECOVERY_CODE_LENGTH + 1);
+        assert_eq!(code.find('-'), Some(RECOVERY_CODE_LENGTH / 2));
+        assert!(code
+            .chars()
+            .all(|v| v == '-' || v.is_ascii_digit() || v.is_ascii_lowercase()));
+
+        // The same code is accepted regardless of how it's typed.
+        assert_eq!(normalize_code("ab1cd-EF2GH"), "ab1cdef2gh");
+        assert_eq!(normalize_code(" AB1CD EF2GH "), "ab1cdef2gh");
+        assert_eq!(
+            hash_token(&normalize_code(&code)),
+            hash_token(&normalize_code(&code.to_uppercase().replace('-', "")))
+        );
+    }
+}
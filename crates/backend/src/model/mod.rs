@@ -1,6 +1,8 @@
 use tokio_postgres::{types::FromSql, Row};
 use crate::Result;
 
+mod api_token;
+mod audit_log;
 mod auth;
 mod book;
 mod book_isbn;
@@ -11,14 +13,25 @@ mod collection_item;
 mod edit;
 mod image;
 mod member;
+mod member_session;
+mod member_token;
+mod member_totp;
 mod metadata_search;
+mod notification;
 mod person;
 mod person_alt;
+mod redirect;
 mod search_global;
 mod search_servers;
 mod server_link;
+mod server_link_grant;
+mod server_link_quota;
 mod tag;
+mod task_run;
+mod watch;
 
+pub use api_token::*;
+pub use audit_log::*;
 pub use auth::*;
 pub use book::*;
 pub use book_isbn::*;
@@ -30,13 +43,22 @@ pub use edit::*;
 
 pub use self::image::*;
 pub use member::*;
+pub use member_session::*;
+pub use member_token::*;
+pub use member_totp::*;
 pub use metadata_search::*;
+pub use notification::*;
 pub use person::*;
 pub use person_alt::*;
+pub use redirect::*;
 pub use search_global::*;
 pub use search_servers::*;
 pub use server_link::*;
+pub use server_link_grant::*;
+pub use server_link_quota::*;
 pub use tag::*;
+pub use task_run::*;
+pub use watch::*;
 
 pub trait TableRow
 where
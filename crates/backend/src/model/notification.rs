@@ -0,0 +1,277 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{
+    notification::{NotificationType, SharedNotification},
+    EditCommentId, EditId, NotificationId,
+};
+use tokio_postgres::Client;
+
+use crate::Result;
+
+use super::{
+    row_bigint_to_usize, AdvRow, EditCommentModel, EditModel, TableRow, WatchModel,
+    SYSTEM_MEMBER_ID,
+};
+
+pub struct NewNotificationModel {
+    pub member_id: MemberId,
+
+    pub type_of: NotificationType,
+
+    pub edit_id: EditId,
+    pub comment_id: Option<EditCommentId>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationModel {
+    pub id: NotificationId,
+
+    pub member_id: MemberId,
+
+    pub type_of: NotificationType,
+
+    pub edit_id: EditId,
+    pub comment_id: Option<EditCommentId>,
+
+    pub is_read: bool,
+    pub is_emailed: bool,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableRow for NotificationModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next()?,
+
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+
+            type_of: row.next()?,
+
+            edit_id: row.next()?,
+            comment_id: row.next_opt()?,
+
+            is_read: row.next()?,
+            is_emailed: row.next()?,
+
+            created_at: row.next()?,
+        })
+    }
+}
+
+impl From<NotificationModel> for SharedNotification {
+    fn from(value: NotificationModel) -> Self {
+        Self {
+            id: value.id,
+            type_of: value.type_of,
+            edit_id: value.edit_id,
+            comment_id: value.comment_id,
+            is_read: value.is_read,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl NewNotificationModel {
+    pub fn new(member_id: MemberId, type_of: NotificationType, edit_id: EditId) -> Self {
+        Self {
+            member_id,
+            type_of,
+            edit_id,
+            comment_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub async fn insert(self, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "INSERT INTO notification (member_id, type_of, edit_id, comment_id, created_at) VALUES ($1, $2, $3, $4, $5)",
+                params![
+                    *self.member_id as i32,
+                    self.type_of,
+                    self.edit_id,
+                    self.comment_id,
+                    self.created_at
+                ],
+            )
+            .await?)
+    }
+}
+
+impl NotificationModel {
+    pub async fn find_by_member_id(
+        member_id: MemberId,
+        unread_only: bool,
+        offset: usize,
+        limit: usize,
+        db: &Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM notification WHERE member_id = $1 AND (NOT $2 OR is_read = false) ORDER BY id DESC LIMIT $3 OFFSET $4",
+                params![*member_id as i32, unread_only, limit as i64, offset as i64],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn count_by_member_id(
+        member_id: MemberId,
+        unread_only: bool,
+        db: &Client,
+    ) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one(
+                "SELECT COUNT(*) FROM notification WHERE member_id = $1 AND (NOT $2 OR is_read = false)",
+                params![*member_id as i32, unread_only],
+            )
+            .await?,
+        )
+    }
+
+    /// Updates every notification of the member if `ids` is None.
+    pub async fn update_is_read(
+        member_id: MemberId,
+        ids: Option<&[NotificationId]>,
+        is_read: bool,
+        db: &Client,
+    ) -> Result<u64> {
+        if let Some(ids) = ids {
+            let ids = ids.iter().map(|v| **v as i32).collect::<Vec<_>>();
+
+            Ok(db
+                .execute(
+                    "UPDATE notification SET is_read = $2 WHERE member_id = $1 AND id = ANY($3)",
+                    params![*member_id as i32, is_read, ids],
+                )
+                .await?)
+        } else {
+            Ok(db
+                .execute(
+                    "UPDATE notification SET is_read = $2 WHERE member_id = $1",
+                    params![*member_id as i32, is_read],
+                )
+                .await?)
+        }
+    }
+
+    /// Members with unread notifications which haven't been included in a digest yet.
+    pub async fn find_member_ids_pending_digest(db: &Client) -> Result<Vec<MemberId>> {
+        let values = db
+            .query(
+                "SELECT DISTINCT member_id FROM notification WHERE is_read = false AND is_emailed = false",
+                &[],
+            )
+            .await?;
+
+        values
+            .into_iter()
+            .map(|v| Ok(MemberId::from(v.try_get::<_, i32>(0)? as usize)))
+            .collect()
+    }
+
+    pub async fn find_pending_digest_by_member_id(
+        member_id: MemberId,
+        db: &Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM notification WHERE member_id = $1 AND is_read = false AND is_emailed = false ORDER BY id ASC",
+                params![*member_id as i32],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    /// Only the notifications which were sent. Ones created while sending are kept for the next digest.
+    pub async fn set_emailed_by_ids(
+        member_id: MemberId,
+        ids: &[NotificationId],
+        db: &Client,
+    ) -> Result<u64> {
+        let ids = ids.iter().map(|v| **v as i32).collect::<Vec<_>>();
+
+        Ok(db
+            .execute(
+                "UPDATE notification SET is_emailed = true WHERE member_id = $1 AND id = ANY($2)",
+                params![*member_id as i32, ids],
+            )
+            .await?)
+    }
+}
+
+/// Notifies members watching the model of the newly created edit.
+pub async fn notify_watchers_of_edit(edit: &EditModel, db: &Client) -> Result<()> {
+    let model_id = match edit.model_id {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    for member_id in WatchModel::find_member_ids_by_model(edit.type_of, model_id, db).await? {
+        if member_id != edit.member_id {
+            NewNotificationModel::new(member_id, NotificationType::WatchedEdit, edit.id)
+                .insert(db)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Notifies the author once their edit was accepted or rejected.
+pub async fn notify_edit_outcome(edit: &EditModel, db: &Client) -> Result<()> {
+    let type_of = if edit.status.is_accepted() {
+        NotificationType::EditAccepted
+    } else if edit.status.is_rejected() {
+        NotificationType::EditRejected
+    } else {
+        return Ok(());
+    };
+
+    if edit.member_id == *SYSTEM_MEMBER_ID {
+        return Ok(());
+    }
+
+    NewNotificationModel::new(edit.member_id, type_of, edit.id)
+        .insert(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Notifies the author of the edit (and of the parent comment) when someone else comments on it.
+pub async fn notify_edit_comment(
+    edit: &EditModel,
+    comment: &EditCommentModel,
+    parent: Option<&EditCommentModel>,
+    db: &Client,
+) -> Result<()> {
+    let mut member_ids = vec![edit.member_id];
+
+    // Replies also notify the author of the comment being replied to.
+    if let Some(parent) = parent {
+        if parent.member_id != edit.member_id {
+            member_ids.push(parent.member_id);
+        }
+    }
+
+    for member_id in member_ids {
+        if comment.member_id == member_id || member_id == *SYSTEM_MEMBER_ID {
+            continue;
+        }
+
+        NewNotificationModel {
+            comment_id: Some(comment.id),
+            ..NewNotificationModel::new(member_id, NotificationType::EditComment, edit.id)
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, NaiveDate, Utc};
-use common::{api::librarian::PublicAuthor, BookId, PersonId, Source, ThumbnailStore};
+use common::{api::librarian::PublicAuthor, BookId, ImageType, PersonId, Source, ThumbnailStore};
 use common_local::{
+    edit::EditType,
     util::{serialize_datetime, serialize_naivedate_opt},
     Person,
 };
@@ -8,7 +9,10 @@ use serde::Serialize;
 
 use crate::Result;
 
-use super::{row_bigint_to_usize, row_int_to_usize, AdvRow, PersonAltModel, TableRow};
+use super::{
+    row_bigint_to_usize, row_int_to_usize, AdvRow, BookModel, BookPersonModel, ModelRedirectModel,
+    PersonAltModel, TableRow,
+};
 
 #[derive(Debug)]
 pub struct NewPersonModel {
@@ -24,7 +28,7 @@ pub struct NewPersonModel {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PersonModel {
     pub id: PersonId,
 
@@ -241,6 +245,16 @@ impl PersonModel {
             .transpose()
     }
 
+    pub async fn get_by_ids(ids: &[PersonId], db: &tokio_postgres::Client) -> Result<Vec<Self>> {
+        let ids = ids.iter().map(|v| **v as i32).collect::<Vec<_>>();
+
+        let conn = db
+            .query("SELECT * FROM person WHERE id = ANY($1)", params![ids])
+            .await?;
+
+        conn.into_iter().map(Self::from_row).collect()
+    }
+
     pub async fn get_by_source(value: &str, db: &tokio_postgres::Client) -> Result<Option<Self>> {
         db.query_opt("SELECT * FROM person WHERE source = $1", params![value])
             .await?
@@ -280,6 +294,91 @@ impl PersonModel {
         Ok(())
     }
 
+    /// Returns the person or, if they were merged into another, the person they were merged into.
+    pub async fn get_by_id_or_redirect(
+        id: PersonId,
+        db: &tokio_postgres::Client,
+    ) -> Result<Option<Self>> {
+        if let Some(model) = Self::get_by_id(id, db).await? {
+            return Ok(Some(model));
+        }
+
+        match ModelRedirectModel::find_by_old_id(EditType::Person, *id, db).await? {
+            Some(redirect) => Self::get_by_id(PersonId::from(redirect.new_id), db).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Moves the alt names, books and images of `source_id` over to `target_id`, fills in any empty fields from the source,
+    /// leaves a redirect and then removes the source person.
+    pub async fn merge_into(
+        source_id: PersonId,
+        target_id: PersonId,
+        db: &tokio_postgres::Client,
+    ) -> Result<()> {
+        let (s, t) = (*source_id, *target_id);
+        let image_type = ImageType::Person.as_num();
+
+        // Update the cached author of the books which displayed the source person.
+        let mut book_cache_sql = String::new();
+
+        if let Some(target) = Self::get_by_id(target_id, db).await? {
+            for book_person in BookPersonModel::find_by_person_id(source_id, db).await? {
+                if let Some(mut book) = BookModel::get_by_id(book_person.book_id, db).await? {
+                    if book.cached.author_id == Some(source_id) {
+                        book.cached.author = Some(target.name.clone());
+                        book.cached.author_id = Some(target.id);
+
+                        // The cache is url encoded so it cannot contain any quotes.
+                        book_cache_sql.push_str(&format!(
+                            "UPDATE book SET cached = '{}' WHERE id = {};\n",
+                            book.cached.as_string(),
+                            *book.id
+                        ));
+                    }
+                }
+            }
+        }
+
+        // NOTE: Sent as a single batch so it's executed inside an implicit transaction.
+        // No BEGIN/COMMIT since the client is shared. Only integers and the url encoded
+        // book caches are formatted into it.
+        let sql = format!(
+            r#"
+            INSERT INTO person_alt (person_id, name)
+                SELECT {t}, name FROM person_alt WHERE person_id = {s} ON CONFLICT DO NOTHING;
+            INSERT INTO person_alt (person_id, name)
+                SELECT {t}, name FROM person WHERE id = {s} ON CONFLICT DO NOTHING;
+            DELETE FROM person_alt WHERE person_id = {t} AND name = (SELECT name FROM person WHERE id = {t});
+
+            INSERT INTO book_person (book_id, person_id, info)
+                SELECT book_id, {t}, info FROM book_person WHERE person_id = {s} ON CONFLICT DO NOTHING;
+
+            INSERT INTO image_link (image_id, link_id, type_of)
+                SELECT image_id, {t}, type_of FROM image_link WHERE link_id = {s} AND type_of = {image_type} ON CONFLICT DO NOTHING;
+            DELETE FROM image_link WHERE link_id = {s} AND type_of = {image_type};
+
+            UPDATE person AS tp SET
+                description = COALESCE(tp.description, sp.description),
+                birth_date = COALESCE(tp.birth_date, sp.birth_date),
+                thumb_url = COALESCE(tp.thumb_url, sp.thumb_url),
+                updated_at = NOW()
+            FROM person AS sp WHERE tp.id = {t} AND sp.id = {s};
+
+            {book_cache_sql}
+
+            {redirect}
+
+            DELETE FROM person WHERE id = {s};
+            "#,
+            redirect = ModelRedirectModel::create_batch_sql(EditType::Person, s, t),
+        );
+
+        db.batch_execute(&sql).await?;
+
+        Ok(())
+    }
+
     pub async fn remove_by_id(id: PersonId, db: &tokio_postgres::Client) -> Result<u64> {
         Ok(db
             .execute("DELETE FROM person WHERE id = $1", params![*id as i32])
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use common_local::edit::EditType;
+use tokio_postgres::Client;
+
+use crate::Result;
+
+use super::{AdvRow, TableRow};
+
+/// Points a removed (merged) model id to the model which it was merged into.
+pub struct ModelRedirectModel {
+    pub type_of: EditType,
+
+    pub old_id: usize,
+    pub new_id: usize,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableRow for ModelRedirectModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            type_of: row.next()?,
+            old_id: row.next::<i32>()? as usize,
+            new_id: row.next::<i32>()? as usize,
+            created_at: row.next()?,
+        })
+    }
+}
+
+impl ModelRedirectModel {
+    pub async fn find_by_old_id(
+        type_of: EditType,
+        old_id: usize,
+        db: &Client,
+    ) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM model_redirect WHERE type_of = $1 AND old_id = $2",
+            params![type_of, old_id as i32],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    /// SQL which redirects `old_id` to `new_id` and updates any previous redirects which pointed to `old_id`.
+    ///
+    /// Only contains integers so it's safe to use inside a batch statement.
+    pub fn create_batch_sql(type_of: EditType, old_id: usize, new_id: usize) -> String {
+        let type_of = u8::from(type_of);
+
+        format!(
+            r#"
+            UPDATE model_redirect SET new_id = {new_id} WHERE type_of = {type_of} AND new_id = {old_id};
+            INSERT INTO model_redirect (type_of, old_id, new_id, created_at) VALUES ({type_of}, {old_id}, {new_id}, NOW())
+                ON CONFLICT (type_of, old_id) DO UPDATE SET new_id = {new_id};
+            "#
+        )
+    }
+}
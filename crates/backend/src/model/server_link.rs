@@ -1,11 +1,15 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use common::MemberId;
-use common_local::{util::serialize_datetime, ServerLinkId};
+use common_local::{
+    api::SharedServerLink,
+    util::{serialize_datetime, serialize_datetime_opt},
+    ServerLinkId,
+};
 use serde::Serialize;
 
 use crate::Result;
 
-use super::{row_int_to_usize, AdvRow, TableRow};
+use super::{row_bigint_to_usize, row_int_to_usize, AdvRow, TableRow};
 
 #[derive(Debug)]
 pub struct NewServerLinkModel {
@@ -37,6 +41,9 @@ pub struct ServerLinkModel {
     pub created_at: DateTime<Utc>,
     #[serde(serialize_with = "serialize_datetime")]
     pub updated_at: DateTime<Utc>,
+    /// Last time the server searched through us.
+    #[serde(serialize_with = "serialize_datetime_opt")]
+    pub last_seen_at: Option<DateTime<Utc>>,
 }
 
 impl TableRow for ServerLinkModel {
@@ -54,10 +61,28 @@ impl TableRow for ServerLinkModel {
 
             created_at: row.next()?,
             updated_at: row.next()?,
+            last_seen_at: row.next_opt()?,
         })
     }
 }
 
+impl From<ServerLinkModel> for SharedServerLink {
+    fn from(value: ServerLinkModel) -> Self {
+        Self {
+            id: value.id,
+            server_owner_name: value.server_owner_name,
+            server_name: value.server_name,
+            public_id: value.public_id,
+            member_id: value.member_id,
+            verified: value.verified,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            last_seen_at: value.last_seen_at,
+            usage: None,
+        }
+    }
+}
+
 impl NewServerLinkModel {
     pub async fn insert(self, db: &tokio_postgres::Client) -> Result<ServerLinkModel> {
         let row = db.query_one(r#"
@@ -82,6 +107,7 @@ impl NewServerLinkModel {
 
             created_at: self.created_at,
             updated_at: self.updated_at,
+            last_seen_at: None,
         })
     }
 }
@@ -123,14 +149,65 @@ impl ServerLinkModel {
         .transpose()
     }
 
-    pub async fn get_by_id(id: MemberId, db: &tokio_postgres::Client) -> Result<Option<Self>> {
-        db.query_opt(
-            r#"SELECT * FROM server_link WHERE id = $1"#,
-            params![*id as i32],
+    pub async fn get_by_id(id: ServerLinkId, db: &tokio_postgres::Client) -> Result<Option<Self>> {
+        db.query_opt(r#"SELECT * FROM server_link WHERE id = $1"#, params![id])
+            .await?
+            .map(Self::from_row)
+            .transpose()
+    }
+
+    pub async fn find_by_member_id(
+        member_id: MemberId,
+        db: &tokio_postgres::Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM server_link WHERE member_id = $1 ORDER BY id ASC",
+                params![*member_id as i32],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn find_all(
+        offset: usize,
+        limit: usize,
+        db: &tokio_postgres::Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM server_link ORDER BY id DESC LIMIT $1 OFFSET $2",
+                params![limit as i64, offset as i64],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn count(db: &tokio_postgres::Client) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one("SELECT COUNT(*) FROM server_link", &[])
+                .await?,
         )
-        .await?
-        .map(Self::from_row)
-        .transpose()
+    }
+
+    /// Only written once a minute at most since it's called on every search.
+    pub async fn update_last_seen(id: ServerLinkId, db: &tokio_postgres::Client) -> Result<u64> {
+        let now = Utc::now();
+
+        Ok(db
+            .execute(
+                "UPDATE server_link SET last_seen_at = $2 WHERE id = $1 AND (last_seen_at IS NULL OR last_seen_at < $3)",
+                params![id, now, now - Duration::minutes(1)],
+            )
+            .await?)
+    }
+
+    pub async fn delete_by_id(id: ServerLinkId, db: &tokio_postgres::Client) -> Result<u64> {
+        Ok(db
+            .execute("DELETE FROM server_link WHERE id = $1", params![id])
+            .await?)
     }
 
     pub async fn update(&self, db: &tokio_postgres::Client) -> Result<u64> {
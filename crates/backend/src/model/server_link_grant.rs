@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{
+    api::SharedServerLinkGrant, api_token::ApiTokenScopes, ServerLinkGrantId, ServerLinkId,
+};
+use tokio_postgres::Client;
+
+use crate::Result;
+
+use super::{AdvRow, ServerLinkModel, TableRow};
+
+/// Lets a linked server act on behalf of the member within the granted scopes.
+///
+/// The server signs its requests and passes the member's ID in the `X-Librarian-Member-Id` header.
+#[derive(Debug, Clone)]
+pub struct ServerLinkGrantModel {
+    pub id: ServerLinkGrantId,
+
+    pub server_link_id: ServerLinkId,
+    pub member_id: MemberId,
+
+    pub scopes: ApiTokenScopes,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl TableRow for ServerLinkGrantModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next()?,
+
+            server_link_id: row.next()?,
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+
+            scopes: row.next()?,
+
+            created_at: row.next()?,
+            updated_at: row.next()?,
+            last_used_at: row.next_opt()?,
+        })
+    }
+}
+
+impl ServerLinkGrantModel {
+    pub fn into_shared(self, link: Option<ServerLinkModel>) -> SharedServerLinkGrant {
+        let (server_owner_name, server_name) = link
+            .map(|v| (v.server_owner_name, v.server_name))
+            .unwrap_or_default();
+
+        SharedServerLinkGrant {
+            id: self.id,
+            server_link_id: self.server_link_id,
+            server_owner_name,
+            server_name,
+            scopes: self.scopes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            last_used_at: self.last_used_at,
+        }
+    }
+
+    /// Replaces the scopes if the member already granted the server access.
+    pub async fn upsert(
+        server_link_id: ServerLinkId,
+        member_id: MemberId,
+        scopes: ApiTokenScopes,
+        db: &Client,
+    ) -> Result<Self> {
+        let now = Utc::now();
+
+        let row = db
+            .query_one(
+                r#"INSERT INTO server_link_grant (server_link_id, member_id, scopes, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $4)
+                ON CONFLICT (server_link_id, member_id) DO UPDATE SET scopes = $3, updated_at = $4
+                RETURNING *"#,
+                params![server_link_id, *member_id as i32, scopes, now],
+            )
+            .await?;
+
+        Self::from_row(row)
+    }
+
+    pub async fn find_by_member_id(member_id: MemberId, db: &Client) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM server_link_grant WHERE member_id = $1 ORDER BY id DESC",
+                params![*member_id as i32],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn get_by_server_link_and_member(
+        server_link_id: ServerLinkId,
+        member_id: MemberId,
+        db: &Client,
+    ) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM server_link_grant WHERE server_link_id = $1 AND member_id = $2",
+            params![server_link_id, *member_id as i32],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    pub async fn update_last_used(id: ServerLinkGrantId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE server_link_grant SET last_used_at = $2 WHERE id = $1",
+                params![id, Utc::now()],
+            )
+            .await?)
+    }
+
+    pub async fn remove(id: ServerLinkGrantId, member_id: MemberId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM server_link_grant WHERE id = $1 AND member_id = $2",
+                params![id, *member_id as i32],
+            )
+            .await?)
+    }
+
+    pub async fn remove_by_member_id(member_id: MemberId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM server_link_grant WHERE member_id = $1",
+                params![*member_id as i32],
+            )
+            .await?)
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use common_local::{api::ServerLinkUsage, ServerLinkId};
+use tokio_postgres::Client;
+
+use crate::Result;
+
+use super::{AdvRow, TableRow};
+
+const MINUTE_SECONDS: i64 = 60;
+const DAY_SECONDS: i64 = 60 * 60 * 24;
+
+pub struct ServerLinkNonceModel;
+
+impl ServerLinkNonceModel {
+    /// Returns `false` if the nonce was already used by the server.
+    pub async fn insert(server_link_id: ServerLinkId, nonce: &str, db: &Client) -> Result<bool> {
+        Ok(db
+            .execute(
+                "INSERT INTO server_link_nonce (server_link_id, nonce, created_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                params![server_link_id, nonce, Utc::now()],
+            )
+            .await?
+            != 0)
+    }
+
+    pub async fn remove_expired(before: DateTime<Utc>, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM server_link_nonce WHERE created_at < $1",
+                params![before],
+            )
+            .await?)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerLinkQuotaModel {
+    pub server_link_id: ServerLinkId,
+
+    /// Uses the configured default if None.
+    pub requests_per_minute: Option<i32>,
+    pub requests_per_day: Option<i32>,
+
+    pub minute_started_at: DateTime<Utc>,
+    pub minute_requests: i32,
+    pub day_started_at: DateTime<Utc>,
+    pub day_requests: i32,
+
+    pub total_requests: i64,
+    pub throttled_requests: i64,
+}
+
+impl TableRow for ServerLinkQuotaModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            server_link_id: row.next()?,
+
+            requests_per_minute: row.next_opt()?,
+            requests_per_day: row.next_opt()?,
+
+            minute_started_at: row.next()?,
+            minute_requests: row.next()?,
+            day_started_at: row.next()?,
+            day_requests: row.next()?,
+
+            total_requests: row.next()?,
+            throttled_requests: row.next()?,
+        })
+    }
+}
+
+impl From<ServerLinkQuotaModel> for ServerLinkUsage {
+    fn from(value: ServerLinkQuotaModel) -> Self {
+        let now = Utc::now();
+
+        Self {
+            requests_per_minute: value.requests_per_minute,
+            requests_per_day: value.requests_per_day,
+
+            // Counters of a window which already ended are outdated.
+            minute_requests: if value.minute_started_at == window_start(now, MINUTE_SECONDS) {
+                value.minute_requests
+            } else {
+                0
+            },
+            day_requests: if value.day_started_at == window_start(now, DAY_SECONDS) {
+                value.day_requests
+            } else {
+                0
+            },
+
+            total_requests: value.total_requests,
+            throttled_requests: value.throttled_requests,
+        }
+    }
+}
+
+impl ServerLinkQuotaModel {
+    pub fn minute_ends_at(&self) -> DateTime<Utc> {
+        self.minute_started_at + Duration::seconds(MINUTE_SECONDS)
+    }
+
+    pub fn day_ends_at(&self) -> DateTime<Utc> {
+        self.day_started_at + Duration::seconds(DAY_SECONDS)
+    }
+
+    /// Counts a request against the current minute and day. The counters restart once their window passed.
+    pub async fn increment(server_link_id: ServerLinkId, db: &Client) -> Result<Self> {
+        let now = Utc::now();
+
+        let row = db
+            .query_one(
+                r#"INSERT INTO server_link_quota (server_link_id, minute_started_at, minute_requests, day_started_at, day_requests, total_requests, throttled_requests)
+                VALUES ($1, $2, 1, $3, 1, 1, 0)
+                ON CONFLICT (server_link_id) DO UPDATE SET
+                    minute_requests = CASE WHEN server_link_quota.minute_started_at = $2 THEN server_link_quota.minute_requests + 1 ELSE 1 END,
+                    minute_started_at = $2,
+                    day_requests = CASE WHEN server_link_quota.day_started_at = $3 THEN server_link_quota.day_requests + 1 ELSE 1 END,
+                    day_started_at = $3,
+                    total_requests = server_link_quota.total_requests + 1
+                RETURNING *"#,
+                params![
+                    server_link_id,
+                    window_start(now, MINUTE_SECONDS),
+                    window_start(now, DAY_SECONDS)
+                ],
+            )
+            .await?;
+
+        Self::from_row(row)
+    }
+
+    pub async fn increment_throttled(server_link_id: ServerLinkId, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE server_link_quota SET throttled_requests = throttled_requests + 1 WHERE server_link_id = $1",
+                params![server_link_id],
+            )
+            .await?)
+    }
+
+    pub async fn find_by_server_link_id(
+        server_link_id: ServerLinkId,
+        db: &Client,
+    ) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM server_link_quota WHERE server_link_id = $1",
+            params![server_link_id],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    /// None resets the limit back to the configured default.
+    pub async fn update_limits(
+        server_link_id: ServerLinkId,
+        requests_per_minute: Option<i32>,
+        requests_per_day: Option<i32>,
+        db: &Client,
+    ) -> Result<u64> {
+        let now = Utc::now();
+
+        Ok(db
+            .execute(
+                r#"INSERT INTO server_link_quota (server_link_id, requests_per_minute, requests_per_day, minute_started_at, minute_requests, day_started_at, day_requests, total_requests, throttled_requests)
+                VALUES ($1, $2, $3, $4, 0, $5, 0, 0, 0)
+                ON CONFLICT (server_link_id) DO UPDATE SET requests_per_minute = $2, requests_per_day = $3"#,
+                params![
+                    server_link_id,
+                    requests_per_minute,
+                    requests_per_day,
+                    window_start(now, MINUTE_SECONDS),
+                    window_start(now, DAY_SECONDS)
+                ],
+            )
+            .await?)
+    }
+}
+
+/// Start of the fixed window of `length` seconds which `now` falls into.
+fn window_start(now: DateTime<Utc>, length: i64) -> DateTime<Utc> {
+    Utc.timestamp(now.timestamp() - now.timestamp().rem_euclid(length), 0)
+}
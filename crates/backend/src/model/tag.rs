@@ -53,11 +53,39 @@ impl TagModel {
             .transpose()
     }
 
+    pub async fn get_by_ids(ids: &[TagId], db: &tokio_postgres::Client) -> Result<Vec<Self>> {
+        let ids = ids.iter().map(|v| **v as i32).collect::<Vec<_>>();
+
+        let conn = db
+            .query("SELECT * FROM tag WHERE id = ANY($1)", params![ids])
+            .await?;
+
+        conn.into_iter().map(Self::from_row).collect()
+    }
+
     pub async fn get_all(db: &tokio_postgres::Client) -> Result<Vec<Self>> {
         let conn = db.query("SELECT * FROM tag", &[]).await?;
 
         conn.into_iter().map(Self::from_row).collect()
     }
+
+    pub async fn update(&self, db: &tokio_postgres::Client) -> Result<()> {
+        let (type_of, data) = self.type_of.clone().split();
+
+        db.execute(
+            "UPDATE tag SET name = $2, type_of = $3, data = $4, updated_at = $5 WHERE id = $1",
+            params![
+                *self.id as i32,
+                &self.name,
+                type_of as i16,
+                data,
+                self.updated_at
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
 }
 
 impl NewTagModel {
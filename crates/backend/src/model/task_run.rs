@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{task::*, TaskRunId};
+use tokio_postgres::Client;
+
+use crate::Result;
+
+use super::{row_bigint_to_usize, row_int_to_usize, AdvRow, TableRow};
+
+pub struct NewTaskRunModel {
+    pub name: String,
+    pub status: TaskStatus,
+
+    pub member_id: Option<MemberId>,
+
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskRunModel {
+    pub id: TaskRunId,
+
+    pub name: String,
+    pub status: TaskStatus,
+
+    pub member_id: Option<MemberId>,
+
+    pub error: Option<String>,
+
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl TableRow for TaskRunModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            id: row.next()?,
+
+            name: row.next()?,
+            status: row.next()?,
+
+            member_id: row.next_opt::<i32>()?.map(|v| MemberId::from(v as usize)),
+
+            error: row.next_opt()?,
+
+            started_at: row.next()?,
+            ended_at: row.next_opt()?,
+        })
+    }
+}
+
+impl From<TaskRunModel> for SharedTaskRun {
+    fn from(value: TaskRunModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            status: value.status,
+            member_id: value.member_id,
+            error: value.error,
+            started_at: value.started_at,
+            ended_at: value.ended_at,
+        }
+    }
+}
+
+impl NewTaskRunModel {
+    pub fn start(name: &str, member_id: Option<MemberId>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: TaskStatus::Running,
+            member_id,
+            started_at: Utc::now(),
+        }
+    }
+
+    pub async fn insert(self, db: &Client) -> Result<TaskRunModel> {
+        let row = db
+            .query_one(
+                "INSERT INTO task_run (name, status, member_id, started_at) VALUES ($1, $2, $3, $4) RETURNING id",
+                params![
+                    &self.name,
+                    self.status,
+                    self.member_id.map(|v| *v as i32),
+                    self.started_at,
+                ],
+            )
+            .await?;
+
+        Ok(TaskRunModel {
+            id: TaskRunId::from(row_int_to_usize(row)?),
+
+            name: self.name,
+            status: self.status,
+
+            member_id: self.member_id,
+
+            error: None,
+
+            started_at: self.started_at,
+            ended_at: None,
+        })
+    }
+}
+
+impl TaskRunModel {
+    pub async fn finish(
+        id: TaskRunId,
+        status: TaskStatus,
+        error: Option<String>,
+        db: &Client,
+    ) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE task_run SET status = $2, error = $3, ended_at = $4 WHERE id = $1",
+                params![id, status, error, Utc::now()],
+            )
+            .await?)
+    }
+
+    /// Runs which were interrupted by a restart will never finish. Marks them as failed.
+    pub async fn fail_all_running(db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "UPDATE task_run SET status = $1, error = $2, ended_at = $3 WHERE status = $4",
+                params![
+                    TaskStatus::Failed,
+                    "Interrupted by a restart.",
+                    Utc::now(),
+                    TaskStatus::Running
+                ],
+            )
+            .await?)
+    }
+
+    pub async fn find_last_by_name(name: &str, db: &Client) -> Result<Option<Self>> {
+        db.query_opt(
+            "SELECT * FROM task_run WHERE name = $1 ORDER BY id DESC LIMIT 1",
+            params![name],
+        )
+        .await?
+        .map(Self::from_row)
+        .transpose()
+    }
+
+    pub async fn find_by_name(
+        name: &str,
+        offset: usize,
+        limit: usize,
+        db: &Client,
+    ) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM task_run WHERE name = $1 ORDER BY id DESC LIMIT $2 OFFSET $3",
+                params![name, limit as i64, offset as i64],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn count_by_name(name: &str, db: &Client) -> Result<usize> {
+        row_bigint_to_usize(
+            db.query_one(
+                "SELECT COUNT(*) FROM task_run WHERE name = $1",
+                params![name],
+            )
+            .await?,
+        )
+    }
+}
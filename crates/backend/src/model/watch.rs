@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{edit::EditType, notification::SharedWatch};
+use tokio_postgres::Client;
+
+use crate::Result;
+
+use super::{AdvRow, TableRow};
+
+/// A member following a model. They're notified about new edits on it.
+pub struct WatchModel {
+    pub member_id: MemberId,
+
+    pub type_of: EditType,
+    pub model_id: usize,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableRow for WatchModel {
+    fn create(row: &mut AdvRow) -> Result<Self> {
+        Ok(Self {
+            member_id: MemberId::from(row.next::<i32>()? as usize),
+            type_of: row.next()?,
+            model_id: row.next::<i32>()? as usize,
+            created_at: row.next()?,
+        })
+    }
+}
+
+impl From<WatchModel> for SharedWatch {
+    fn from(value: WatchModel) -> Self {
+        Self {
+            type_of: value.type_of,
+            model_id: value.model_id,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl WatchModel {
+    pub fn new(member_id: MemberId, type_of: EditType, model_id: usize) -> Self {
+        Self {
+            member_id,
+            type_of,
+            model_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub async fn insert(&self, db: &Client) -> Result<u64> {
+        Ok(db
+            .execute(
+                "INSERT INTO watch (member_id, type_of, model_id, created_at) VALUES ($1, $2, $3, $4) ON CONFLICT DO NOTHING",
+                params![
+                    *self.member_id as i32,
+                    self.type_of,
+                    self.model_id as i32,
+                    self.created_at
+                ],
+            )
+            .await?)
+    }
+
+    pub async fn remove(
+        member_id: MemberId,
+        type_of: EditType,
+        model_id: usize,
+        db: &Client,
+    ) -> Result<u64> {
+        Ok(db
+            .execute(
+                "DELETE FROM watch WHERE member_id = $1 AND type_of = $2 AND model_id = $3",
+                params![*member_id as i32, type_of, model_id as i32],
+            )
+            .await?)
+    }
+
+    pub async fn find_by_member_id(member_id: MemberId, db: &Client) -> Result<Vec<Self>> {
+        let values = db
+            .query(
+                "SELECT * FROM watch WHERE member_id = $1 ORDER BY created_at DESC",
+                params![*member_id as i32],
+            )
+            .await?;
+
+        values.into_iter().map(Self::from_row).collect()
+    }
+
+    pub async fn find_member_ids_by_model(
+        type_of: EditType,
+        model_id: usize,
+        db: &Client,
+    ) -> Result<Vec<MemberId>> {
+        let values = db
+            .query(
+                "SELECT member_id FROM watch WHERE type_of = $1 AND model_id = $2",
+                params![type_of, model_id as i32],
+            )
+            .await?;
+
+        values
+            .into_iter()
+            .map(|v| Ok(MemberId::from(v.try_get::<_, i32>(0)? as usize)))
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{Datelike, Utc, Weekday};
+use common_local::item::member::DigestFrequency;
+use tokio_postgres::Client;
+
+use crate::{
+    config::get_config,
+    email::send_email,
+    model::{MemberModel, NotificationModel},
+    Result,
+};
+
+use super::CancelToken;
+
+/// Emails unread notifications to members who opted into a digest.
+///
+/// Scheduled daily. Weekly digests are only sent on Mondays.
+pub async fn task_send_email_digest(client: &Client, cancel: &CancelToken) -> Result<()> {
+    let email_config = match get_config().email {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let is_weekly_day = Utc::now().weekday() == Weekday::Mon;
+
+    for member_id in NotificationModel::find_member_ids_pending_digest(client).await? {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        let member = match MemberModel::get_by_id(member_id, client).await? {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let email = match member.email.as_deref() {
+            Some(v) => v,
+            None => continue,
+        };
+
+        match member.get_settings().email_digest {
+            Some(DigestFrequency::Daily) => (),
+            Some(DigestFrequency::Weekly) if is_weekly_day => (),
+            _ => continue,
+        }
+
+        let notifications =
+            NotificationModel::find_pending_digest_by_member_id(member_id, client).await?;
+
+        if notifications.is_empty() {
+            continue;
+        }
+
+        let lines = notifications
+            .iter()
+            .map(|v| format!("{} (Edit #{})", v.type_of.get_description(), v.edit_id))
+            .collect::<Vec<_>>();
+
+        let alt_text = format!(
+            "You have {} unread notifications on {}:\n\n{}",
+            lines.len(),
+            email_config.display_name,
+            lines.join("\n")
+        );
+
+        let main_html = format!(
+            "<p>You have {} unread notifications on {}:</p><ul>{}</ul>",
+            lines.len(),
+            email_config.display_name,
+            lines
+                .iter()
+                .map(|v| format!("<li>{v}</li>"))
+                .collect::<String>()
+        );
+
+        // Keep going if a single address fails. It'll be retried on the next run.
+        if let Err(e) = send_email(
+            email,
+            &format!("{} - Notifications", email_config.subject_line),
+            alt_text,
+            main_html,
+            &email_config,
+        ) {
+            eprintln!("{e}");
+            continue;
+        }
+
+        let ids = notifications.iter().map(|v| v.id).collect::<Vec<_>>();
+
+        NotificationModel::set_emailed_by_ids(member_id, &ids, client).await?;
+    }
+
+    Ok(())
+}
@@ -1,20 +1,247 @@
-use std::{thread, time::Duration};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::Result;
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use common_local::{
+    task::{TaskInfo, TaskStatus},
+    ws::{TaskType, UniqueId, WebsocketNotification},
+};
+use cron::Schedule;
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use tokio_postgres::Client;
 
+use crate::{
+    config::get_config,
+    http::send_notification,
+    model::{NewTaskRunModel, TaskRunModel},
+    Result,
+};
+
+mod book_cache;
+mod digest;
 mod pending;
+mod purge;
+mod reputation;
+
+/// How often we check if a scheduled task is due.
+const TICK: Duration = Duration::from_secs(30);
+
+pub struct Task {
+    pub name: &'static str,
+    pub description: &'static str,
+
+    run: for<'a> fn(&'a Client, &'a CancelToken) -> BoxFuture<'a, Result<()>>,
+}
+
+/// Set once an Admin cancels a running task.
+///
+/// Tasks check it between items and stop early so an item is never left partially processed.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+pub static TASKS: &[Task] = &[
+    Task {
+        name: "update_pending",
+        description: "Accepts or rejects pending edits which expired or have enough votes.",
+        run: |db, cancel| Box::pin(pending::task_update_pending(db, cancel)),
+    },
+    Task {
+        name: "purge_deleted_books",
+        description: "Permanently removes books which have been in the trash for too long.",
+        run: |db, cancel| Box::pin(purge::task_purge_deleted_books(db, cancel)),
+    },
+    Task {
+        name: "update_reputation",
+        description: "Recalculates the reputation of every member.",
+        run: |db, cancel| Box::pin(reputation::task_update_reputation(db, cancel)),
+    },
+    Task {
+        name: "refresh_book_cache",
+        description: "Re-caches the displayed person of every book.",
+        run: |db, cancel| Box::pin(book_cache::task_refresh_book_cache(db, cancel)),
+    },
+    Task {
+        name: "send_email_digest",
+        description: "Emails unread notifications to members who opted into a digest.",
+        run: |db, cancel| Box::pin(digest::task_send_email_digest(db, cancel)),
+    },
+    Task {
+        name: "purge_expired_auth",
+        description: "Removes expired login tokens, account links, sessions and request nonces.",
+        run: |db, cancel| Box::pin(purge::task_purge_expired_auth(db, cancel)),
+    },
+];
+
+lazy_static! {
+    /// Tasks which are currently running. Used to prevent overlapping runs and to cancel them.
+    static ref RUNNING: Mutex<HashMap<&'static str, CancelToken>> = Mutex::default();
+}
+
+pub fn find_task(name: &str) -> Option<&'static Task> {
+    TASKS.iter().find(|v| v.name == name)
+}
+
+pub fn is_running(name: &str) -> bool {
+    RUNNING.lock().unwrap().contains_key(name)
+}
+
+pub async fn get_task_info(task: &Task, db: &Client) -> Result<TaskInfo> {
+    Ok(TaskInfo {
+        name: task.name.to_string(),
+        description: task.description.to_string(),
+        schedule: get_config()
+            .tasks
+            .schedules
+            .get(task.name)
+            .filter(|v| !v.is_empty())
+            .cloned(),
+        is_running: is_running(task.name),
+        last_run: TaskRunModel::find_last_by_name(task.name, db)
+            .await?
+            .map(|v| v.into()),
+    })
+}
+
+/// Starts a task in the background. Returns None if it's already running.
+pub async fn run_task(
+    task: &'static Task,
+    member_id: Option<MemberId>,
+    db: web::Data<Client>,
+) -> Result<Option<TaskRunModel>> {
+    let cancel = CancelToken::default();
+
+    {
+        let mut running = RUNNING.lock().unwrap();
+
+        if running.contains_key(task.name) {
+            return Ok(None);
+        }
 
-pub fn start(db: actix_web::web::Data<tokio_postgres::Client>) -> thread::JoinHandle<Result<()>> {
+        running.insert(task.name, cancel.clone());
+    }
+
+    let model = match NewTaskRunModel::start(task.name, member_id)
+        .insert(&db)
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            RUNNING.lock().unwrap().remove(task.name);
+            return Err(e);
+        }
+    };
+
+    let run_id = model.id;
+
+    let notify_id = UniqueId::new();
+
+    send_notification(WebsocketNotification::new_task(
+        notify_id,
+        TaskType::Scheduled(task.name.to_string()),
+    ));
+
+    tokio::spawn(async move {
+        let result = (task.run)(&db, &cancel).await;
+
+        RUNNING.lock().unwrap().remove(task.name);
+
+        let (status, error) = match result {
+            Ok(_) if cancel.is_cancelled() => (TaskStatus::Cancelled, None),
+            Ok(_) => (TaskStatus::Succeeded, None),
+            Err(e) => (TaskStatus::Failed, Some(e.to_string())),
+        };
+
+        if let Err(e) = TaskRunModel::finish(run_id, status, error, &db).await {
+            eprintln!("{e}");
+        }
+
+        send_notification(WebsocketNotification::TaskEnd(notify_id));
+    });
+
+    Ok(Some(model))
+}
+
+/// Returns false if the task isn't running. The task stops once it finishes its current item.
+pub fn cancel_task(name: &str) -> bool {
+    if let Some(cancel) = RUNNING.lock().unwrap().get(name) {
+        cancel.cancel();
+        true
+    } else {
+        false
+    }
+}
+
+pub fn start(db: web::Data<Client>) -> thread::JoinHandle<Result<()>> {
     thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
 
         rt.block_on(async {
+            TaskRunModel::fail_all_running(&db).await?;
+
+            let mut next_runs: HashMap<&'static str, DateTime<Utc>> = HashMap::new();
+
             loop {
-                if let Err(e) = pending::task_update_pending(&*db).await {
-                    eprintln!("{}", e);
+                let now = Utc::now();
+                let schedules = get_config().tasks.schedules;
+
+                for task in TASKS {
+                    let schedule = match schedules.get(task.name) {
+                        Some(v) if !v.is_empty() => v,
+                        _ => {
+                            next_runs.remove(task.name);
+                            continue;
+                        }
+                    };
+
+                    let schedule = match Schedule::from_str(schedule) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            eprintln!("Invalid schedule for task {}: {e}", task.name);
+                            continue;
+                        }
+                    };
+
+                    match next_runs.get(task.name) {
+                        Some(next_run) if *next_run <= now => {
+                            if let Err(e) = run_task(task, None, db.clone()).await {
+                                eprintln!("{e}");
+                            }
+                        }
+
+                        Some(_) => continue,
+
+                        // First time seeing it. Only schedule the next run.
+                        None => (),
+                    }
+
+                    match schedule.after(&now).next() {
+                        Some(next) => next_runs.insert(task.name, next),
+                        None => next_runs.remove(task.name),
+                    };
                 }
 
-                tokio::time::sleep(Duration::from_secs(60 * 15)).await;
+                tokio::time::sleep(TICK).await;
             }
         })
     })
@@ -3,76 +3,61 @@ use common_local::edit::*;
 use tokio_postgres::Client;
 
 use crate::{
-    model::{EditModel, NewEditCommentModel, TableRow, SYSTEM_MEMBER_ID},
+    config::get_config,
+    model::{EditModel, EditVoteModel, NewEditCommentModel, TableRow, SYSTEM_MEMBER_ID},
     Result,
 };
 
-pub async fn task_update_pending(client: &Client) -> Result<()> {
-    let now = Utc::now();
-    let pending = u8::from(EditStatus::Pending);
-
-    let sql_rejected = "UPDATE edit SET status = $1, ended_at = $2, is_applied = true WHERE expires_at < $2 AND status = $3 AND vote_count < 0";
+use super::CancelToken;
 
-    {
-        // Get all rejected
-        let items = {
-            let conn = client
-                .query(
-                    sql_rejected,
-                    params![EditStatus::Rejected, now, pending as i16],
-                )
-                .await?;
-
-            conn.into_iter()
-                .map(EditModel::from_row)
-                .collect::<Result<Vec<_>>>()?
-        };
+pub async fn task_update_pending(client: &Client, cancel: &CancelToken) -> Result<()> {
+    let now = Utc::now();
+    let policy = get_config().voting;
 
-        for item in items {
-            NewEditCommentModel::new(
-                item.id,
-                *SYSTEM_MEMBER_ID,
-                String::from(r#"SYSTEM: Auto denied."#),
+    let items = {
+        let conn = client
+            .query(
+                "SELECT * FROM edit WHERE status = $1",
+                params![EditStatus::Pending],
             )
-            .insert(client)
             .await?;
+
+        conn.into_iter()
+            .map(EditModel::from_row)
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    for mut item in items {
+        if cancel.is_cancelled() {
+            break;
         }
-    }
 
-    // Reject All
-    client
-        .execute(
-            sql_rejected,
-            params![EditStatus::Rejected, now, pending as i16],
-        )
-        .await?;
+        let expired = item.expires_at.map(|v| v < now).unwrap_or_default();
 
-    {
-        // Get all approved
-        let items =
-            {
-                let conn = client.query(
-                "SELECT * FROM edit WHERE expires_at < $1 AND status = $2 AND vote_count >= 0",
-                params![ now, pending as i16 ],
-            ).await?;
+        let tally = EditVoteModel::tally(item.id, &policy, client).await?;
 
-                conn.into_iter()
-                    .map(EditModel::from_row)
-                    .collect::<Result<Vec<_>>>()?
-            };
+        let new_status = match tally.decide(&policy, expired) {
+            Some(v) => v,
+            None => continue,
+        };
 
-        for mut item in items {
-            item.process_status_change(EditStatus::Accepted, client)
-                .await?;
+        // Keep going so one broken edit doesn't hold up the rest.
+        if let Err(e) = item.process_status_change(new_status, client).await {
+            eprintln!("Unable to update edit {}: {e}", *item.id);
+            continue;
+        }
 
-            NewEditCommentModel::new(
-                item.id,
-                *SYSTEM_MEMBER_ID,
-                String::from(r#"SYSTEM: Auto accepted."#),
-            )
+        let message = if new_status.is_accepted() {
+            "SYSTEM: Auto accepted."
+        } else if tally.voters < policy.min_voters {
+            "SYSTEM: Auto denied. Not enough votes."
+        } else {
+            "SYSTEM: Auto denied."
+        };
+
+        NewEditCommentModel::new(item.id, *SYSTEM_MEMBER_ID, String::from(message))
             .insert(client)
             .await?;
-        }
     }
 
     Ok(())
@@ -0,0 +1,51 @@
+use chrono::{Duration, Utc};
+use tokio_postgres::Client;
+
+use crate::{
+    config::get_config,
+    model::{AuthModel, BookModel, MemberSessionModel, MemberTokenModel, ServerLinkNonceModel},
+    Result,
+};
+
+use super::CancelToken;
+
+pub async fn task_purge_deleted_books(client: &Client, cancel: &CancelToken) -> Result<()> {
+    let purge_before = Utc::now() - Duration::days(get_config().trash.purge_after_days);
+
+    for book in BookModel::find_deleted_before(purge_before, client).await? {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        BookModel::purge_by_id(book.id, client).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn task_purge_expired_auth(client: &Client, _cancel: &CancelToken) -> Result<()> {
+    let config = get_config().passwordless;
+
+    // Kept for the length of the limit window so they still count towards it.
+    let window = config.limit_window_minutes.max(config.token_expiry_minutes);
+
+    AuthModel::remove_expired(Utc::now() - Duration::minutes(window), client).await?;
+
+    // Password reset requests are throttled by the same window.
+    MemberTokenModel::remove_expired(
+        Utc::now() - Duration::minutes(config.limit_window_minutes),
+        client,
+    )
+    .await?;
+
+    MemberSessionModel::remove_expired(client).await?;
+
+    // Anything older is rejected by its timestamp before the nonce is checked.
+    ServerLinkNonceModel::remove_expired(
+        Utc::now() - Duration::seconds(get_config().linked_servers.signature_max_age_secs * 2),
+        client,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use tokio_postgres::Client;
+
+use crate::{model::MemberModel, Result};
+
+use super::CancelToken;
+
+pub async fn task_update_reputation(client: &Client, _cancel: &CancelToken) -> Result<()> {
+    MemberModel::update_reputation(None, client).await?;
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
-use std::sync::Mutex;
+use std::{collections::HashMap, net::IpAddr, sync::Mutex};
 
-use common_local::{AuthConfig, ConfigEmail, ConfigServer, SharedConfig};
+use common_local::{edit::EditType, AuthConfig, ConfigEmail, ConfigServer, SharedConfig};
 use lazy_static::lazy_static;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::Result;
 
@@ -43,10 +43,28 @@ pub async fn save_config() -> Result<()> {
 pub struct Config {
     pub database: DatabaseConfig,
     pub server: ConfigServer,
+    #[serde(default)]
+    pub proxy: ConfigProxy,
     pub auth: AuthConfig,
+    #[serde(default)]
+    pub passwordless: ConfigPasswordless,
+    #[serde(default)]
+    pub account: ConfigAccount,
+    #[serde(default)]
+    pub session: ConfigSession,
+    #[serde(default)]
+    pub linked_servers: ConfigLinkedServers,
     pub email: Option<ConfigEmail>,
     #[serde(default)]
     pub storage: ConfigStores,
+    #[serde(default)]
+    pub trash: ConfigTrash,
+    #[serde(default)]
+    pub voting: ConfigVoting,
+    #[serde(default)]
+    pub reputation: ConfigReputation,
+    #[serde(default)]
+    pub tasks: ConfigTasks,
 }
 
 impl Default for Config {
@@ -56,9 +74,18 @@ impl Default for Config {
                 url: String::from("postgresql://127.0.0.1:5433"),
             },
             server: ConfigServer::default(),
+            proxy: ConfigProxy::default(),
             auth: AuthConfig::default(),
+            passwordless: ConfigPasswordless::default(),
+            account: ConfigAccount::default(),
+            session: ConfigSession::default(),
+            linked_servers: ConfigLinkedServers::default(),
             email: Some(ConfigEmail::default()),
             storage: ConfigStores::default(),
+            trash: ConfigTrash::default(),
+            voting: ConfigVoting::default(),
+            reputation: ConfigReputation::default(),
+            tasks: ConfigTasks::default(),
         }
     }
 }
@@ -82,6 +109,262 @@ pub struct DatabaseConfig {
     pub url: String,
 }
 
+// Proxy
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigProxy {
+    /// Addresses of reverse proxies whose `X-Forwarded-For` and `Forwarded` headers are used for
+    /// the client's IP address. Anyone else could spoof them.
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+// Passwordless
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigPasswordless {
+    /// Minutes an emailed login link stays valid.
+    pub token_expiry_minutes: i64,
+    /// Window in minutes which the request limits apply to.
+    pub limit_window_minutes: i64,
+    /// Login emails which can be requested for a single email within the window.
+    pub max_requests_per_email: usize,
+    /// Login emails which can be requested from a single IP address within the window.
+    pub max_requests_per_ip: usize,
+}
+
+impl Default for ConfigPasswordless {
+    fn default() -> Self {
+        Self {
+            token_expiry_minutes: 15,
+            limit_window_minutes: 60,
+            max_requests_per_email: 3,
+            max_requests_per_ip: 10,
+        }
+    }
+}
+
+// Account
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigAccount {
+    /// Password logins are refused until the member verified their email.
+    pub require_verified_email: bool,
+    /// Hours an email verification link stays valid.
+    pub verification_expiry_hours: i64,
+    /// Minutes a password reset link stays valid.
+    pub reset_expiry_minutes: i64,
+    /// Admins have to set up two-factor authentication before their login completes.
+    pub require_admin_two_factor: bool,
+}
+
+impl Default for ConfigAccount {
+    fn default() -> Self {
+        Self {
+            require_verified_email: false,
+            verification_expiry_hours: 48,
+            reset_expiry_minutes: 60,
+            require_admin_two_factor: false,
+        }
+    }
+}
+
+// Session
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigSession {
+    /// Days a session stays valid without being used.
+    pub idle_timeout_days: i64,
+    /// Days until a login expires regardless of use.
+    pub max_age_days: i64,
+}
+
+impl Default for ConfigSession {
+    fn default() -> Self {
+        Self {
+            idle_timeout_days: 14,
+            max_age_days: 90,
+        }
+    }
+}
+
+// Linked Servers
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigLinkedServers {
+    /// Rejects requests which only pass their `server_id` instead of being signed.
+    pub require_signature: bool,
+    /// Seconds a signed request's timestamp may be off by.
+    pub signature_max_age_secs: i64,
+    /// Default limits for servers without their own. 0 is unlimited.
+    pub requests_per_minute: i32,
+    pub requests_per_day: i32,
+}
+
+impl Default for ConfigLinkedServers {
+    fn default() -> Self {
+        Self {
+            require_signature: false,
+            signature_max_age_secs: 300,
+            requests_per_minute: 60,
+            requests_per_day: 10_000,
+        }
+    }
+}
+
+// Trash
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigTrash {
+    /// Days a soft-deleted book stays in the trash before it's permanently removed.
+    pub purge_after_days: i64,
+}
+
+impl Default for ConfigTrash {
+    fn default() -> Self {
+        Self {
+            purge_after_days: 30,
+        }
+    }
+}
+
+// Voting
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigVoting {
+    /// Minimum amount of members, other than the author, who have to vote before an edit can be accepted.
+    pub min_voters: usize,
+    /// Weighted approvals divided by all weighted votes required to accept an edit.
+    pub approval_ratio: f32,
+    /// Once approvals or rejections reach this weight the edit is decided before it expires.
+    pub early_decision_weight: Option<usize>,
+    /// Weight of a vote from a member with Force Vote permissions or enough reputation to be trusted.
+    /// Everyone else counts as 1.
+    pub trusted_vote_weight: usize,
+    /// Days an edit stays open for voting.
+    pub expiry_days: ConfigVotingExpiry,
+}
+
+impl ConfigVoting {
+    pub fn vote_weight(&self, has_force_vote: bool, is_trusted: bool) -> usize {
+        if has_force_vote || is_trusted {
+            self.trusted_vote_weight
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for ConfigVoting {
+    fn default() -> Self {
+        Self {
+            min_voters: 1,
+            approval_ratio: 0.5,
+            early_decision_weight: Some(5),
+            trusted_vote_weight: 3,
+            expiry_days: ConfigVotingExpiry::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigVotingExpiry {
+    pub book: i64,
+    pub person: i64,
+    pub tag: i64,
+    pub collection: i64,
+}
+
+impl ConfigVotingExpiry {
+    pub fn get(&self, type_of: EditType) -> i64 {
+        match type_of {
+            EditType::Book => self.book,
+            EditType::Person => self.person,
+            EditType::Tag => self.tag,
+            EditType::Collection => self.collection,
+        }
+    }
+}
+
+impl Default for ConfigVotingExpiry {
+    fn default() -> Self {
+        Self {
+            book: 7,
+            person: 7,
+            tag: 3,
+            collection: 7,
+        }
+    }
+}
+
+// Reputation
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigReputation {
+    /// Reputation required for low-risk edits to be applied without voting. None disables it.
+    pub trusted_threshold: Option<i32>,
+    /// Account age in days required before a member can be trusted.
+    pub trusted_min_age_days: i64,
+}
+
+impl Default for ConfigReputation {
+    fn default() -> Self {
+        Self {
+            trusted_threshold: Some(50),
+            trusted_min_age_days: 30,
+        }
+    }
+}
+
+// Tasks
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigTasks {
+    /// Cron expression (including seconds) per task name. Tasks not listed only run when triggered.
+    ///
+    /// Merged over the default schedules. An empty expression disables a default schedule.
+    #[serde(deserialize_with = "merge_default_schedules")]
+    pub schedules: HashMap<String, String>,
+}
+
+fn merge_default_schedules<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, String>, D::Error> {
+    let mut schedules = ConfigTasks::default().schedules;
+
+    schedules.extend(HashMap::<String, String>::deserialize(deserializer)?);
+
+    Ok(schedules)
+}
+
+impl Default for ConfigTasks {
+    fn default() -> Self {
+        let every_fifteen_minutes = "0 */15 * * * *";
+        let every_morning = "0 0 8 * * *";
+
+        Self {
+            schedules: [
+                ("update_pending", every_fifteen_minutes),
+                ("purge_deleted_books", every_fifteen_minutes),
+                ("update_reputation", every_fifteen_minutes),
+                ("send_email_digest", every_morning),
+                ("purge_expired_auth", every_fifteen_minutes),
+            ]
+            .into_iter()
+            .map(|(name, schedule)| (name.to_string(), schedule.to_string()))
+            .collect(),
+        }
+    }
+}
+
 // Services
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -118,3 +401,22 @@ pub struct ConfigStoreB2 {
 
     pub directory: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tasks_merge_default_schedules() {
+        let tasks: ConfigTasks = serde_json::from_str(
+            r#"{ "schedules": { "update_reputation": "0 0 * * * *", "purge_expired_auth": "" } }"#,
+        )
+        .unwrap();
+
+        let schedule = |name: &str| tasks.schedules.get(name).map(String::as_str);
+
+        assert_eq!(schedule("update_pending"), Some("0 */15 * * * *"));
+        assert_eq!(schedule("update_reputation"), Some("0 0 * * * *"));
+        assert_eq!(schedule("purge_expired_auth"), Some(""));
+    }
+}
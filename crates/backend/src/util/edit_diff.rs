@@ -0,0 +1,331 @@
+use std::fmt::Display;
+
+use common::{BookId, ImageId, PersonId, TagId};
+use common_local::item::edit::*;
+use tokio_postgres::Client;
+
+use crate::{
+    model::{BookModel, PersonModel, TagModel, UploadedImageModel},
+    Result,
+};
+
+/// Builds the list of changed fields for an edit with People, Tags, Books and Images resolved.
+pub async fn create_edit_diff(data: &EditData, db: &Client) -> Result<Vec<EditFieldDiff>> {
+    let mut diff = Vec::new();
+
+    match data {
+        EditData::Book(data) => {
+            let new = match data.new.as_ref() {
+                Some(v) => v,
+                None => return Ok(diff),
+            };
+
+            let old = data.old.clone().unwrap_or_default();
+
+            // Resolve every referenced Person and Tag at once.
+            let person_ids = [old.display_person_id, new.display_person_id]
+                .into_iter()
+                .flatten()
+                .chain(new.added_people.iter().flatten().copied())
+                .chain(new.removed_people.iter().flatten().copied())
+                .chain(new.updated_people.iter().flatten().map(|(id, _)| *id))
+                .collect::<Vec<_>>();
+
+            let tag_ids = new
+                .added_tags
+                .iter()
+                .flatten()
+                .chain(new.removed_tags.iter().flatten())
+                .copied()
+                .collect::<Vec<_>>();
+
+            let people = PersonModel::get_by_ids(&person_ids, db).await?;
+            let tags = TagModel::get_by_ids(&tag_ids, db).await?;
+
+            diff.extend(value_diff("title", old.title, new.title.as_ref()));
+            diff.extend(value_diff(
+                "clean_title",
+                old.clean_title,
+                new.clean_title.as_ref(),
+            ));
+            diff.extend(value_diff(
+                "description",
+                old.description,
+                new.description.as_ref(),
+            ));
+            diff.extend(value_diff("rating", old.rating, new.rating.as_ref()));
+            diff.extend(value_diff(
+                "is_public",
+                old.is_public,
+                new.is_public.as_ref(),
+            ));
+            diff.extend(value_diff(
+                "available_at",
+                old.available_at,
+                new.available_at.as_ref(),
+            ));
+            diff.extend(value_diff("language", old.language, new.language.as_ref()));
+            diff.extend(value_diff(
+                "publisher",
+                old.publisher,
+                new.publisher.as_ref(),
+            ));
+
+            if let Some(person_id) = new.display_person_id {
+                diff.push(EditFieldDiff {
+                    field: String::from("display_person_id"),
+                    change: EditFieldChange::Value {
+                        before: old.display_person_id.map(|id| person_name(id, &people)),
+                        after: Some(person_name(person_id, &people)),
+                    },
+                });
+            }
+
+            if new.added_people.is_some() || new.removed_people.is_some() {
+                diff.push(EditFieldDiff {
+                    field: String::from("people"),
+                    change: EditFieldChange::List {
+                        added: person_names(new.added_people.as_deref(), &people),
+                        removed: person_names(new.removed_people.as_deref(), &people),
+                    },
+                });
+            }
+
+            if let Some(updated) = new.updated_people.as_ref() {
+                let added = updated
+                    .iter()
+                    .map(|(person_id, info)| {
+                        let name = person_name(*person_id, &people);
+
+                        match info {
+                            Some(info) => format!("{name} ({info})"),
+                            None => name,
+                        }
+                    })
+                    .collect();
+
+                diff.push(EditFieldDiff {
+                    field: String::from("updated_people"),
+                    change: EditFieldChange::List {
+                        added,
+                        removed: Vec::new(),
+                    },
+                });
+            }
+
+            if new.added_tags.is_some() || new.removed_tags.is_some() {
+                diff.push(EditFieldDiff {
+                    field: String::from("tags"),
+                    change: EditFieldChange::List {
+                        added: tag_names(new.added_tags.as_deref(), &tags),
+                        removed: tag_names(new.removed_tags.as_deref(), &tags),
+                    },
+                });
+            }
+
+            if new.added_isbns.is_some() || new.removed_isbns.is_some() {
+                diff.push(EditFieldDiff {
+                    field: String::from("isbns"),
+                    change: EditFieldChange::List {
+                        added: new.added_isbns.clone().unwrap_or_default(),
+                        removed: new.removed_isbns.clone().unwrap_or_default(),
+                    },
+                });
+            }
+
+            push_images(
+                &mut diff,
+                new.added_images.as_deref(),
+                new.removed_images.as_deref(),
+                db,
+            )
+            .await?;
+        }
+
+        EditData::Person(data) => {
+            let new = match data.new.as_ref() {
+                Some(v) => v,
+                None => return Ok(diff),
+            };
+
+            let old = data.old.clone().unwrap_or_default();
+
+            diff.extend(value_diff("name", old.name, new.name.as_ref()));
+            diff.extend(value_diff(
+                "description",
+                old.description,
+                new.description.as_ref(),
+            ));
+            diff.extend(value_diff(
+                "birth_date",
+                old.birth_date,
+                new.birth_date.as_ref(),
+            ));
+
+            push_images(
+                &mut diff,
+                new.added_images.as_deref(),
+                new.removed_images.as_deref(),
+                db,
+            )
+            .await?;
+        }
+
+        EditData::Tag(data) => {
+            let new = match data.new.as_ref() {
+                Some(v) => v,
+                None => return Ok(diff),
+            };
+
+            let old = data.old.clone().unwrap_or_default();
+
+            diff.extend(value_diff("name", old.name, new.name.as_ref()));
+            diff.extend(value_diff(
+                "type_of",
+                old.type_of.map(|v| format!("{v:?}")),
+                new.type_of.as_ref().map(|v| format!("{v:?}")).as_ref(),
+            ));
+        }
+
+        EditData::Collection(data) => {
+            let new = match data.new.as_ref() {
+                Some(v) => v,
+                None => return Ok(diff),
+            };
+
+            let old = data.old.clone().unwrap_or_default();
+
+            diff.extend(value_diff("name", old.name, new.name.as_ref()));
+            diff.extend(value_diff(
+                "description",
+                old.description,
+                new.description.as_ref(),
+            ));
+
+            if let Some(book_ids) = new.added_books.as_deref() {
+                diff.push(EditFieldDiff {
+                    field: String::from("books"),
+                    change: EditFieldChange::List {
+                        added: book_titles(book_ids, db).await?,
+                        removed: Vec::new(),
+                    },
+                });
+            }
+        }
+
+        EditData::BookMerge(data) => {
+            diff.push(EditFieldDiff {
+                field: String::from("merge"),
+                change: EditFieldChange::Value {
+                    before: book_titles(&[data.source_id], db).await?.pop(),
+                    after: book_titles(&[data.target_id], db).await?.pop(),
+                },
+            });
+        }
+
+        EditData::PersonMerge(data) => {
+            let people = PersonModel::get_by_ids(&[data.source_id, data.target_id], db).await?;
+
+            diff.push(EditFieldDiff {
+                field: String::from("merge"),
+                change: EditFieldChange::Value {
+                    before: Some(person_name(data.source_id, &people)),
+                    after: Some(person_name(data.target_id, &people)),
+                },
+            });
+        }
+    }
+
+    Ok(diff)
+}
+
+fn value_diff<V: Display>(
+    field: &str,
+    before: Option<V>,
+    after: Option<&V>,
+) -> Option<EditFieldDiff> {
+    Some(EditFieldDiff {
+        field: field.to_string(),
+        change: EditFieldChange::Value {
+            before: before.map(|v| v.to_string()),
+            after: Some(after?.to_string()),
+        },
+    })
+}
+
+async fn push_images(
+    diff: &mut Vec<EditFieldDiff>,
+    added: Option<&[NewOrCachedImage]>,
+    removed: Option<&[ImageId]>,
+    db: &Client,
+) -> Result<()> {
+    if added.is_none() && removed.is_none() {
+        return Ok(());
+    }
+
+    let mut removed_urls = Vec::new();
+
+    for image_id in removed.unwrap_or_default() {
+        if let Some(image) = UploadedImageModel::get_by_id(*image_id, db).await? {
+            if let Some(path) = image.path.into_value() {
+                removed_urls.push(format!("/api/v1/image/{path}"));
+            }
+        }
+    }
+
+    diff.push(EditFieldDiff {
+        field: String::from("images"),
+        change: EditFieldChange::Images {
+            added: added
+                .unwrap_or_default()
+                .iter()
+                .map(|v| v.as_url().into_owned())
+                .collect(),
+            removed: removed_urls,
+        },
+    });
+
+    Ok(())
+}
+
+fn person_name(id: PersonId, people: &[PersonModel]) -> String {
+    people
+        .iter()
+        .find(|v| v.id == id)
+        .map(|v| v.name.clone())
+        .unwrap_or_else(|| format!("Person {id}"))
+}
+
+fn person_names(ids: Option<&[PersonId]>, people: &[PersonModel]) -> Vec<String> {
+    ids.unwrap_or_default()
+        .iter()
+        .map(|id| person_name(*id, people))
+        .collect()
+}
+
+fn tag_names(ids: Option<&[TagId]>, tags: &[TagModel]) -> Vec<String> {
+    ids.unwrap_or_default()
+        .iter()
+        .map(|id| {
+            tags.iter()
+                .find(|v| v.id == *id)
+                .map(|v| v.name.clone())
+                .unwrap_or_else(|| format!("Tag {id}"))
+        })
+        .collect()
+}
+
+async fn book_titles(ids: &[BookId], db: &Client) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+
+    for id in ids {
+        names.push(
+            BookModel::get_by_id(*id, db)
+                .await?
+                .and_then(|v| v.title)
+                .unwrap_or_else(|| format!("Book {id}")),
+        );
+    }
+
+    Ok(names)
+}
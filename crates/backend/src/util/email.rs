@@ -0,0 +1,57 @@
+use common_local::ConfigEmail;
+use lettre::{
+    message::{header::ContentType, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
+    Message, SmtpTransport, Transport,
+};
+
+use crate::Result;
+
+/// Sends a plain text and HTML email through the configured SMTP relay.
+pub fn send_email(
+    sending_to_email: &str,
+    subject: &str,
+    alt_text: String,
+    main_html: String,
+    email_config: &ConfigEmail,
+) -> Result<()> {
+    let email = Message::builder()
+        .from(
+            format!(
+                "{} <{}>",
+                email_config.display_name, email_config.sending_email
+            )
+            .parse()?,
+        )
+        .reply_to(email_config.sending_email.parse()?)
+        .to(sending_to_email.parse()?)
+        .subject(subject)
+        .multipart(
+            MultiPart::alternative() // This is composed of two parts.
+                .singlepart(
+                    SinglePart::builder()
+                        .header(ContentType::TEXT_PLAIN)
+                        .body(alt_text),
+                )
+                .singlepart(
+                    SinglePart::builder()
+                        .header(ContentType::TEXT_HTML)
+                        .body(main_html),
+                ),
+        )?;
+
+    let creds = Credentials::new(
+        email_config.smtp_username.clone(),
+        email_config.smtp_password.clone(),
+    );
+
+    // Open a remote connection to gmail
+    let mailer = SmtpTransport::relay(&email_config.smtp_relay)?
+        .credentials(creds)
+        .build();
+
+    // Send the email
+    mailer.send(&email)?;
+
+    Ok(())
+}
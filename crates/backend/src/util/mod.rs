@@ -1,6 +1,18 @@
 pub mod config;
+pub mod edit_diff;
 pub mod edit_translate;
+pub mod email;
 pub mod events;
 pub mod image;
 
+use sha2::{Digest, Sha256};
+
 pub use self::image::store_image;
+
+/// Hex encoded SHA-256 of a secret token. Used so only the hash has to be stored.
+pub fn hash_token(value: &str) -> String {
+    Sha256::digest(value.as_bytes())
+        .iter()
+        .map(|v| format!("{:02x}", v))
+        .collect()
+}
@@ -1,19 +1,30 @@
 use std::collections::HashMap;
 
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use common::{
-    api::QueryListResponse, BookId, BookTagId, Either, ImageId, ImageIdType, PersonId, Source,
-    TagId,
+    api::QueryListResponse, BookId, BookTagId, Either, ImageId, ImageIdType, MemberId, PersonId,
+    Source, TagId,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    edit::ModifyValuesBy,
-    item::edit::{BookEdit, NewOrCachedImage, PersonEdit, SharedEditModel, SharedEditVoteModel},
-    util::{deserialize_naivedate_opt, serialize_naivedate_opt},
+    api_token::{ApiTokenScopes, SharedApiToken},
+    audit::{AuditAction, SharedAuditLog},
+    edit::{EditOperation, EditStatus, EditType, ModifyValuesBy},
+    item::edit::{
+        BookEdit, NewOrCachedImage, PersonEdit, SharedEditCommentModel, SharedEditModel,
+        SharedEditVoteModel,
+    },
+    notification::{SharedNotification, SharedWatch},
+    task::{SharedTaskRun, TaskInfo},
+    util::{
+        deserialize_datetime, deserialize_datetime_opt, deserialize_naivedate_opt,
+        serialize_datetime, serialize_datetime_opt, serialize_naivedate_opt,
+    },
     BasicDirectory, BasicLibrary, BookTag, Chapter, Collection, CollectionType, DisplayItem,
-    DisplayMetaItem, LibraryColl, MediaItem, Member, MetadataItemCached, Person, Poster,
-    Progression, SearchType, SharedConfig, TagFE, TagType,
+    DisplayMetaItem, EditCommentId, LibraryColl, MediaItem, Member, MetadataItemCached,
+    NotificationId, Permissions, Person, Poster, Progression, SearchType, ServerLinkGrantId,
+    ServerLinkId, SharedConfig, TagFE, TagType,
 };
 
 #[derive(Serialize, Deserialize, Clone, Copy)]
@@ -76,6 +87,37 @@ pub struct UpdateCollectionModel {
 // GET /edits
 pub type GetEditListResponse = QueryListResponse<SharedEditModel>;
 
+#[derive(Default, Serialize, Deserialize, Clone)]
+pub struct EditListQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+
+    pub type_of: Option<EditType>,
+    pub operation: Option<EditOperation>,
+    pub status: Option<EditStatus>,
+    pub member_id: Option<MemberId>,
+    pub model_id: Option<usize>,
+
+    /// Unix timestamp (seconds)
+    pub created_after: Option<i64>,
+    /// Unix timestamp (seconds)
+    pub created_before: Option<i64>,
+
+    /// Only return edits which the current member hasn't voted on.
+    #[serde(default)]
+    pub not_voted: bool,
+
+    pub sort: Option<EditListSort>,
+    pub order: Option<OrderBy>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EditListSort {
+    Created,
+    Expires,
+    Votes,
+}
+
 // GET /edit/{id}
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GetEditResponse {
@@ -89,6 +131,114 @@ pub struct PostEditResponse {
     pub vote: Option<SharedEditVoteModel>,
 }
 
+// POST /edit/{id}/override
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OverrideEditBody {
+    /// `Pending` re-opens the edit. Otherwise `ForceAccepted` or `ForceRejected`.
+    pub status: EditStatus,
+    pub reason: String,
+}
+
+// GET /edit/{id}/comments
+pub type GetEditCommentListResponse = QueryListResponse<SharedEditCommentModel>;
+
+// POST /edit/{id}/comments
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewEditCommentBody {
+    pub text: String,
+
+    /// Replying to another comment on the same edit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<EditCommentId>,
+}
+
+pub type NewEditCommentResponse = SharedEditCommentModel;
+
+// Tasks
+// GET /tasks
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetTasksResponse {
+    pub items: Vec<TaskInfo>,
+}
+
+// GET /task/{name}/runs
+pub type GetTaskRunsResponse = QueryListResponse<SharedTaskRun>;
+
+// POST /task/{name}/run
+pub type RunTaskResponse = SharedTaskRun;
+
+// Watches
+// GET /watches
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetWatchesResponse {
+    pub items: Vec<SharedWatch>,
+}
+
+// POST /watch
+// DELETE /watch
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct WatchBody {
+    pub type_of: EditType,
+    pub model_id: usize,
+}
+
+// Notifications
+// GET /notifications
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetNotificationsResponse {
+    pub offset: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub unread: usize,
+
+    pub items: Vec<SharedNotification>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct NotificationListQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+// POST /notifications/read
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkNotificationsBody {
+    /// Marks every notification if None.
+    pub ids: Option<Vec<NotificationId>>,
+    pub is_read: bool,
+}
+
+// API Tokens
+// GET /tokens
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetApiTokensResponse {
+    pub items: Vec<SharedApiToken>,
+}
+
+// POST /token
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewApiTokenBody {
+    pub name: String,
+    pub scopes: ApiTokenScopes,
+
+    /// Never expires if None.
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewApiTokenResponse {
+    pub token: SharedApiToken,
+    /// Only returned once. Sent as `Authorization: Bearer <secret>`.
+    pub secret: String,
+}
+
 // Tags
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -107,7 +257,8 @@ pub struct NewTagBody {
     pub type_of: TagType,
 }
 
-pub type NewTagResponse = TagFE;
+/// Returns `None` if the tag was submitted for review instead of being created.
+pub type NewTagResponse = Option<TagFE>;
 
 // Book Tags
 
@@ -157,6 +308,314 @@ pub struct GetMemberSelfResponse {
     pub member: Option<Member>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateMemberBody {
+    pub name: Option<String>,
+    pub permissions: Option<Permissions>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SuspendMemberBody {
+    pub reason: String,
+
+    /// Suspended indefinitely if None.
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct DeleteMemberQuery {
+    /// Scrubs the member's details instead of removing them.
+    #[serde(default)]
+    pub anonymize: bool,
+}
+
+// Account
+// POST /account/name
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeNameBody {
+    pub name: String,
+}
+
+// POST /account/email
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeEmailBody {
+    pub email: String,
+    /// Required if the account has a password.
+    pub password: Option<String>,
+}
+
+// POST /account/password
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangePasswordBody {
+    /// Required if the account already has a password.
+    pub current_password: Option<String>,
+    pub new_password: String,
+}
+
+// POST /auth/password/reset
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PasswordResetBody {
+    pub email: String,
+}
+
+// POST /auth/password/reset/confirm
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PasswordResetConfirmBody {
+    pub token: String,
+    pub password: String,
+}
+
+// Two-Factor
+
+/// Returned once the first login factor was accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginStatus {
+    Complete,
+    /// A code has to be sent to `/auth/2fa/verify`.
+    TwoFactorRequired,
+    /// Two-factor has to be set up through `/auth/2fa/setup` and `/auth/2fa/enable` first.
+    TwoFactorSetupRequired,
+}
+
+impl LoginStatus {
+    pub fn is_complete(self) -> bool {
+        matches!(self, Self::Complete)
+    }
+}
+
+// GET /auth/2fa
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TwoFactorStatusResponse {
+    pub enabled: bool,
+    pub recovery_codes_remaining: usize,
+}
+
+// POST /auth/2fa/setup
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TwoFactorSetupResponse {
+    /// Base32 encoded. For manually entering into an authenticator app.
+    pub secret: String,
+    pub otpauth_url: String,
+    /// Base64 encoded PNG of the `otpauth_url`.
+    pub qr_code: Option<String>,
+}
+
+// POST /auth/2fa/enable
+// POST /auth/2fa/verify
+// POST /auth/2fa/disable
+// POST /auth/2fa/recovery
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TwoFactorCodeBody {
+    /// A TOTP code or, unless enabling, a recovery code.
+    pub code: String,
+}
+
+// POST /auth/2fa/enable
+// POST /auth/2fa/recovery
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TwoFactorRecoveryCodesResponse {
+    /// Only shown once.
+    pub recovery_codes: Vec<String>,
+}
+
+// Sessions
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberSession {
+    pub id: usize,
+
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub last_seen_at: DateTime<Utc>,
+
+    /// The session this was requested with.
+    pub is_current: bool,
+}
+
+// GET /account/sessions
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetSessionsResponse {
+    pub items: Vec<MemberSession>,
+}
+
+// Server Links
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedServerLink {
+    pub id: ServerLinkId,
+
+    pub server_owner_name: Option<String>,
+    pub server_name: Option<String>,
+    pub public_id: String,
+
+    pub member_id: MemberId,
+    pub verified: bool,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub updated_at: DateTime<Utc>,
+    /// Last time the server searched through us.
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub last_seen_at: Option<DateTime<Utc>>,
+
+    /// Only returned to admins.
+    #[serde(default)]
+    pub usage: Option<ServerLinkUsage>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerLinkUsage {
+    /// The configured default is used if None.
+    pub requests_per_minute: Option<i32>,
+    pub requests_per_day: Option<i32>,
+
+    pub minute_requests: i32,
+    pub day_requests: i32,
+
+    pub total_requests: i64,
+    pub throttled_requests: i64,
+}
+
+// GET /account/servers
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetServerLinksResponse {
+    pub items: Vec<SharedServerLink>,
+}
+
+// GET /admin/servers
+pub type GetAllServerLinksResponse = QueryListResponse<SharedServerLink>;
+
+// POST /server/{id}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateServerLinkBody {
+    pub server_owner_name: Option<String>,
+    pub server_name: Option<String>,
+}
+
+// POST /server/{id}/rotate
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RotateServerLinkResponse {
+    pub link: SharedServerLink,
+    /// Only returned once. The server has to be updated with it and verify itself again.
+    pub server_id: String,
+}
+
+// POST /admin/server/{id}/limits
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateServerLinkLimitsBody {
+    /// Resets to the configured default if None. 0 is unlimited.
+    pub requests_per_minute: Option<i32>,
+    pub requests_per_day: Option<i32>,
+}
+
+// GET /search/book
+// GET /search/author
+/// Same as `librarian::GetSearchQuery` except `server_id` is only needed by unsigned requests.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublicSearchQuery {
+    pub query: String,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub view_private: bool,
+    #[serde(default)]
+    pub with_isbn: bool,
+
+    /// Sent in plain text. Sign requests instead.
+    pub server_id: Option<String>,
+}
+
+// Server Link Grants
+// Lets a linked server act on behalf of a member. It signs its requests and passes the member's ID.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedServerLinkGrant {
+    pub id: ServerLinkGrantId,
+
+    pub server_link_id: ServerLinkId,
+    pub server_owner_name: Option<String>,
+    pub server_name: Option<String>,
+
+    pub scopes: ApiTokenScopes,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub updated_at: DateTime<Utc>,
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+// GET /account/grants
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetServerLinkGrantsResponse {
+    pub items: Vec<SharedServerLinkGrant>,
+}
+
+// GET /grant/server/{public_id}
+/// Shown to the member before they grant the server access.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetGrantServerResponse {
+    pub server_owner_name: Option<String>,
+    pub server_name: Option<String>,
+}
+
+// POST /account/grant
+/// Replaces the scopes if the server was already granted access.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewServerLinkGrantBody {
+    pub public_id: String,
+    pub scopes: ApiTokenScopes,
+}
+
+// Audit Log
+// GET /admin/audit
+pub type GetAuditLogResponse = QueryListResponse<SharedAuditLog>;
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct AuditLogQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+
+    pub member_id: Option<MemberId>,
+    pub action: Option<AuditAction>,
+    pub target_id: Option<String>,
+}
+
 // Libraries
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -170,9 +629,22 @@ pub struct GetLibrariesResponse {
 pub struct MassEditBooks {
     pub book_ids: Vec<BookId>,
 
-    // People
+    // People. Ignored if the list is empty.
     pub people_list: Vec<PersonId>,
     pub people_list_mod: ModifyValuesBy,
+
+    // Tags. Ignored if the list is empty.
+    #[serde(default)]
+    pub tags_list: Vec<TagId>,
+    #[serde(default)]
+    pub tags_list_mod: ModifyValuesBy,
+
+    #[serde(default)]
+    pub language: Option<u16>,
+    #[serde(default)]
+    pub publisher: Option<String>,
+    #[serde(default)]
+    pub is_public: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
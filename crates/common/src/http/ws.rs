@@ -1,7 +1,10 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use common::{BookId, PersonId};
 use serde::{Deserialize, Serialize};
 
+use crate::{edit::EditStatus, EditId};
+
 pub static UNIQUE_ID: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -19,6 +22,26 @@ impl Default for UniqueId {
     }
 }
 
+/// Sent from the client.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum WebsocketRequest {
+    Ping,
+    Pong,
+
+    Subscribe(WebsocketTopic),
+    Unsubscribe(WebsocketTopic),
+}
+
+/// Notifications are only sent to connections which subscribed to their topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebsocketTopic {
+    Edits,
+    Tasks,
+
+    Book(BookId),
+    Person(PersonId),
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum WebsocketResponse {
     Ping,
@@ -39,19 +62,50 @@ impl WebsocketResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WebsocketNotification {
-    TaskStart { id: UniqueId, type_of: TaskType },
+    TaskStart {
+        id: UniqueId,
+        type_of: TaskType,
+    },
     TaskEnd(UniqueId),
+
+    EditCreated(EditId),
+    EditVoted {
+        id: EditId,
+        vote_count: i16,
+    },
+    EditStatusChanged {
+        id: EditId,
+        status: EditStatus,
+    },
+
+    /// An accepted edit changed the model. Cached copies should be reloaded.
+    BookUpdated(BookId),
+    PersonUpdated(PersonId),
 }
 
 impl WebsocketNotification {
     pub fn new_task(id: UniqueId, type_of: TaskType) -> Self {
         Self::TaskStart { id, type_of }
     }
+
+    pub fn topic(&self) -> WebsocketTopic {
+        match *self {
+            Self::TaskStart { .. } | Self::TaskEnd(_) => WebsocketTopic::Tasks,
+
+            Self::EditCreated(_) | Self::EditVoted { .. } | Self::EditStatusChanged { .. } => {
+                WebsocketTopic::Edits
+            }
+
+            Self::BookUpdated(id) => WebsocketTopic::Book(id),
+            Self::PersonUpdated(id) => WebsocketTopic::Person(id),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TaskType {
     UpdatingMetadata(usize),
 
-    TempRustWarningFix,
+    /// A task from the scheduler, by name.
+    Scheduled(String),
 }
@@ -2,10 +2,15 @@ use chrono::{DateTime, Utc};
 use common::{api::QueryListResponse, BookId, MemberId, PersonId, TagId};
 use serde::{Deserialize, Serialize};
 
-use crate::{edit::*, util::*, EditId, EditVoteId, Member};
+use crate::{
+    edit::*, util::*, Collection, CollectionId, DisplayMetaItem, EditCommentId, EditId, EditVoteId,
+    Member, Person, ServerLinkId, TagFE, TagType,
+};
 
 pub use book_edit::*;
+pub use collection_edit::*;
 pub use person_edit::*;
+pub use tag_edit::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SharedEditModel {
@@ -47,6 +52,18 @@ pub struct SharedEditModel {
         deserialize_with = "deserialize_datetime"
     )]
     pub updated_at: DateTime<Utc>,
+
+    /// Edits from the same mass edit share this. They're voted on and closed together.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_id: Option<EditId>,
+
+    /// Set if a linked server submitted the edit on behalf of the member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_link_id: Option<ServerLinkId>,
+
+    /// Changed fields with People, Tags, Books and Images resolved. Filled in by the backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<Vec<EditFieldDiff>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +83,27 @@ pub struct SharedEditVoteModel {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedEditCommentModel {
+    pub id: EditCommentId,
+
+    pub edit_id: EditId,
+    pub member: Option<Member>,
+
+    pub text: String,
+    pub deleted: bool,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+
+    /// The comment this is replying to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<EditCommentId>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateEditModel {
     pub status: Option<EditStatus>,
@@ -91,8 +129,11 @@ pub struct UpdateEditModel {
 pub enum EditData {
     Book(BookEditData),
     Person(PersonEditData),
-    Tag,
-    Collection,
+    Tag(TagEditData),
+    Collection(CollectionEditData),
+
+    BookMerge(BookMergeData),
+    PersonMerge(PersonMergeData),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -100,6 +141,7 @@ pub enum ModelIdGroup {
     Book(BookId),
     Person(PersonId),
     Tag(TagId),
+    Collection(CollectionId),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +157,55 @@ pub struct InnerEditData<Curr, Cached, Update: Clone + Default> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated: Option<Update>,
+
+    /// Fields which were changed on the model after the edit was made. They aren't applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<Update>,
+}
+
+/// Used for [`EditOperation::Merge`]. The source is merged into the target and then removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeEditData<Id, Preview> {
+    pub source_id: Id,
+    pub target_id: Id,
+
+    /// What the target should look like once merged. Updated with the actual result once accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<Preview>,
+}
+
+pub type BookMergeData = MergeEditData<BookId, DisplayMetaItem>;
+pub type PersonMergeData = MergeEditData<PersonId, Person>;
+
+/// A single changed field of an edit. Computed by the backend so clients don't have to compare
+/// `current`, `old` and `new` themselves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditFieldDiff {
+    /// Name of the field. ie: "title", "added_people"
+    pub field: String,
+    pub change: EditFieldChange,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EditFieldChange {
+    /// A single value which was set or changed.
+    Value {
+        before: Option<String>,
+        after: Option<String>,
+    },
+
+    /// Items added to or removed from a list. People, Tags and Books are resolved to their names.
+    List {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+
+    /// Thumbnail URLs which were added or removed.
+    Images {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
 }
 
 impl SharedEditModel {
@@ -123,7 +214,7 @@ impl SharedEditModel {
             EditType::Book => ModelIdGroup::Book(BookId::from(id)),
             EditType::Person => ModelIdGroup::Person(PersonId::from(id)),
             EditType::Tag => ModelIdGroup::Tag(TagId::from(id)),
-            EditType::Collection => todo!(),
+            EditType::Collection => ModelIdGroup::Collection(CollectionId::from(id)),
         })
     }
 }
@@ -138,7 +229,6 @@ mod book_edit {
     use common::ImageId;
 
     use super::*;
-    use crate::DisplayMetaItem;
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
     pub enum NewOrCachedImage {
@@ -248,6 +338,12 @@ mod book_edit {
 
         #[serde(default, skip_serializing_if = "is_false")]
         pub publisher: bool,
+
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub added_tags: bool,
+
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub removed_tags: bool,
     }
 
     impl UpdatedBookEdit {
@@ -265,6 +361,8 @@ mod book_edit {
                 && !self.removed_people
                 && !self.added_isbns
                 && !self.removed_isbns
+                && !self.added_tags
+                && !self.removed_tags
         }
     }
 
@@ -290,6 +388,18 @@ mod book_edit {
                 && self.removed_images.is_none()
         }
 
+        /// Only the description or tags are changed.
+        pub fn is_low_risk(&self) -> bool {
+            let other = Self {
+                description: None,
+                added_tags: None,
+                removed_tags: None,
+                ..self.clone()
+            };
+
+            !self.is_empty() && other.is_empty()
+        }
+
         pub fn insert_added_isbn(&mut self, value: String) {
             let items = self.added_isbns
                 .get_or_insert_with(Default::default);
@@ -563,7 +673,6 @@ mod person_edit {
     use common::ImageId;
 
     use super::*;
-    use crate::Person;
 
     pub type PersonEditData = InnerEditData<Person, PersonEdit, UpdatedPersonEdit>;
 
@@ -600,6 +709,16 @@ mod person_edit {
                 && self.added_images.is_none()
                 && self.removed_images.is_none()
         }
+
+        /// Only the description is changed.
+        pub fn is_low_risk(&self) -> bool {
+            let other = Self {
+                description: None,
+                ..self.clone()
+            };
+
+            !self.is_empty() && other.is_empty()
+        }
     }
 
     impl UpdatedPersonEdit {
@@ -709,3 +828,77 @@ mod person_edit {
         }
     }
 }
+
+mod tag_edit {
+    use super::*;
+
+    pub type TagEditData = InnerEditData<TagFE, TagEdit, UpdatedTagEdit>;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct TagEdit {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub type_of: Option<TagType>,
+    }
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct UpdatedTagEdit {
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub name: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub type_of: bool,
+    }
+
+    impl TagEdit {
+        pub fn is_empty(&self) -> bool {
+            self.name.is_none() && self.type_of.is_none()
+        }
+    }
+
+    impl UpdatedTagEdit {
+        pub fn is_empty(&self) -> bool {
+            !self.name && !self.type_of
+        }
+    }
+}
+
+mod collection_edit {
+    use super::*;
+
+    pub type CollectionEditData = InnerEditData<Collection, CollectionEdit, UpdatedCollectionEdit>;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct CollectionEdit {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub added_books: Option<Vec<BookId>>,
+    }
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct UpdatedCollectionEdit {
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub name: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub description: bool,
+
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub added_books: bool,
+    }
+
+    impl CollectionEdit {
+        pub fn is_empty(&self) -> bool {
+            self.name.is_none() && self.description.is_none() && self.added_books.is_none()
+        }
+    }
+
+    impl UpdatedCollectionEdit {
+        pub fn is_empty(&self) -> bool {
+            !self.name && !self.description && !self.added_books
+        }
+    }
+}
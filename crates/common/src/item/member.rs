@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MemberSettings {
     pub page_view: Option<PageView>,
+
+    /// Unread notifications are emailed at this interval. None if opted out.
+    #[serde(default)]
+    pub email_digest: Option<DigestFrequency>,
 }
 
 impl MemberSettings {
@@ -20,6 +24,21 @@ pub enum PageView {
     Editing,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFrequency {
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+        }
+    }
+}
+
 impl PageView {
     pub fn is_viewing(self) -> bool {
         matches!(self, Self::Viewing)
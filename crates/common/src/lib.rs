@@ -206,6 +206,8 @@ pub struct Member {
 
     pub name: String,
     pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: bool,
 
     pub permissions: Permissions,
 
@@ -221,6 +223,25 @@ pub struct Member {
         deserialize_with = "deserialize_datetime"
     )]
     pub updated_at: DateTime<Utc>,
+
+    #[serde(default)]
+    pub reputation: i32,
+
+    /// Set while the member is suspended.
+    #[serde(default)]
+    pub suspension: Option<MemberSuspension>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberSuspension {
+    pub reason: String,
+
+    /// Suspended indefinitely if None.
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub until: Option<DateTime<Utc>>,
 }
 
 // Used for People View
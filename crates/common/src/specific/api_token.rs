@@ -0,0 +1,206 @@
+use bitflags::bitflags;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{util::*, ApiTokenId, GroupPermissions, Permissions, SpecificPermissions};
+
+#[cfg(feature = "backend")]
+use std::error::Error;
+#[cfg(feature = "backend")]
+use tokio_postgres::types::{private::BytesMut, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+bitflags! {
+    #[derive(Serialize, Deserialize)]
+    pub struct ApiTokenScopes: u64 {
+        const READ  = 1 << 0;
+        const EDIT  = 1 << 1;
+        const VOTE  = 1 << 2;
+        const ADMIN = 1 << 3;
+    }
+}
+
+impl ApiTokenScopes {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::READ => "Read",
+            Self::EDIT => "Edit",
+            Self::VOTE => "Vote",
+            Self::ADMIN => "Admin",
+            _ => "Multiple",
+        }
+    }
+
+    /// Parses space or comma separated scope names, e.g. `read edit`. Returns None for unknown names.
+    pub fn from_names(value: &str) -> Option<Self> {
+        value
+            .split(|c: char| c == ' ' || c == ',')
+            .filter(|v| !v.is_empty())
+            .try_fold(Self::empty(), |scopes, name| {
+                Some(
+                    scopes
+                        | match name.to_lowercase().as_str() {
+                            "read" => Self::READ,
+                            "edit" => Self::EDIT,
+                            "vote" => Self::VOTE,
+                            "admin" => Self::ADMIN,
+                            _ => return None,
+                        },
+                )
+            })
+    }
+
+    /// Limits the member's permissions to what the token was granted.
+    pub fn restrict(self, permissions: Permissions) -> Permissions {
+        if self.contains(Self::ADMIN) {
+            return permissions;
+        }
+
+        // Group permissions otherwise grant everything so they're expanded first.
+        let specific =
+            if permissions.intersects_group(GroupPermissions::ADMIN | GroupPermissions::MANAGER) {
+                SpecificPermissions::all()
+            } else {
+                permissions.specific
+            };
+
+        let mut allowed = SpecificPermissions::empty();
+
+        if self.intersects(Self::READ | Self::EDIT | Self::VOTE) {
+            allowed |= SpecificPermissions::VIEW;
+        }
+
+        if self.contains(Self::EDIT) {
+            allowed |= SpecificPermissions::EDIT
+                | SpecificPermissions::CREATE
+                | SpecificPermissions::DELETE
+                | SpecificPermissions::COMMENT;
+        }
+
+        if self.contains(Self::VOTE) {
+            allowed |= SpecificPermissions::VOTING | SpecificPermissions::FORCE_VOTE;
+        }
+
+        Permissions {
+            group: permissions.group & GroupPermissions::BASIC,
+            specific: specific & allowed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedApiToken {
+    pub id: ApiTokenId,
+
+    pub name: String,
+    pub scopes: ApiTokenScopes,
+
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "backend")]
+impl<'a> FromSql<'a> for ApiTokenScopes {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self::from_bits_truncate(i64::from_sql(ty, raw)? as u64))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ToSql for ApiTokenScopes {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        (self.bits() as i64).to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_names() {
+        assert_eq!(
+            ApiTokenScopes::from_names("read edit"),
+            Some(ApiTokenScopes::READ | ApiTokenScopes::EDIT)
+        );
+
+        assert_eq!(
+            ApiTokenScopes::from_names("Read,VOTE"),
+            Some(ApiTokenScopes::READ | ApiTokenScopes::VOTE)
+        );
+
+        assert_eq!(
+            ApiTokenScopes::from_names(""),
+            Some(ApiTokenScopes::empty())
+        );
+
+        assert_eq!(ApiTokenScopes::from_names("read write"), None);
+    }
+
+    #[test]
+    fn test_restrict() {
+        // Admin tokens keep everything.
+        assert_eq!(
+            ApiTokenScopes::ADMIN.restrict(Permissions::admin()),
+            Permissions::admin()
+        );
+
+        // Read only tokens can only view.
+        let perms = ApiTokenScopes::READ.restrict(Permissions::admin());
+
+        assert!(!perms.is_admin());
+        assert_eq!(perms.specific, SpecificPermissions::VIEW);
+        assert!(!perms.has_editing_perms());
+        assert!(!perms.has_voting_perms());
+        assert!(!perms.has_manage_members_perms());
+
+        // Group permissions are expanded before being limited.
+        let perms = ApiTokenScopes::EDIT.restrict(Permissions::manager());
+
+        assert!(perms.has_editing_perms());
+        assert!(perms.has_comment_perms());
+        assert!(!perms.has_voting_perms());
+        assert!(!perms.has_force_vote_perms());
+        assert!(!perms.has_manage_members_perms());
+
+        // Scopes never grant more than the member has.
+        let perms = (ApiTokenScopes::EDIT | ApiTokenScopes::VOTE).restrict(Permissions::basic());
+
+        assert!(!perms.has_editing_perms());
+        assert!(perms.has_voting_perms());
+        assert!(!perms.has_force_vote_perms());
+
+        assert_eq!(
+            ApiTokenScopes::empty()
+                .restrict(Permissions::basic())
+                .specific,
+            SpecificPermissions::empty()
+        );
+    }
+}
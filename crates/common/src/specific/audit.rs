@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+
+use crate::{util::*, AuditLogId};
+
+#[cfg(feature = "backend")]
+use std::error::Error;
+#[cfg(feature = "backend")]
+use tokio_postgres::types::{private::BytesMut, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, Serialize, Deserialize,
+)]
+#[repr(u8)]
+pub enum AuditAction {
+    UpdateSettings = 0,
+
+    ReopenEdit = 1,
+    ForceAcceptEdit = 2,
+    ForceRejectEdit = 3,
+
+    DeleteBook = 4,
+    MergeBook = 5,
+    MergePerson = 6,
+
+    UpdateSearch = 7,
+
+    RotateServerLink = 8,
+    RevokeServerLink = 9,
+    UpdateServerLinkLimits = 10,
+
+    ChangeEditStatus = 11,
+
+    RestoreBook = 12,
+    PurgeBook = 13,
+
+    UpdateMember = 14,
+    SuspendMember = 15,
+    UnsuspendMember = 16,
+    DeleteMember = 17,
+}
+
+impl AuditAction {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::UpdateSettings => "Update Settings",
+            Self::ReopenEdit => "Re-open Edit",
+            Self::ForceAcceptEdit => "Force Accept Edit",
+            Self::ForceRejectEdit => "Force Reject Edit",
+            Self::DeleteBook => "Delete Book",
+            Self::MergeBook => "Merge Book",
+            Self::MergePerson => "Merge Person",
+            Self::UpdateSearch => "Update Search",
+            Self::RotateServerLink => "Rotate Server Link",
+            Self::RevokeServerLink => "Revoke Server Link",
+            Self::UpdateServerLinkLimits => "Update Server Link Limits",
+            Self::ChangeEditStatus => "Change Edit Status",
+            Self::RestoreBook => "Restore Book",
+            Self::PurgeBook => "Purge Book",
+            Self::UpdateMember => "Update Member",
+            Self::SuspendMember => "Suspend Member",
+            Self::UnsuspendMember => "Unsuspend Member",
+            Self::DeleteMember => "Delete Member",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedAuditLog {
+    pub id: AuditLogId,
+
+    pub member_id: MemberId,
+
+    pub action: AuditAction,
+    /// The ID of the model the action was performed on.
+    pub target_id: Option<String>,
+
+    /// JSON values
+    pub before: Option<String>,
+    pub after: Option<String>,
+
+    pub ip_address: Option<String>,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "backend")]
+impl<'a> FromSql<'a> for AuditAction {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self::try_from(i16::from_sql(ty, raw)? as u8).unwrap())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i16 as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ToSql for AuditAction {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        (u8::from(*self) as i16).to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i16 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
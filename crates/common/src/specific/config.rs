@@ -12,6 +12,9 @@ pub struct SharedConfig {
 pub struct ConfigServer {
     pub name: String,
     pub is_secure: bool,
+    /// Public address used when building links which are emailed out, e.g. `https://books.example.com`.
+    #[serde(default)]
+    pub base_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
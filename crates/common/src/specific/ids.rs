@@ -14,6 +14,7 @@ create_single_id!(EditCommentId);
 create_single_id!(EditVoteId);
 
 create_single_id!(ServerLinkId);
+create_single_id!(ServerLinkGrantId);
 
 create_single_id!(SearchItemId);
 create_single_id!(SearchGroupId);
@@ -22,6 +23,14 @@ create_single_id!(MetadataSearchId);
 
 create_single_id!(CollectionId);
 
+create_single_id!(TaskRunId);
+
+create_single_id!(NotificationId);
+
+create_single_id!(ApiTokenId);
+
+create_single_id!(AuditLogId);
+
 #[cfg(feature = "backend")]
 mod backend {
     use super::*;
@@ -68,6 +77,11 @@ mod backend {
     add_sql!(SearchGroupId);
     add_sql!(MetadataSearchId);
     add_sql!(CollectionId);
+    add_sql!(TaskRunId);
+    add_sql!(NotificationId);
+    add_sql!(ApiTokenId);
+    add_sql!(AuditLogId);
+    add_sql!(ServerLinkGrantId);
 }
 
 #[cfg(feature = "backend")]
@@ -1,7 +1,11 @@
+pub mod api_token;
+pub mod audit;
 pub mod config;
 pub mod edit;
 mod ids;
+pub mod notification;
 mod perms;
+pub mod task;
 
 pub use config::*;
 pub use ids::*;
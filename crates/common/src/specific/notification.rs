@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+
+use crate::{edit::EditType, util::*, EditCommentId, EditId, NotificationId};
+
+#[cfg(feature = "backend")]
+use std::error::Error;
+#[cfg(feature = "backend")]
+use tokio_postgres::types::{private::BytesMut, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, Serialize, Deserialize,
+)]
+#[repr(u8)]
+pub enum NotificationType {
+    /// A new pending edit on a watched model.
+    WatchedEdit = 0,
+    /// One of your edits was accepted.
+    EditAccepted = 1,
+    /// One of your edits was rejected, failed or cancelled.
+    EditRejected = 2,
+    /// Someone commented on one of your edits.
+    EditComment = 3,
+}
+
+impl NotificationType {
+    pub fn get_description(self) -> &'static str {
+        match self {
+            Self::WatchedEdit => "New edit on something you're watching",
+            Self::EditAccepted => "Your edit was accepted",
+            Self::EditRejected => "Your edit was rejected",
+            Self::EditComment => "New comment on your edit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedWatch {
+    pub type_of: EditType,
+    pub model_id: usize,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedNotification {
+    pub id: NotificationId,
+
+    pub type_of: NotificationType,
+
+    pub edit_id: EditId,
+    pub comment_id: Option<EditCommentId>,
+
+    pub is_read: bool,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "backend")]
+impl<'a> FromSql<'a> for NotificationType {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self::try_from(i16::from_sql(ty, raw)? as u8).unwrap())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i16 as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ToSql for NotificationType {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        (u8::from(*self) as i16).to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i16 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
@@ -63,6 +63,13 @@ impl Permissions {
         }
     }
 
+    pub fn admin() -> Self {
+        Self {
+            group: GroupPermissions::ADMIN,
+            specific: SpecificPermissions::as_manager(),
+        }
+    }
+
     /// Returns true if all of the flags in other are contained within self.
     pub fn contains_group(self, value: GroupPermissions) -> bool {
         self.group.contains(value)
@@ -83,6 +90,11 @@ impl Permissions {
         self.specific.intersects(value)
     }
 
+    /// Returns true if all of the group and specific flags in other are contained within self.
+    pub fn contains_all(self, value: Self) -> bool {
+        self.group.contains(value.group) && self.specific.contains(value.specific)
+    }
+
     /// Returns true if all of the flags in other are contained within self.
     pub fn contains_any(self, group: GroupPermissions, specific: SpecificPermissions) -> bool {
         self.group.contains(group) || self.specific.contains(specific)
@@ -138,6 +150,36 @@ impl Permissions {
             SpecificPermissions::VOTING,
         )
     }
+
+    /// Either Group: Admin or Manager
+    ///
+    /// Specific: Comment
+    pub fn has_comment_perms(self) -> bool {
+        self.intersects_any(
+            GroupPermissions::ADMIN | GroupPermissions::MANAGER,
+            SpecificPermissions::COMMENT,
+        )
+    }
+
+    /// Either Group: Admin or Manager
+    ///
+    /// Specific: Manage Members
+    pub fn has_manage_members_perms(self) -> bool {
+        self.intersects_any(
+            GroupPermissions::ADMIN | GroupPermissions::MANAGER,
+            SpecificPermissions::MANAGE_MEMBERS,
+        )
+    }
+
+    /// Either Group: Admin or Manager
+    ///
+    /// Specific: Force Vote
+    pub fn has_force_vote_perms(self) -> bool {
+        self.intersects_any(
+            GroupPermissions::ADMIN | GroupPermissions::MANAGER,
+            SpecificPermissions::FORCE_VOTE,
+        )
+    }
 }
 
 impl SpecificPermissions {
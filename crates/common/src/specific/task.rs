@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use common::MemberId;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+
+use crate::{util::*, TaskRunId};
+
+#[cfg(feature = "backend")]
+use std::error::Error;
+#[cfg(feature = "backend")]
+use tokio_postgres::types::{private::BytesMut, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, Serialize, Deserialize,
+)]
+#[repr(u8)]
+pub enum TaskStatus {
+    Running = 0,
+    Succeeded = 1,
+    Failed = 2,
+    Cancelled = 3,
+}
+
+impl TaskStatus {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::Running => "Running",
+            Self::Succeeded => "Succeeded",
+            Self::Failed => "Failed",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+
+    pub fn is_running(self) -> bool {
+        matches!(self, Self::Running)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskInfo {
+    pub name: String,
+    pub description: String,
+
+    /// Cron expression from the config. Tasks without one only run when triggered.
+    pub schedule: Option<String>,
+
+    pub is_running: bool,
+    pub last_run: Option<SharedTaskRun>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedTaskRun {
+    pub id: TaskRunId,
+
+    pub name: String,
+    pub status: TaskStatus,
+
+    /// Member who triggered it. None if it was scheduled.
+    pub member_id: Option<MemberId>,
+
+    pub error: Option<String>,
+
+    #[serde(
+        serialize_with = "serialize_datetime",
+        deserialize_with = "deserialize_datetime"
+    )]
+    pub started_at: DateTime<Utc>,
+    #[serde(
+        serialize_with = "serialize_datetime_opt",
+        deserialize_with = "deserialize_datetime_opt"
+    )]
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "backend")]
+impl<'a> FromSql<'a> for TaskStatus {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self::try_from(i16::from_sql(ty, raw)? as u8).unwrap())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i16 as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ToSql for TaskStatus {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        (u8::from(*self) as i16).to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i16 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
@@ -0,0 +1,248 @@
+use common::api::WrappingResponse;
+use common_local::{api, item::edit::SharedEditCommentModel, EditCommentId, EditId};
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::HtmlTextAreaElement;
+use yew::{html::Scope, prelude::*};
+
+use crate::{components::LoginBarrier, get_member_self, request};
+
+#[derive(Properties, PartialEq, Eq)]
+pub struct Property {
+    pub edit_id: EditId,
+}
+
+pub enum Msg {
+    // Requests
+    RequestComments,
+
+    Submit,
+    Delete(EditCommentId),
+    ReplyTo(Option<EditCommentId>),
+
+    // Results
+    CommentsResults(WrappingResponse<api::GetEditCommentListResponse>),
+    SubmitResult(WrappingResponse<api::NewEditCommentResponse>),
+    DeleteResult(WrappingResponse<String>),
+}
+
+/// Discussion thread displayed underneath an edit.
+pub struct EditCommentsPanel {
+    items_resp: Option<WrappingResponse<api::GetEditCommentListResponse>>,
+
+    /// The comment the textarea is currently replying to.
+    replying_to: Option<EditCommentId>,
+
+    textarea_ref: NodeRef,
+}
+
+impl Component for EditCommentsPanel {
+    type Message = Msg;
+    type Properties = Property;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::RequestComments);
+
+        Self {
+            items_resp: None,
+            replying_to: None,
+            textarea_ref: NodeRef::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RequestComments => {
+                let edit_id = ctx.props().edit_id;
+
+                ctx.link().send_future(async move {
+                    Msg::CommentsResults(request::get_edit_comments(edit_id, None, None).await)
+                });
+
+                return false;
+            }
+
+            Msg::Submit => {
+                let edit_id = ctx.props().edit_id;
+
+                let textarea = self
+                    .textarea_ref
+                    .cast::<HtmlTextAreaElement>()
+                    .unwrap_throw();
+                let text = textarea.value().trim().to_string();
+
+                if text.is_empty() {
+                    return false;
+                }
+
+                textarea.set_value("");
+
+                let parent_id = self.replying_to.take();
+
+                ctx.link().send_future(async move {
+                    Msg::SubmitResult(request::new_edit_comment(edit_id, text, parent_id).await)
+                });
+            }
+
+            Msg::ReplyTo(comment_id) => {
+                self.replying_to = comment_id;
+            }
+
+            Msg::Delete(comment_id) => {
+                let edit_id = ctx.props().edit_id;
+
+                ctx.link().send_future(async move {
+                    Msg::DeleteResult(request::delete_edit_comment(edit_id, comment_id).await)
+                });
+
+                return false;
+            }
+
+            Msg::CommentsResults(resp) => {
+                self.items_resp = Some(resp);
+            }
+
+            Msg::SubmitResult(resp) => match resp.ok() {
+                Ok(comment) => {
+                    if let Some(WrappingResponse::Resp(list)) = self.items_resp.as_mut() {
+                        list.total += 1;
+                        list.items.push(comment);
+                    }
+                }
+
+                Err(e) => {
+                    log::error!("{e}");
+
+                    return false;
+                }
+            },
+
+            Msg::DeleteResult(resp) => {
+                if let Err(e) = resp.ok() {
+                    log::error!("{e}");
+                }
+
+                ctx.link().send_message(Msg::RequestComments);
+
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let scope = ctx.link();
+
+        html! {
+            <div class="edit-comments">
+                {
+                    if let Some(resp) = self.items_resp.as_ref() {
+                        let resp = crate::continue_or_html_err!(resp);
+
+                        if resp.items.is_empty() {
+                            html! {
+                                <span class="text-muted">{ "No comments yet." }</span>
+                            }
+                        } else {
+                            // Replies whose parent isn't loaded are displayed at the top level.
+                            html! {
+                                for resp.items.iter()
+                                    .filter(|item| item.parent_id.map_or(true, |id| !resp.items.iter().any(|v| v.id == id)))
+                                    .map(|item| Self::render_comment(item, &resp.items, scope))
+                            }
+                        }
+                    } else {
+                        html! {
+                            <span>{ "Loading..." }</span>
+                        }
+                    }
+                }
+
+                <LoginBarrier>
+                    <div class="mt-2">
+                        {
+                            if let Some(name) = self.replying_to.and_then(|id| self.find_comment_author(id)) {
+                                html! {
+                                    <div class="text-muted">
+                                        { format!("Replying to {name}") }
+                                        <button class="btn btn-sm btn-link" onclick={ scope.callback(|_| Msg::ReplyTo(None)) }>{ "Cancel" }</button>
+                                    </div>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                        <textarea class="form-control" rows="2" placeholder="Add to the discussion" ref={ self.textarea_ref.clone() }></textarea>
+                        <button class="btn btn-sm btn-primary mt-1" onclick={ scope.callback(|_| Msg::Submit) }>{ "Comment" }</button>
+                    </div>
+                </LoginBarrier>
+            </div>
+        }
+    }
+}
+
+impl EditCommentsPanel {
+    fn find_comment_author(&self, comment_id: EditCommentId) -> Option<String> {
+        let comment = match self.items_resp.as_ref() {
+            Some(WrappingResponse::Resp(resp)) => resp.items.iter().find(|v| v.id == comment_id)?,
+            _ => return None,
+        };
+
+        Some(
+            comment
+                .member
+                .as_ref()
+                .map(|v| v.name.clone())
+                .unwrap_or_else(|| String::from("(Unknown)")),
+        )
+    }
+
+    fn render_comment(
+        item: &SharedEditCommentModel,
+        items: &[SharedEditCommentModel],
+        scope: &Scope<Self>,
+    ) -> Html {
+        let comment_id = item.id;
+
+        let can_delete = get_member_self()
+            .map(|me| {
+                me.permissions.is_admin() || item.member.as_ref().map(|v| v.id) == Some(me.id)
+            })
+            .unwrap_or_default();
+
+        html! {
+            <div class="edit-comment mb-2" key={ comment_id.to_string() }>
+                <div>
+                    <b>{ item.member.as_ref().map(|v| v.name.clone()).unwrap_or_else(|| String::from("(Unknown)")) }</b>
+                    <span class="text-muted ms-2">{ item.created_at.format("%b %e, %Y %T %p").to_string() }</span>
+                    <button
+                        class="btn btn-sm btn-link"
+                        title="Reply"
+                        onclick={ scope.callback(move |_| Msg::ReplyTo(Some(comment_id))) }
+                    >{ "Reply" }</button>
+                    {
+                        if can_delete {
+                            html! {
+                                <button
+                                    class="btn btn-sm btn-link text-danger"
+                                    title="Delete"
+                                    onclick={ scope.callback(move |_| Msg::Delete(comment_id)) }
+                                >{ "Delete" }</button>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+                <div>{ item.text.clone() }</div>
+                <div class="edit-comment-replies ms-4 mt-2">
+                    {
+                        for items.iter()
+                            .filter(|v| v.parent_id == Some(comment_id))
+                            .map(|v| Self::render_comment(v, items, scope))
+                    }
+                </div>
+            </div>
+        }
+    }
+}
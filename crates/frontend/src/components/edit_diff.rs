@@ -0,0 +1,58 @@
+use common::util::upper_case_first_char;
+use common_local::item::edit::{EditFieldChange, EditFieldDiff};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct EditDiffProps {
+    pub diff: Vec<EditFieldDiff>,
+}
+
+/// Displays the changed fields of an edit which were computed by the backend.
+#[function_component(EditDiffView)]
+pub fn edit_diff_view(props: &EditDiffProps) -> Html {
+    html! {
+        for props.diff.iter().map(render_field)
+    }
+}
+
+fn render_field(item: &EditFieldDiff) -> Html {
+    let title = upper_case_first_char(item.field.replace('_', " "));
+
+    match &item.change {
+        EditFieldChange::Value { before, after } => html! {
+            <div class="comparison-row">
+                <div class="row-title"><span>{ title }</span></div>
+                <div class="row-grow">
+                    <div class="badge text-bg-danger text-wrap">{ before.clone().unwrap_or_else(|| String::from("(Empty)")) }</div>
+                </div>
+                <div class="row-grow">
+                    <div class="badge text-bg-success text-wrap">{ after.clone().unwrap_or_else(|| String::from("(Empty)")) }</div>
+                </div>
+            </div>
+        },
+
+        EditFieldChange::List { added, removed } => html! {
+            <div class="comparison-row">
+                <div class="row-title"><span>{ title }</span></div>
+                <div class="row-grow">
+                    { for removed.iter().map(|v| html! { <div class="badge text-bg-danger text-wrap me-1">{ v.clone() }</div> }) }
+                </div>
+                <div class="row-grow">
+                    { for added.iter().map(|v| html! { <div class="badge text-bg-success text-wrap me-1">{ v.clone() }</div> }) }
+                </div>
+            </div>
+        },
+
+        EditFieldChange::Images { added, removed } => html! {
+            <div class="comparison-row">
+                <div class="row-title"><span>{ title }</span></div>
+                <div class="row-grow">
+                    { for removed.iter().map(|v| html! { <img class="me-1" height="96" src={ v.clone() } /> }) }
+                </div>
+                <div class="row-grow">
+                    { for added.iter().map(|v| html! { <img class="me-1" height="96" src={ v.clone() } /> }) }
+                </div>
+            </div>
+        },
+    }
+}
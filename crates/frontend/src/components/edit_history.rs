@@ -0,0 +1,167 @@
+use common::{api::WrappingResponse, BookId, PersonId};
+use common_local::{
+    api,
+    edit::{EditOperation, EditStatus},
+    item::edit::SharedEditModel,
+    EditId,
+};
+use yew::{html::Scope, prelude::*};
+
+use crate::{
+    components::{EditDiffView, LoginBarrier},
+    get_member_self, request,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HistoryOf {
+    Book(BookId),
+    Person(PersonId),
+}
+
+#[derive(Properties, PartialEq, Eq)]
+pub struct Property {
+    pub model: HistoryOf,
+}
+
+pub enum Msg {
+    // Requests
+    RequestHistory,
+
+    Revert(EditId),
+
+    // Results
+    HistoryResults(WrappingResponse<api::GetEditListResponse>),
+    RevertResult(WrappingResponse<bool>),
+}
+
+/// Chronological list of the accepted edits for a Book or Person.
+pub struct EditHistoryPanel {
+    items_resp: Option<WrappingResponse<api::GetEditListResponse>>,
+}
+
+impl Component for EditHistoryPanel {
+    type Message = Msg;
+    type Properties = Property;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::RequestHistory);
+
+        Self { items_resp: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RequestHistory => {
+                let model = ctx.props().model;
+
+                ctx.link().send_future(async move {
+                    Msg::HistoryResults(match model {
+                        HistoryOf::Book(id) => request::get_book_history(id, None, None).await,
+                        HistoryOf::Person(id) => request::get_person_history(id, None, None).await,
+                    })
+                });
+
+                return false;
+            }
+
+            Msg::Revert(edit_id) => {
+                ctx.link().send_future(async move {
+                    Msg::RevertResult(request::revert_edit_item(edit_id).await)
+                });
+
+                return false;
+            }
+
+            Msg::HistoryResults(resp) => {
+                self.items_resp = Some(resp);
+            }
+
+            Msg::RevertResult(resp) => {
+                match resp.ok() {
+                    // Reverted immediately. Reload to show the new entry.
+                    Ok(true) => ctx.link().send_message(Msg::RequestHistory),
+                    Ok(false) => {
+                        let _ = gloo_utils::window()
+                            .alert_with_message("Revert was submitted for review.");
+                    }
+                    Err(e) => log::error!("{e}"),
+                }
+
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let scope = ctx.link();
+
+        html! {
+            <div class="edit-history">
+                {
+                    if let Some(resp) = self.items_resp.as_ref() {
+                        let resp = crate::continue_or_html_err!(resp);
+
+                        if resp.items.is_empty() {
+                            html! {
+                                <span class="text-muted">{ "No history yet." }</span>
+                            }
+                        } else {
+                            html! {
+                                for resp.items.iter().map(|item| Self::render_item(item, scope))
+                            }
+                        }
+                    } else {
+                        html! {
+                            <span>{ "Loading..." }</span>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl EditHistoryPanel {
+    fn render_item(item: &SharedEditModel, scope: &Scope<Self>) -> Html {
+        let edit_id = item.id;
+
+        let can_revert = item.operation == EditOperation::Modify
+            && matches!(
+                item.status,
+                EditStatus::Accepted | EditStatus::ForceAccepted
+            )
+            && get_member_self()
+                .map(|v| v.permissions.has_editing_perms())
+                .unwrap_or_default();
+
+        html! {
+            <div class="edit-history-item mb-3" key={ edit_id.to_string() }>
+                <div>
+                    <b>{ item.operation.get_name() }</b>
+                    <span class="ms-2">{ item.member.as_ref().map(|v| v.name.clone()).unwrap_or_else(|| String::from("(Unknown)")) }</span>
+                    <span class="text-muted ms-2">
+                        { item.ended_at.unwrap_or(item.created_at).format("%b %e, %Y %T %p").to_string() }
+                    </span>
+                    {
+                        if can_revert {
+                            html! {
+                                <LoginBarrier>
+                                    <button
+                                        class="btn btn-sm btn-link text-danger"
+                                        title="Create an edit which undoes this one"
+                                        onclick={ scope.callback(move |_| Msg::Revert(edit_id)) }
+                                    >{ "Revert" }</button>
+                                </LoginBarrier>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+                <EditDiffView diff={ item.diff.clone().unwrap_or_default() } />
+            </div>
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use std::{rc::Rc, sync::Mutex};
 
 use common_local::{
-    api::{GetPeopleResponse, MassEditBooks, NewBookBody},
+    api::{GetPeopleResponse, GetTagsResponse, MassEditBooks, NewBookBody},
     edit::ModifyValuesBy,
-    Person,
+    Person, TagFE,
 };
 use gloo_timers::callback::Timeout;
 use wasm_bindgen::UnwrapThrowExt;
-use web_sys::{HtmlElement, HtmlSelectElement};
+use web_sys::{HtmlElement, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 use common::{
@@ -16,7 +16,8 @@ use common::{
         popup::button::ButtonWithPopup, MultiSelectEvent, MultiSelectItem, MultiSelectModule,
         Popup, PopupClose, PopupType,
     },
-    BookId, PersonId,
+    util::upper_case_first_char,
+    BookId, PersonId, TagId, LANGUAGES,
 };
 
 use crate::request;
@@ -54,6 +55,8 @@ pub enum MsgEditPopup {
     SearchText(String),
     TogglePerson { toggle: bool, id: PersonId },
     PeopleResponse(WrappingResponse<GetPeopleResponse>),
+    ToggleTag { toggle: bool, id: TagId },
+    TagsResponse(WrappingResponse<GetTagsResponse>),
 
     UpdateEdit(Box<dyn Fn(&mut MassEditBooks, String)>, String),
     Save,
@@ -107,6 +110,10 @@ impl Component for MassSelectBar {
 
             Msg::ShowEditPopup(v) => {
                 self.popup_display = Some(v);
+
+                ctx.link().send_future(async {
+                    Msg::EditPopup(MsgEditPopup::TagsResponse(request::get_tags().await))
+                });
             }
 
             Msg::CloseEditPopup => {
@@ -119,6 +126,7 @@ impl Component for MassSelectBar {
                         LocalPopupType::EditBooks {
                             selected_people,
                             cached_people,
+                            cached_tags,
                             edit,
                         } => match msg {
                             MsgEditPopup::SearchText(search) => {
@@ -160,6 +168,23 @@ impl Component for MassSelectBar {
                                 *cached_people = resp.ok().unwrap_throw().items;
                             }
 
+                            MsgEditPopup::ToggleTag { toggle, id } => {
+                                if toggle {
+                                    if !edit.tags_list.contains(&id) {
+                                        edit.tags_list.push(id);
+                                    }
+                                } else if let Some(index) =
+                                    edit.tags_list.iter().position(|v| *v == id)
+                                {
+                                    edit.tags_list.remove(index);
+                                }
+                            }
+
+                            MsgEditPopup::TagsResponse(resp) => match resp.ok() {
+                                Ok(resp) => *cached_tags = resp.items,
+                                Err(err) => log::error!("{:?}", err),
+                            },
+
                             MsgEditPopup::UpdateEdit(func, input) => {
                                 func(edit, input);
                             }
@@ -229,7 +254,7 @@ impl Component for MassSelectBar {
                     {
                         if let Some(popup) = self.popup_display.as_ref() {
                             match popup {
-                                LocalPopupType::EditBooks { selected_people, cached_people, edit } => html! {
+                                LocalPopupType::EditBooks { selected_people, cached_people, cached_tags, edit } => html! {
                                     <Popup type_of={ PopupType::FullOverlay } on_close={ ctx.link().callback(|_| Msg::CloseEditPopup) } classes={ classes!("popup-book-edit") }>
                                         <div class="content shrink-width-to-content">
                                             // Update People
@@ -271,6 +296,94 @@ impl Component for MassSelectBar {
                                                     <option value="2" selected={ edit.people_list_mod as u8 == 2 }>{ "Remove" }</option>
                                                 </select>
                                             </div>
+
+                                            // Update Tags
+                                            <div class="form-container">
+                                                <h4>{ "Tags" }</h4>
+
+                                                <MultiSelectModule<TagId>
+                                                    editing=true
+                                                    create_new=false
+                                                    on_event={
+                                                        ctx.link().callback(|v| match v {
+                                                            MultiSelectEvent::Toggle { toggle, id } => Msg::EditPopup(MsgEditPopup::ToggleTag { toggle, id }),
+                                                            MultiSelectEvent::Input { .. } => Msg::Ignore,
+                                                            MultiSelectEvent::Create(_) => Msg::Ignore,
+                                                        })
+                                                    }
+                                                >
+                                                    {
+                                                        for cached_tags.iter()
+                                                            .map(|tag| html_nested! {
+                                                                <MultiSelectItem<TagId> id={ tag.id } name={ tag.name.clone() } selected={ edit.tags_list.contains(&tag.id) } />
+                                                            })
+                                                    }
+                                                </MultiSelectModule<TagId>>
+
+                                                <select onchange={ ctx.link().callback(|v: Event| Msg::EditPopup(MsgEditPopup::UpdateEdit(
+                                                    Box::new(|e, v| { e.tags_list_mod = ModifyValuesBy::from(v.parse::<u8>().unwrap()); }),
+                                                    v.target_unchecked_into::<HtmlSelectElement>().selected_index().to_string()
+                                                ))) }>
+                                                    <option value="0" selected={ edit.tags_list_mod as u8 == 0 }>{ "Overwrite" }</option>
+                                                    <option value="1" selected={ edit.tags_list_mod as u8 == 1 }>{ "Append" }</option>
+                                                    <option value="2" selected={ edit.tags_list_mod as u8 == 2 }>{ "Remove" }</option>
+                                                </select>
+                                            </div>
+
+                                            // Update Info
+                                            <div class="form-container">
+                                                <h4>{ "Language" }</h4>
+
+                                                // The first option is "No Change". Everything after it is offset by one.
+                                                <select onchange={ ctx.link().callback(|v: Event| Msg::EditPopup(MsgEditPopup::UpdateEdit(
+                                                    Box::new(|e, v| { e.language = v.parse::<u16>().unwrap().checked_sub(1); }),
+                                                    v.target_unchecked_into::<HtmlSelectElement>().selected_index().to_string()
+                                                ))) }>
+                                                    <option value="" selected={ edit.language.is_none() }>{ "No Change" }</option>
+                                                    {
+                                                        for LANGUAGES.iter()
+                                                            .enumerate()
+                                                            .map(|(index, lang)| html! {
+                                                                <option value={ index.to_string() } selected={ edit.language == Some(index as u16) }>
+                                                                    { upper_case_first_char(lang.to_string()) }
+                                                                </option>
+                                                            })
+                                                    }
+                                                </select>
+                                            </div>
+
+                                            <div class="form-container">
+                                                <h4>{ "Publisher" }</h4>
+
+                                                <input
+                                                    type="text"
+                                                    placeholder="No Change"
+                                                    value={ edit.publisher.clone().unwrap_or_default() }
+                                                    onchange={ ctx.link().callback(|v: Event| Msg::EditPopup(MsgEditPopup::UpdateEdit(
+                                                        Box::new(|e, v| { e.publisher = Some(v.trim().to_string()).filter(|v| !v.is_empty()); }),
+                                                        v.target_unchecked_into::<HtmlInputElement>().value()
+                                                    ))) }
+                                                />
+                                            </div>
+
+                                            <div class="form-container">
+                                                <h4>{ "Visibility" }</h4>
+
+                                                <select onchange={ ctx.link().callback(|v: Event| Msg::EditPopup(MsgEditPopup::UpdateEdit(
+                                                    Box::new(|e, v| {
+                                                        e.is_public = match v.as_str() {
+                                                            "1" => Some(true),
+                                                            "2" => Some(false),
+                                                            _ => None,
+                                                        };
+                                                    }),
+                                                    v.target_unchecked_into::<HtmlSelectElement>().selected_index().to_string()
+                                                ))) }>
+                                                    <option value="0" selected={ edit.is_public.is_none() }>{ "No Change" }</option>
+                                                    <option value="1" selected={ edit.is_public == Some(true) }>{ "Public" }</option>
+                                                    <option value="2" selected={ edit.is_public == Some(false) }>{ "Unlisted" }</option>
+                                                </select>
+                                            </div>
                                         </div>
 
                                         <div class="footer">
@@ -318,6 +431,7 @@ pub enum LocalPopupType {
         edit: MassEditBooks,
         selected_people: Vec<Person>,
         cached_people: Vec<Person>,
+        cached_tags: Vec<TagFE>,
     },
 }
 
@@ -327,6 +441,7 @@ impl LocalPopupType {
             edit: MassEditBooks::default(),
             selected_people: Default::default(),
             cached_people: Default::default(),
+            cached_tags: Default::default(),
         }
     }
 }
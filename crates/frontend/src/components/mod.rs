@@ -1,8 +1,15 @@
+pub mod edit_comments;
+pub mod edit_diff;
+pub mod edit_history;
 mod login_barrier;
 pub mod mass_selector_bar;
 pub mod navbar;
 pub mod popup;
+pub mod watch_button;
 
+pub use edit_comments::EditCommentsPanel;
+pub use edit_diff::EditDiffView;
+pub use edit_history::{EditHistoryPanel, HistoryOf};
 pub use login_barrier::LoginBarrier;
 pub use mass_selector_bar::MassSelectBar;
 pub use navbar::NavbarModule;
@@ -10,3 +17,4 @@ pub use popup::{
     edit_metadata::PopupEditMetadata, search::PopupSearch, search_person::PopupSearchPerson,
     SearchBy,
 };
+pub use watch_button::WatchButton;
@@ -50,6 +50,11 @@ impl Component for NavbarModule {
                 ),
             ],
             right_items: vec![
+                (
+                    true,
+                    Route::Notifications,
+                    DisplayType::Icon("notifications", "Notifications"),
+                ),
                 (
                     true,
                     Route::Options,
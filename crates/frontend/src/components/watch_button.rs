@@ -0,0 +1,88 @@
+use common::api::WrappingResponse;
+use common_local::{api, edit::EditType};
+use yew::prelude::*;
+
+use crate::request;
+
+#[derive(Properties, PartialEq, Eq)]
+pub struct Property {
+    pub type_of: EditType,
+    pub model_id: usize,
+}
+
+pub enum Msg {
+    WatchesResults(WrappingResponse<api::GetWatchesResponse>),
+
+    Toggle,
+    ToggleResult(bool, WrappingResponse<String>),
+}
+
+/// Watches a model to be notified about new edits on it.
+pub struct WatchButton {
+    is_watching: Option<bool>,
+}
+
+impl Component for WatchButton {
+    type Message = Msg;
+    type Properties = Property;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link()
+            .send_future(async { Msg::WatchesResults(request::get_watches().await) });
+
+        Self { is_watching: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::WatchesResults(resp) => {
+                let props = ctx.props();
+
+                self.is_watching = resp.ok().ok().map(|resp| {
+                    resp.items
+                        .iter()
+                        .any(|v| v.type_of == props.type_of && v.model_id == props.model_id)
+                });
+            }
+
+            Msg::Toggle => {
+                let is_watching = !self.is_watching.unwrap_or_default();
+
+                let body = api::WatchBody {
+                    type_of: ctx.props().type_of,
+                    model_id: ctx.props().model_id,
+                };
+
+                ctx.link().send_future(async move {
+                    Msg::ToggleResult(is_watching, request::update_watch(body, is_watching).await)
+                });
+
+                return false;
+            }
+
+            Msg::ToggleResult(is_watching, resp) => match resp.ok() {
+                Ok(_) => self.is_watching = Some(is_watching),
+                Err(e) => {
+                    log::error!("{e}");
+
+                    return false;
+                }
+            },
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let is_watching = match self.is_watching {
+            Some(v) => v,
+            None => return html! {},
+        };
+
+        html! {
+            <button class="btn btn-secondary" onclick={ ctx.link().callback(|_| Msg::Toggle) }>
+                { if is_watching { "Unwatch" } else { "Watch" } }
+            </button>
+        }
+    }
+}
@@ -15,6 +15,7 @@ mod components;
 mod pages;
 mod request;
 mod util;
+mod ws;
 
 lazy_static! {
     pub static ref MEMBER_SELF: Arc<Mutex<Option<Member>>> = Arc::new(Mutex::new(None));
@@ -68,6 +69,10 @@ impl Component for Model {
 
                 match member.ok() {
                     Ok(resp) => {
+                        if resp.member.is_some() {
+                            ws::connect();
+                        }
+
                         *MEMBER_SELF.lock().unwrap() = resp.member;
                     }
 
@@ -121,6 +126,12 @@ pub enum Route {
     #[at("/logout")]
     Logout,
 
+    #[at("/reset-password")]
+    ResetPassword,
+
+    #[at("/two-factor")]
+    TwoFactor,
+
     #[at("/collections")]
     Collections,
 
@@ -139,12 +150,18 @@ pub enum Route {
     #[at("/edits")]
     EditList,
 
+    #[at("/notifications")]
+    Notifications,
+
     #[at("/options")]
     Options,
 
     #[at("/authorize")]
     Authorize,
 
+    #[at("/grant")]
+    Grant,
+
     #[at("/admin/:path")]
     Admin { path: String },
 
@@ -161,6 +178,10 @@ fn switch(route: Route) -> Html {
             html! { <pages::AuthorizePage /> }
         }
 
+        Route::Grant => {
+            html! { <pages::GrantPage /> }
+        }
+
         Route::Login => {
             html! { <pages::LoginPage /> }
         }
@@ -169,6 +190,14 @@ fn switch(route: Route) -> Html {
             html! { <pages::LogoutPage /> }
         }
 
+        Route::ResetPassword => {
+            html! { <pages::ResetPasswordPage /> }
+        }
+
+        Route::TwoFactor => {
+            html! { <pages::TwoFactorPage /> }
+        }
+
         Route::Collections => {
             html! { <pages::ListCollectionsPage /> }
         }
@@ -193,6 +222,14 @@ fn switch(route: Route) -> Html {
             html! { <pages::EditListPage /> }
         }
 
+        Route::Notifications => {
+            if !is_signed_in() {
+                return html! { <pages::LoginPage /> };
+            }
+
+            html! { <pages::NotificationsPage /> }
+        }
+
         Route::Options => {
             // Require a sign in for the Options Page
             if !is_signed_in() {
@@ -204,7 +241,10 @@ fn switch(route: Route) -> Html {
 
         Route::Admin { path } => {
             if let Some(me) = get_member_self() {
-                if !me.permissions.is_admin() {
+                // Members with member management perms are only able to view the member list.
+                if !me.permissions.is_admin()
+                    && !(path == "members" && me.permissions.has_manage_members_perms())
+                {
                     return html! { <h2>{ "Not Admin" }</h2> };
                 }
             } else {
@@ -212,8 +252,11 @@ fn switch(route: Route) -> Html {
             }
 
             match path.as_str() {
+                "audit" => html! { <pages::admin::ListAuditPage /> },
                 "members" => html! { <pages::admin::ListMembersPage /> },
                 "searches" => html! { <pages::admin::ListSearchesPage /> },
+                "servers" => html! { <pages::admin::ListServersPage /> },
+                "trash" => html! { <pages::admin::ListTrashPage /> },
                 _ => html! {},
             }
         }
@@ -0,0 +1,257 @@
+use common::api::WrappingResponse;
+use common_local::{
+    api::{AuditLogQuery, GetAuditLogResponse},
+    audit::{AuditAction, SharedAuditLog},
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{html::Scope, prelude::*};
+
+use crate::request;
+
+#[derive(Clone)]
+pub enum Msg {
+    // Requests
+    RequestAuditLog,
+
+    UpdateFilter(Box<AuditLogQuery>),
+
+    // Results
+    AuditLogResults(WrappingResponse<GetAuditLogResponse>),
+}
+
+pub struct ListAuditPage {
+    items_resp: Option<WrappingResponse<GetAuditLogResponse>>,
+
+    filter: AuditLogQuery,
+}
+
+impl Component for ListAuditPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::RequestAuditLog);
+
+        Self {
+            items_resp: None,
+            filter: AuditLogQuery::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RequestAuditLog => {
+                let filter = self.filter.clone();
+
+                ctx.link().send_future(async move {
+                    Msg::AuditLogResults(request::get_audit_log(filter).await)
+                });
+            }
+
+            Msg::UpdateFilter(filter) => {
+                self.filter = *filter;
+
+                ctx.link().send_message(Msg::RequestAuditLog);
+
+                return false;
+            }
+
+            Msg::AuditLogResults(resp) => {
+                self.items_resp = Some(resp);
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(resp) = self.items_resp.as_ref() {
+            let resp = crate::continue_or_html_err!(resp);
+
+            let offset = resp.offset;
+            let limit = resp.limit;
+            let total = resp.total;
+
+            html! {
+                <div class="view-container searches-list-view-container">
+                    <div class="list-items">
+                        { self.render_filters(ctx.link()) }
+
+                        <div class="search-item-card">
+                            <button disabled={ offset == 0 } onclick={ self.page_callback(ctx.link(), move |f| {
+                                f.offset = Some(offset.saturating_sub(limit));
+                            }) }>{ "Previous Page" }</button>
+
+                            <button disabled={ offset + limit >= total } onclick={ self.page_callback(ctx.link(), move |f| {
+                                f.offset = Some(offset + limit);
+                            }) }>{ "Next Page" }</button>
+
+                            <hr />
+
+                            <span>{ "Page: " } { offset / limit } { "/" } { total / limit } { " - " } { "Total " } { total }</span>
+                        </div>
+
+                        { for resp.items.iter().map(Self::render_item) }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <h1>{ "Loading..." }</h1>
+            }
+        }
+    }
+}
+
+impl ListAuditPage {
+    fn page_callback(
+        &self,
+        scope: &Scope<Self>,
+        update: impl Fn(&mut AuditLogQuery) + 'static,
+    ) -> Callback<MouseEvent> {
+        let filter = self.filter.clone();
+
+        scope.callback(move |_| {
+            let mut filter = filter.clone();
+
+            update(&mut filter);
+
+            Msg::UpdateFilter(Box::new(filter))
+        })
+    }
+
+    /// Creates a callback which applies the changed input value to a copy of the current filter.
+    fn filter_callback(
+        &self,
+        scope: &Scope<Self>,
+        get_value: fn(Event) -> String,
+        update: fn(&mut AuditLogQuery, String),
+    ) -> Callback<Event> {
+        let filter = self.filter.clone();
+
+        scope.callback(move |e: Event| {
+            let mut filter = filter.clone();
+
+            update(&mut filter, get_value(e));
+
+            // Always start back at the first page.
+            filter.offset = None;
+
+            Msg::UpdateFilter(Box::new(filter))
+        })
+    }
+
+    fn render_filters(&self, scope: &Scope<Self>) -> Html {
+        fn select_value(e: Event) -> String {
+            e.target_unchecked_into::<HtmlSelectElement>().value()
+        }
+
+        fn input_value(e: Event) -> String {
+            e.target_unchecked_into::<HtmlInputElement>().value()
+        }
+
+        let filter = &self.filter;
+
+        html! {
+            <div class="search-item-card">
+                <div class="row g-2">
+                    <div class="col">
+                        <select class="form-select form-select-sm" title="Action" onchange={ self.filter_callback(scope, select_value, |f, v| {
+                            f.action = v.parse::<u8>().ok().and_then(|v| AuditAction::try_from(v).ok());
+                        }) }>
+                            <option value="" selected={ filter.action.is_none() }>{ "All Actions" }</option>
+                            {
+                                for [
+                                    AuditAction::UpdateSettings,
+                                    AuditAction::ReopenEdit,
+                                    AuditAction::ForceAcceptEdit,
+                                    AuditAction::ForceRejectEdit,
+                                    AuditAction::DeleteBook,
+                                    AuditAction::MergeBook,
+                                    AuditAction::MergePerson,
+                                    AuditAction::UpdateSearch,
+                                    AuditAction::RotateServerLink,
+                                    AuditAction::RevokeServerLink,
+                                    AuditAction::UpdateServerLinkLimits,
+                                    AuditAction::ChangeEditStatus,
+                                    AuditAction::RestoreBook,
+                                    AuditAction::PurgeBook,
+                                    AuditAction::UpdateMember,
+                                    AuditAction::SuspendMember,
+                                    AuditAction::UnsuspendMember,
+                                    AuditAction::DeleteMember,
+                                ]
+                                    .into_iter()
+                                    .map(|v| html! {
+                                        <option value={ (v as u8).to_string() } selected={ filter.action == Some(v) }>{ v.get_name() }</option>
+                                    })
+                            }
+                        </select>
+                    </div>
+
+                    <div class="col">
+                        <input
+                            class="form-control form-control-sm"
+                            type="number"
+                            placeholder="Member ID"
+                            value={ filter.member_id.map(|v| v.to_string()).unwrap_or_default() }
+                            onchange={ self.filter_callback(scope, input_value, |f, v| {
+                                f.member_id = v.trim().parse::<usize>().ok().map(Into::into);
+                            }) }
+                        />
+                    </div>
+
+                    <div class="col">
+                        <input
+                            class="form-control form-control-sm"
+                            type="text"
+                            placeholder="Target ID"
+                            value={ filter.target_id.clone().unwrap_or_default() }
+                            onchange={ self.filter_callback(scope, input_value, |f, v| {
+                                f.target_id = Some(v.trim().to_string()).filter(|v| !v.is_empty());
+                            }) }
+                        />
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
+    fn render_item(item: &SharedAuditLog) -> Html {
+        html! {
+            <div class="search-item-card">
+                <div class="body">
+                    <h4>{ item.action.get_name() }</h4>
+
+                    <div>{ "Member ID: " } { *item.member_id }</div>
+                    {
+                        if let Some(target_id) = item.target_id.as_deref() {
+                            html! { <div>{ "Target ID: " } { target_id }</div> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    {
+                        if let Some(before) = item.before.as_deref() {
+                            html! { <div>{ "Before: " } <code>{ before }</code></div> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    {
+                        if let Some(after) = item.after.as_deref() {
+                            html! { <div>{ "After: " } <code>{ after }</code></div> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+
+                <div class="footer">
+                    <div>{ "IP Address: " } { item.ip_address.as_deref().unwrap_or("(Unknown)") }</div>
+                    <div>{ "Created: " } { item.created_at.format("%a, %e %b %y %r %Z") }</div>
+                </div>
+            </div>
+        }
+    }
+}
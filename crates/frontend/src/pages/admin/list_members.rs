@@ -1,16 +1,54 @@
-use common::api::{QueryListResponse, WrappingResponse};
-use common_local::Member;
+use chrono::{Duration, Utc};
+use common::{
+    api::{QueryListResponse, WrappingResponse},
+    MemberId,
+};
+use common_local::{
+    api::{SuspendMemberBody, UpdateMemberBody},
+    Member, Permissions, SpecificPermissions,
+};
+use gloo_utils::window;
+use web_sys::HtmlSelectElement;
 use yew::{html::Scope, prelude::*};
 
 use crate::{get_member_self, request};
 
+const PRESETS: [(&str, fn() -> Permissions); 4] = [
+    ("Basic", Permissions::basic),
+    ("Editor", Permissions::editor),
+    ("Manager", Permissions::manager),
+    ("Admin", Permissions::admin),
+];
+
+const FLAGS: [(&str, SpecificPermissions); 8] = [
+    ("View", SpecificPermissions::VIEW),
+    ("Voting", SpecificPermissions::VOTING),
+    ("Comment", SpecificPermissions::COMMENT),
+    ("Edit", SpecificPermissions::EDIT),
+    ("Delete", SpecificPermissions::DELETE),
+    ("Create", SpecificPermissions::CREATE),
+    ("Force Vote", SpecificPermissions::FORCE_VOTE),
+    ("Manage Members", SpecificPermissions::MANAGE_MEMBERS),
+];
+
 #[derive(Clone)]
 pub enum Msg {
     // Requests
     RequestMembers,
 
+    SetPermissions(MemberId, Permissions),
+    ToggleFlag(MemberId, SpecificPermissions),
+    Rename(MemberId),
+    Suspend(MemberId),
+    Unsuspend(MemberId),
+    /// Anonymizes the member instead if true.
+    Delete(MemberId, bool),
+    RevokeSessions(MemberId),
+
     // Results
     MembersResults(WrappingResponse<QueryListResponse<Member>>),
+    MemberUpdated(WrappingResponse<Member>),
+    ActionResult(WrappingResponse<String>),
 }
 
 pub struct ListMembersPage {
@@ -35,9 +73,147 @@ impl Component for ListMembersPage {
                 });
             }
 
+            Msg::SetPermissions(id, permissions) => {
+                Self::send_update(
+                    ctx,
+                    id,
+                    UpdateMemberBody {
+                        permissions: Some(permissions),
+                        ..Default::default()
+                    },
+                );
+
+                return false;
+            }
+
+            Msg::ToggleFlag(id, flag) => {
+                if let Some(member) = self.find_member(id) {
+                    let mut permissions = member.permissions;
+                    permissions.specific.toggle(flag);
+
+                    ctx.link()
+                        .send_message(Msg::SetPermissions(id, permissions));
+                }
+
+                return false;
+            }
+
+            Msg::Rename(id) => {
+                let name = window()
+                    .prompt_with_message("New Name")
+                    .ok()
+                    .flatten()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+
+                if let Some(name) = name {
+                    Self::send_update(
+                        ctx,
+                        id,
+                        UpdateMemberBody {
+                            name: Some(name),
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                return false;
+            }
+
+            Msg::Suspend(id) => {
+                let reason = window()
+                    .prompt_with_message("Reason")
+                    .ok()
+                    .flatten()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+
+                if let Some(reason) = reason {
+                    let until = window()
+                        .prompt_with_message(
+                            "Suspended for how many days? Leave empty for indefinitely.",
+                        )
+                        .ok()
+                        .flatten()
+                        .and_then(|v| v.trim().parse::<i64>().ok())
+                        .map(|days| Utc::now() + Duration::days(days));
+
+                    ctx.link().send_future(async move {
+                        Msg::MemberUpdated(
+                            request::suspend_member(id, &SuspendMemberBody { reason, until }).await,
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::Unsuspend(id) => {
+                ctx.link().send_future(async move {
+                    Msg::MemberUpdated(request::unsuspend_member(id).await)
+                });
+
+                return false;
+            }
+
+            Msg::Delete(id, anonymize) => {
+                let message = if anonymize {
+                    "Remove this member's personal details? This cannot be undone."
+                } else {
+                    "Delete this member? Their edits, comments and votes will be kept under a placeholder. This cannot be undone."
+                };
+
+                if window().confirm_with_message(message).unwrap_or_default() {
+                    ctx.link().send_future(async move {
+                        Msg::ActionResult(request::delete_member(id, anonymize).await)
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RevokeSessions(id) => {
+                if window()
+                    .confirm_with_message("Log this member out of all their sessions?")
+                    .unwrap_or_default()
+                {
+                    ctx.link().send_future(async move {
+                        Msg::ActionResult(request::revoke_member_sessions(id).await)
+                    });
+                }
+
+                return false;
+            }
+
             Msg::MembersResults(resp) => {
                 self.items_resp = Some(resp);
             }
+
+            Msg::MemberUpdated(resp) => match resp.ok() {
+                Ok(member) => {
+                    if let Some(WrappingResponse::Resp(resp)) = self.items_resp.as_mut() {
+                        if let Some(item) = resp.items.iter_mut().find(|v| v.id == member.id) {
+                            *item = member;
+                        }
+                    }
+                }
+
+                Err(e) => {
+                    log::error!("{e}");
+
+                    return false;
+                }
+            },
+
+            Msg::ActionResult(resp) => {
+                if let Err(e) = resp.ok() {
+                    log::error!("{e}");
+                }
+
+                ctx.link().send_message(Msg::RequestMembers);
+
+                return false;
+            }
         }
 
         true
@@ -63,7 +239,20 @@ impl Component for ListMembersPage {
 }
 
 impl ListMembersPage {
-    fn render_item(&self, item: &Member, _scope: &Scope<Self>) -> Html {
+    fn find_member(&self, id: MemberId) -> Option<&Member> {
+        match self.items_resp.as_ref() {
+            Some(WrappingResponse::Resp(resp)) => resp.items.iter().find(|v| v.id == id),
+            _ => None,
+        }
+    }
+
+    fn send_update(ctx: &Context<Self>, id: MemberId, body: UpdateMemberBody) {
+        ctx.link().send_future(async move {
+            Msg::MemberUpdated(request::update_member(id, &body).await)
+        });
+    }
+
+    fn render_item(&self, item: &Member, scope: &Scope<Self>) -> Html {
         html! {
             <div class="member-item-card">
                 <div class="title-container">
@@ -73,8 +262,27 @@ impl ListMembersPage {
 
                 <h5 class="email">{ item.email.as_deref().unwrap_or_default() }</h5>
 
+                <span class="reputation" title="Reputation">{ "Reputation: " }{ item.reputation }</span>
+
                 <span class="created">{ item.created_at.to_rfc2822() }</span>
 
+                {
+                    if let Some(suspension) = item.suspension.as_ref() {
+                        html! {
+                            <span class="badge bg-danger" title={ suspension.reason.clone() }>
+                                {
+                                    match suspension.until {
+                                        Some(until) => format!("Suspended until {}", until.to_rfc2822()),
+                                        None => String::from("Suspended"),
+                                    }
+                                }
+                            </span>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
                 <div class="tools">
                     {
                         if item.id == get_member_self().unwrap().id {
@@ -82,13 +290,83 @@ impl ListMembersPage {
                                 <span class="badge bg-success">{ "This is YOU!" }</span>
                             }
                         } else {
-                            html! {
-                                // <button class="red">{ "Delete" }</button>
-                            }
+                            Self::render_tools(item, scope)
                         }
                     }
                 </div>
             </div>
         }
     }
+
+    fn render_tools(item: &Member, scope: &Scope<Self>) -> Html {
+        let id = item.id;
+        let permissions = item.permissions;
+
+        html! {
+            <>
+                <select
+                    class="form-select"
+                    onchange={
+                        scope.callback(move |e: Event| {
+                            let index = e.target_unchecked_into::<HtmlSelectElement>().selected_index();
+
+                            match PRESETS.get(index as usize) {
+                                Some((_, preset)) => Msg::SetPermissions(id, preset()),
+                                None => Msg::RequestMembers,
+                            }
+                        })
+                    }
+                >
+                    {
+                        for PRESETS.iter().map(|&(name, preset)| html! {
+                            <option selected={ preset() == permissions }>{ name }</option>
+                        })
+                    }
+                    <option selected={ !PRESETS.iter().any(|(_, preset)| preset() == permissions) }>{ "Custom" }</option>
+                </select>
+
+                <div class="permissions">
+                    {
+                        for FLAGS.iter().map(|&(name, flag)| html! {
+                            <label>
+                                <input
+                                    type="checkbox"
+                                    checked={ permissions.contains_specific(flag) }
+                                    onclick={ scope.callback(move |_| Msg::ToggleFlag(id, flag)) }
+                                />
+                                { name }
+                            </label>
+                        })
+                    }
+                </div>
+
+                <button onclick={ scope.callback(move |_| Msg::Rename(id)) }>{ "Rename" }</button>
+
+                {
+                    if item.suspension.is_some() {
+                        html! {
+                            <button onclick={ scope.callback(move |_| Msg::Unsuspend(id)) }>{ "Unsuspend" }</button>
+                        }
+                    } else {
+                        html! {
+                            <button onclick={ scope.callback(move |_| Msg::Suspend(id)) }>{ "Suspend" }</button>
+                        }
+                    }
+                }
+
+                {
+                    if get_member_self().map(|v| v.permissions.is_admin()).unwrap_or_default() {
+                        html! {
+                            <button onclick={ scope.callback(move |_| Msg::RevokeSessions(id)) }>{ "Log Out Everywhere" }</button>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <button class="red" onclick={ scope.callback(move |_| Msg::Delete(id, true)) }>{ "Anonymize" }</button>
+                <button class="red" onclick={ scope.callback(move |_| Msg::Delete(id, false)) }>{ "Delete" }</button>
+            </>
+        }
+    }
 }
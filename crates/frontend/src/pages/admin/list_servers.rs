@@ -0,0 +1,262 @@
+use common::api::WrappingResponse;
+use common_local::{
+    api::{
+        GetAllServerLinksResponse, RotateServerLinkResponse, ServerLinkUsage, SharedServerLink,
+        SimpleListQuery, UpdateServerLinkLimitsBody,
+    },
+    ServerLinkId,
+};
+use gloo_utils::window;
+use yew::{html::Scope, prelude::*};
+
+use crate::request;
+
+#[derive(Clone)]
+pub enum Msg {
+    // Requests
+    RequestServerLinks,
+
+    Rotate(ServerLinkId),
+    Revoke(ServerLinkId),
+    SetLimits(ServerLinkId),
+
+    // Results
+    ServerLinksResults(WrappingResponse<GetAllServerLinksResponse>),
+    RotateResult(WrappingResponse<RotateServerLinkResponse>),
+    RevokeResult(WrappingResponse<String>),
+    SetLimitsResult(WrappingResponse<String>),
+}
+
+pub struct ListServersPage {
+    items_resp: Option<WrappingResponse<GetAllServerLinksResponse>>,
+    /// Shown once after rotating the IDs of a server.
+    rotated: Option<RotateServerLinkResponse>,
+}
+
+impl Component for ListServersPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::RequestServerLinks);
+
+        Self {
+            items_resp: None,
+            rotated: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RequestServerLinks => {
+                ctx.link().send_future(async move {
+                    let query = SimpleListQuery::from_url_search_params();
+                    Msg::ServerLinksResults(
+                        request::get_all_server_links(query.offset, query.limit).await,
+                    )
+                });
+            }
+
+            Msg::Rotate(id) => {
+                let confirmed = window()
+                    .confirm_with_message(
+                        "Generate new IDs for this server? It won't be able to search until it's updated with them.",
+                    )
+                    .unwrap_or_default();
+
+                if confirmed {
+                    ctx.link().send_future(async move {
+                        Msg::RotateResult(request::rotate_server_link(id).await)
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::Revoke(id) => {
+                let confirmed = window()
+                    .confirm_with_message(
+                        "Revoke this server? It will no longer be able to search.",
+                    )
+                    .unwrap_or_default();
+
+                if confirmed {
+                    ctx.link().send_future(async move {
+                        Msg::RevokeResult(request::revoke_server_link(id).await)
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::SetLimits(id) => {
+                let limits = Self::prompt_limit("Requests per minute").and_then(|per_minute| {
+                    Some((per_minute, Self::prompt_limit("Requests per day")?))
+                });
+
+                if let Some((requests_per_minute, requests_per_day)) = limits {
+                    ctx.link().send_future(async move {
+                        Msg::SetLimitsResult(
+                            request::update_server_link_limits(
+                                id,
+                                &UpdateServerLinkLimitsBody {
+                                    requests_per_minute,
+                                    requests_per_day,
+                                },
+                            )
+                            .await,
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RotateResult(resp) => {
+                match resp.ok() {
+                    Ok(resp) => self.rotated = Some(resp),
+                    Err(e) => log::error!("{e}"),
+                }
+
+                ctx.link().send_message(Msg::RequestServerLinks);
+            }
+
+            Msg::RevokeResult(resp) | Msg::SetLimitsResult(resp) => {
+                if let Err(e) = resp.ok() {
+                    log::error!("{e}");
+                }
+
+                ctx.link().send_message(Msg::RequestServerLinks);
+
+                return false;
+            }
+
+            Msg::ServerLinksResults(resp) => {
+                self.items_resp = Some(resp);
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(resp) = self.items_resp.as_ref() {
+            let resp = crate::continue_or_html_err!(resp);
+
+            html! {
+                <div class="view-container searches-list-view-container">
+                    <div class="list-items">
+                        <div class="search-item-card">
+                            <button onclick={ Callback::from(|_| {
+                                let mut query = SimpleListQuery::from_url_search_params();
+                                query.set_page(query.get_page().saturating_sub(1));
+
+                                let _ = window().location().set_href(&format!("{}?{}", window().location().pathname().unwrap(), query.to_query()));
+                            }) }>{ "Previous Page" }</button>
+
+                            <button onclick={ Callback::from(|_| {
+                                let mut query = SimpleListQuery::from_url_search_params();
+                                query.set_page(query.get_page() + 1);
+
+                                let _ = window().location().set_href(&format!("{}?{}", window().location().pathname().unwrap(), query.to_query()));
+                            }) }>{ "Next Page" }</button>
+
+                            <hr />
+
+                            <span>{ "Page: " } { resp.offset / resp.limit } { "/" } { resp.total / resp.limit } { " - " } { "Limit " } { resp.limit }</span>
+
+                            {
+                                if let Some(rotated) = self.rotated.as_ref() {
+                                    html! {
+                                        <>
+                                            <hr />
+                                            <div>{ "New IDs for " } { rotated.link.server_name.as_deref().unwrap_or("Unnamed") } { ". The Server ID won't be shown again." }</div>
+                                            <div>{ "Server ID: " } <code>{ rotated.server_id.clone() }</code></div>
+                                            <div>{ "Public ID: " } <code>{ rotated.link.public_id.clone() }</code></div>
+                                        </>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
+                        </div>
+
+                        { for resp.items.iter().map(|item| Self::render_item(item, ctx.link())) }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <h1>{ "Loading..." }</h1>
+            }
+        }
+    }
+}
+
+impl ListServersPage {
+    /// Returns None if cancelled. An empty value resets the limit back to the default.
+    fn prompt_limit(name: &str) -> Option<Option<i32>> {
+        let value = window()
+            .prompt_with_message(&format!(
+                "{name}. Leave empty for the default, 0 for unlimited."
+            ))
+            .ok()
+            .flatten()?;
+
+        Some(value.trim().parse::<i32>().ok())
+    }
+
+    fn render_usage(usage: Option<&ServerLinkUsage>) -> Html {
+        fn limit(value: Option<i32>) -> String {
+            match value {
+                None => String::from("Default"),
+                Some(0) => String::from("Unlimited"),
+                Some(v) => v.to_string(),
+            }
+        }
+
+        if let Some(usage) = usage {
+            html! {
+                <>
+                    <div>{ "This Minute: " } { usage.minute_requests } { " / " } { limit(usage.requests_per_minute) }</div>
+                    <div>{ "Today: " } { usage.day_requests } { " / " } { limit(usage.requests_per_day) }</div>
+                    <div>{ "Total Requests: " } { usage.total_requests } { " - " } { "Throttled: " } { usage.throttled_requests }</div>
+                </>
+            }
+        } else {
+            html! {
+                <div>{ "No Requests" }</div>
+            }
+        }
+    }
+
+    fn render_item(item: &SharedServerLink, scope: &Scope<Self>) -> Html {
+        let id = item.id;
+
+        html! {
+            <div class="search-item-card">
+                <div class="body">
+                    <h4>{ item.server_name.as_deref().unwrap_or("Unnamed") }</h4>
+
+                    <div>{ "Owner: " } { item.server_owner_name.as_deref().unwrap_or("(Unknown)") }</div>
+                    <div>{ "Member ID: " } { *item.member_id }</div>
+                    <div>{ "Public ID: " } { item.public_id.clone() }</div>
+                    <div>{ "Verified: " } { if item.verified { "Yes" } else { "No" } }</div>
+
+                    { Self::render_usage(item.usage.as_ref()) }
+                </div>
+
+                <div class="footer">
+                    <div>{ "Last Seen: " } { item.last_seen_at.map(|v| v.format("%a, %e %b %y %r %Z").to_string()).unwrap_or_else(|| String::from("Never")) }</div>
+                    <div>{ "Linked: " } { item.created_at.format("%a, %e %b %y %r %Z") }</div>
+                </div>
+
+                <div class="tools">
+                    <button onclick={ scope.callback(move |_| Msg::SetLimits(id)) }>{ "Set Limits" }</button>
+                    <button class="yellow" onclick={ scope.callback(move |_| Msg::Rotate(id)) }>{ "Rotate IDs" }</button>
+                    <button class="red" onclick={ scope.callback(move |_| Msg::Revoke(id)) }>{ "Revoke" }</button>
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use common::{
+    api::{QueryListResponse, WrappingResponse},
+    BookId,
+};
+use common_local::{api::SimpleListQuery, DisplayMetaItem};
+use gloo_utils::window;
+use yew::{html::Scope, prelude::*};
+use yew_router::prelude::Link;
+
+use crate::{request, Route};
+
+#[derive(Clone)]
+pub enum Msg {
+    // Requests
+    RequestBooks,
+
+    Restore(BookId),
+    Purge(BookId),
+
+    // Results
+    BooksResults(WrappingResponse<QueryListResponse<DisplayMetaItem>>),
+    ActionResult(WrappingResponse<bool>),
+}
+
+pub struct ListTrashPage {
+    items_resp: Option<WrappingResponse<QueryListResponse<DisplayMetaItem>>>,
+}
+
+impl Component for ListTrashPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::RequestBooks);
+
+        Self { items_resp: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RequestBooks => {
+                ctx.link().send_future(async move {
+                    let query = SimpleListQuery::from_url_search_params();
+                    Msg::BooksResults(request::get_deleted_books(query.offset, query.limit).await)
+                });
+            }
+
+            Msg::Restore(id) => {
+                ctx.link()
+                    .send_future(async move { Msg::ActionResult(request::restore_book(id).await) });
+
+                return false;
+            }
+
+            Msg::Purge(id) => {
+                let confirmed = window()
+                    .confirm_with_message("Permanently remove this book? This cannot be undone.")
+                    .unwrap_or_default();
+
+                if confirmed {
+                    ctx.link().send_future(async move {
+                        Msg::ActionResult(request::purge_book(id).await)
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::ActionResult(resp) => {
+                if let Err(e) = resp.ok() {
+                    log::error!("{e}");
+                }
+
+                ctx.link().send_message(Msg::RequestBooks);
+
+                return false;
+            }
+
+            Msg::BooksResults(resp) => {
+                self.items_resp = Some(resp);
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(resp) = self.items_resp.as_ref() {
+            let resp = crate::continue_or_html_err!(resp);
+
+            html! {
+                <div class="view-container searches-list-view-container">
+                    <div class="list-items">
+                        <div class="search-item-card">
+                            <button onclick={ Callback::from(|_| {
+                                let mut query = SimpleListQuery::from_url_search_params();
+                                query.set_page(query.get_page().saturating_sub(1));
+
+                                let _ = window().location().set_href(&format!("{}?{}", window().location().pathname().unwrap(), query.to_query()));
+                            }) }>{ "Previous Page" }</button>
+
+                            <button onclick={ Callback::from(|_| {
+                                let mut query = SimpleListQuery::from_url_search_params();
+                                query.set_page(query.get_page() + 1);
+
+                                let _ = window().location().set_href(&format!("{}?{}", window().location().pathname().unwrap(), query.to_query()));
+                            }) }>{ "Next Page" }</button>
+
+                            <hr />
+
+                            <span>{ "Page: " } { resp.offset / resp.limit } { "/" } { resp.total / resp.limit } { " - " } { "Limit " } { resp.limit }</span>
+                        </div>
+
+                        { for resp.items.iter().map(|item| self.render_item(item, ctx.link())) }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <h1>{ "Loading..." }</h1>
+            }
+        }
+    }
+}
+
+impl ListTrashPage {
+    fn render_item(&self, item: &DisplayMetaItem, scope: &Scope<Self>) -> Html {
+        let id = item.id;
+
+        html! {
+            <div class="search-item-card">
+                <div class="body">
+                    <h4>{ item.title.as_deref().or(item.clean_title.as_deref()).unwrap_or("(Untitled)") }</h4>
+
+                    <div>{ "Author: " } { item.cached.author.as_deref().unwrap_or("(Unknown)") }</div>
+                </div>
+
+                <div class="footer">
+                    {
+                        if let Some(deleted_at) = item.deleted_at {
+                            html! {
+                                <div>{ "Deleted: " } { deleted_at.format("%a, %e %b %y %r %Z") }</div>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    <div>{ "Created: " } { item.created_at.format("%a, %e %b %y %r %Z") }</div>
+                </div>
+
+                <div class="tools">
+                    <Link<Route> to={ Route::ViewMeta { meta_id: id } } classes="button yellow">{ "View" }</Link<Route>>
+                    <button class="green" onclick={ scope.callback(move |_| Msg::Restore(id)) }>{ "Restore" }</button>
+                    <button class="red" onclick={ scope.callback(move |_| Msg::Purge(id)) }>{ "Purge" }</button>
+                </div>
+            </div>
+        }
+    }
+}
@@ -1,5 +1,11 @@
+pub mod list_audit;
 pub mod list_members;
 pub mod list_searches;
+pub mod list_servers;
+pub mod list_trash;
 
+pub use list_audit::ListAuditPage;
 pub use list_members::ListMembersPage;
 pub use list_searches::ListSearchesPage;
+pub use list_servers::ListServersPage;
+pub use list_trash::ListTrashPage;
@@ -0,0 +1,251 @@
+use common::api::WrappingResponse;
+use common_local::{
+    api::{GetGrantServerResponse, NewServerLinkGrantBody, SharedServerLinkGrant},
+    api_token::ApiTokenScopes,
+};
+use gloo_utils::window;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use crate::{get_member_self, request};
+
+pub enum Msg {
+    ServerResponse(WrappingResponse<GetGrantServerResponse>),
+    GrantResponse(WrappingResponse<SharedServerLinkGrant>),
+
+    Allow,
+    Deny,
+}
+
+/// Lets a linked server act on behalf of the member.
+///
+/// The server sends the member here. Afterwards they're redirected back with their member ID.
+pub struct GrantPage {
+    query: std::result::Result<GrantQuery, &'static str>,
+
+    server_resp: Option<WrappingResponse<GetGrantServerResponse>>,
+    error: Option<String>,
+}
+
+impl Component for GrantPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let query = GrantQuery::load();
+
+        if let Ok(query) = query.as_ref() {
+            let public_id = query.public_id.clone();
+
+            ctx.link().send_future(async move {
+                Msg::ServerResponse(request::get_grant_server(&public_id).await)
+            });
+        }
+
+        Self {
+            query,
+            server_resp: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let query = match self.query.clone() {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+
+        match msg {
+            Msg::ServerResponse(resp) => {
+                self.server_resp = Some(resp);
+            }
+
+            Msg::Allow => {
+                let body = NewServerLinkGrantBody {
+                    scopes: query.scopes(),
+                    public_id: query.public_id,
+                };
+
+                ctx.link().send_future(async move {
+                    Msg::GrantResponse(request::create_server_link_grant(&body).await)
+                });
+
+                return false;
+            }
+
+            Msg::Deny => {
+                query.redirect(&GrantRedirect {
+                    member_id: None,
+                    scope: None,
+                    error: Some("access_denied"),
+                    state: query.state.clone(),
+                });
+
+                return false;
+            }
+
+            Msg::GrantResponse(resp) => match resp.ok() {
+                Ok(_) => {
+                    query.redirect(&GrantRedirect {
+                        member_id: get_member_self().map(|v| *v.id),
+                        scope: Some(query.scope.clone()),
+                        error: None,
+                        state: query.state.clone(),
+                    });
+
+                    return false;
+                }
+
+                Err(e) => self.error = Some(e.description),
+            },
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let inner = match (self.query.as_ref(), get_member_self()) {
+            (Err(e), _) => html! {
+                <h2>{ *e }</h2>
+            },
+
+            (Ok(_), None) => html! {
+                <>
+                    <h2>{ "Grant Access" }</h2>
+                    <div>{ "You have to be logged in to grant a server access to your account." }</div>
+                    <a class="btn btn-primary" href="/login">{ "Login" }</a>
+                </>
+            },
+
+            (Ok(query), Some(member)) => match self.server_resp.as_ref() {
+                Some(WrappingResponse::Resp(server)) => html! {
+                    <>
+                        <h2>{ "Grant Access" }</h2>
+
+                        <div>
+                            <b>{ server.server_name.as_deref().unwrap_or("Unnamed") }</b>
+                            { " by " }
+                            <b>{ server.server_owner_name.as_deref().unwrap_or("(Unknown)") }</b>
+                            { " wants to act on behalf of " }
+                            <b>{ member.name }</b>
+                            { "." }
+                        </div>
+
+                        <br />
+
+                        <h4>{ "It will be able to" }</h4>
+                        <ul>
+                            {
+                                for [
+                                    (ApiTokenScopes::READ, "View books and people"),
+                                    (ApiTokenScopes::EDIT, "Submit edits and comments as you"),
+                                    (ApiTokenScopes::VOTE, "Vote on edits as you"),
+                                ]
+                                    .into_iter()
+                                    .filter(|(scope, _)| query.scopes().contains(*scope))
+                                    .map(|(_, desc)| html! { <li>{ desc }</li> })
+                            }
+                        </ul>
+
+                        <div class="badge bg-danger">
+                            <b>{ "Only do this if you trust the user running the server!" }</b>
+                        </div>
+
+                        <br />
+
+                        <button class="btn btn-primary" onclick={ ctx.link().callback(|_| Msg::Allow) }>{ "Allow" }</button>
+                        <button class="btn btn-secondary" onclick={ ctx.link().callback(|_| Msg::Deny) }>{ "Deny" }</button>
+                    </>
+                },
+
+                Some(WrappingResponse::Error(e)) => html! {
+                    <h2>{ e.description.clone() }</h2>
+                },
+
+                None => html! {
+                    <h2>{ "Loading..." }</h2>
+                },
+            },
+        };
+
+        html! {
+            <div class="login-container">
+                <div class="center-normal">
+                    <div class="center-container">
+                        { inner }
+
+                        {
+                            if let Some(e) = self.error.as_ref() {
+                                html! {
+                                    <div class="badge bg-danger">{ e.clone() }</div>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GrantQuery {
+    public_id: String,
+    /// Space separated scope names. e.g. `read edit vote`
+    scope: String,
+    redirect_uri: String,
+    state: Option<String>,
+}
+
+impl GrantQuery {
+    fn load() -> std::result::Result<Self, &'static str> {
+        let q = window().location().search().unwrap_or_default();
+
+        let this =
+            serde_qs::from_str::<Self>(q.trim_start_matches('?')).map_err(|_| "Invalid Query")?;
+
+        match ApiTokenScopes::from_names(&this.scope) {
+            Some(scopes) if !scopes.is_empty() && !scopes.contains(ApiTokenScopes::ADMIN) => {
+                Ok(this)
+            }
+
+            _ => Err("Invalid Scope"),
+        }
+    }
+
+    fn scopes(&self) -> ApiTokenScopes {
+        ApiTokenScopes::from_names(&self.scope).unwrap_or_else(ApiTokenScopes::empty)
+    }
+
+    fn redirect(&self, value: &GrantRedirect) {
+        let separator = if self.redirect_uri.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+
+        window()
+            .location()
+            .set_href(&format!(
+                "{}{separator}{}",
+                self.redirect_uri,
+                serde_qs::to_string(value).unwrap_throw()
+            ))
+            .unwrap_throw();
+    }
+}
+
+#[derive(Serialize)]
+struct GrantRedirect {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    member_id: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
+}
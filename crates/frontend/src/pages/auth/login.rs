@@ -1,4 +1,5 @@
 use common::api::ApiErrorResponse;
+use common_local::api::{LoginStatus, PasswordResetBody};
 use gloo_utils::window;
 use wasm_bindgen::UnwrapThrowExt;
 use web_sys::HtmlInputElement;
@@ -8,13 +9,17 @@ use yew_router::prelude::RouterScopeExt;
 use crate::{request, Route};
 
 pub enum Msg {
-    LoginPasswordResponse(std::result::Result<String, ApiErrorResponse>),
+    LoginPasswordResponse(std::result::Result<LoginStatus, ApiErrorResponse>),
     LoginPasswordlessResponse(std::result::Result<String, ApiErrorResponse>),
+    SignupPasswordResponse(std::result::Result<String, ApiErrorResponse>),
+    PasswordResetResponse(std::result::Result<String, ApiErrorResponse>),
 }
 
 pub struct LoginPage {
-    password_response: Option<std::result::Result<String, ApiErrorResponse>>,
+    password_response: Option<std::result::Result<LoginStatus, ApiErrorResponse>>,
     passwordless_response: Option<std::result::Result<String, ApiErrorResponse>>,
+    signup_response: Option<std::result::Result<String, ApiErrorResponse>>,
+    reset_response: Option<std::result::Result<String, ApiErrorResponse>>,
     // prevent_submit: bool,
 }
 
@@ -26,6 +31,8 @@ impl Component for LoginPage {
         Self {
             password_response: None,
             passwordless_response: None,
+            signup_response: None,
+            reset_response: None,
             // prevent_submit: false,
         }
     }
@@ -33,8 +40,37 @@ impl Component for LoginPage {
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::LoginPasswordResponse(resp) => {
+                match resp {
+                    Ok(LoginStatus::Complete) => {
+                        let location = ctx.link().location().unwrap();
+                        if location.path() == "/login" {
+                            let nav = ctx.link().navigator().unwrap();
+                            nav.push(&Route::Home);
+                        } else {
+                            window().location().reload().unwrap_throw();
+                        }
+                    }
+
+                    Ok(status) => {
+                        let nav = ctx.link().navigator().unwrap();
+
+                        nav.push_with_query(
+                            &Route::TwoFactor,
+                            [("setup", status == LoginStatus::TwoFactorSetupRequired)],
+                        )
+                        .unwrap_throw();
+                    }
+
+                    Err(_) => (),
+                }
+
+                self.password_response = Some(resp);
+            }
+
+            Msg::LoginPasswordlessResponse(resp) => {
                 if resp.is_ok() {
                     let location = ctx.link().location().unwrap();
+
                     if location.path() == "/login" {
                         let nav = ctx.link().navigator().unwrap();
                         nav.push(&Route::Home);
@@ -43,10 +79,10 @@ impl Component for LoginPage {
                     }
                 }
 
-                self.password_response = Some(resp);
+                self.passwordless_response = Some(resp);
             }
 
-            Msg::LoginPasswordlessResponse(resp) => {
+            Msg::SignupPasswordResponse(resp) => {
                 if resp.is_ok() {
                     let location = ctx.link().location().unwrap();
 
@@ -58,7 +94,11 @@ impl Component for LoginPage {
                     }
                 }
 
-                self.passwordless_response = Some(resp);
+                self.signup_response = Some(resp);
+            }
+
+            Msg::PasswordResetResponse(resp) => {
+                self.reset_response = Some(resp);
             }
         }
 
@@ -72,6 +112,15 @@ impl Component for LoginPage {
                     <div class="center-container">
                         <PasswordLogin cb={ ctx.link().clone() } />
                         <PasswordlessLogin cb={ ctx.link().clone() } />
+                        <PasswordSignup cb={ ctx.link().clone() } />
+                        { Self::render_response(self.signup_response.as_ref(), "") }
+                        <ForgotPassword cb={ ctx.link().clone() } />
+                        {
+                            Self::render_response(
+                                self.reset_response.as_ref(),
+                                "If an account exists for that email a reset link has been sent to it.",
+                            )
+                        }
                     </div>
                 </div>
             </div>
@@ -79,6 +128,25 @@ impl Component for LoginPage {
     }
 }
 
+impl LoginPage {
+    fn render_response(
+        resp: Option<&std::result::Result<String, ApiErrorResponse>>,
+        success: &'static str,
+    ) -> Html {
+        match resp {
+            Some(Ok(_)) if !success.is_empty() => html! {
+                <div class="badge bg-success">{ success }</div>
+            },
+
+            Some(Err(e)) => html! {
+                <div class="badge bg-danger">{ e.description.clone() }</div>
+            },
+
+            _ => html! {},
+        }
+    }
+}
+
 #[derive(Properties)]
 struct InnerProps {
     cb: Scope<LoginPage>,
@@ -177,3 +245,111 @@ fn password(props: &InnerProps) -> Html {
         </>
     }
 }
+
+#[function_component(PasswordSignup)]
+fn password_signup(props: &InnerProps) -> Html {
+    let signup_name = use_state(String::new);
+    let signup_email = use_state(String::new);
+    let signup_pass = use_state(String::new);
+
+    let on_change_signup_name = {
+        let value = signup_name.setter();
+        Callback::from(move |e: Event| {
+            value.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let on_change_signup_email = {
+        let value = signup_email.setter();
+        Callback::from(move |e: Event| {
+            value.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let on_change_signup_pass = {
+        let value = signup_pass.setter();
+        Callback::from(move |e: Event| {
+            value.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let submit_signup = {
+        props.cb.callback_future(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let name = signup_name.clone();
+            let email = signup_email.clone();
+            let pass = signup_pass.clone();
+
+            async move {
+                let resp = request::signup_with_password(
+                    name.to_string(),
+                    email.to_string(),
+                    pass.to_string(),
+                )
+                .await;
+
+                Msg::SignupPasswordResponse(resp.ok())
+            }
+        })
+    };
+
+    html! {
+        <>
+            <h2>{ "Sign Up" }</h2>
+            <form class="mb-2" onsubmit={ submit_signup }>
+                <label for="signupname">{ "Name" }</label>
+                <input class="form-control" type="text" name="name" id="signupname" onchange={ on_change_signup_name } />
+
+                <label for="signupemail">{ "Email Address" }</label>
+                <input class="form-control" type="email" name="email" id="signupemail" onchange={ on_change_signup_email } />
+
+                <label for="signuppassword">{ "Password" }</label>
+                <input class="form-control" type="password" name="password" id="signuppassword" onchange={ on_change_signup_pass } />
+
+                <input class="btn btn-primary" type="submit" value="Sign up" />
+            </form>
+        </>
+    }
+}
+
+#[function_component(ForgotPassword)]
+fn forgot_password(props: &InnerProps) -> Html {
+    let reset_email = use_state(String::new);
+
+    let on_change_reset_email = {
+        let value = reset_email.setter();
+        Callback::from(move |e: Event| {
+            value.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let submit_reset = {
+        props.cb.callback_future(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let email = reset_email.clone();
+
+            async move {
+                let resp = request::request_password_reset(&PasswordResetBody {
+                    email: email.to_string(),
+                })
+                .await;
+
+                Msg::PasswordResetResponse(resp.ok())
+            }
+        })
+    };
+
+    html! {
+        <>
+            <h2>{ "Forgot Password" }</h2>
+            <form class="mb-2" onsubmit={ submit_reset }>
+                <label for="resetemail">{ "Email Address" }</label>
+                <input class="form-control" type="email" name="email" id="resetemail" onchange={ on_change_reset_email } />
+
+                <input class="btn btn-primary" type="submit" value="Send reset link" />
+            </form>
+        </>
+    }
+}
@@ -1,3 +1,6 @@
 pub mod authorize;
+pub mod grant;
 pub mod login;
 pub mod logout;
+pub mod reset_password;
+pub mod two_factor;
@@ -0,0 +1,146 @@
+use common::api::ApiErrorResponse;
+use common_local::api::PasswordResetConfirmBody;
+use serde::Deserialize;
+use web_sys::HtmlInputElement;
+use yew::{html::Scope, prelude::*};
+use yew_router::prelude::Link;
+
+use crate::{request, Route};
+
+pub enum Msg {
+    ResetResponse(std::result::Result<String, ApiErrorResponse>),
+}
+
+pub struct ResetPasswordPage {
+    token: Option<String>,
+    response: Option<std::result::Result<String, ApiErrorResponse>>,
+}
+
+impl Component for ResetPasswordPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let q = gloo_utils::window().location().search().unwrap_or_default();
+
+        let token = serde_qs::from_str::<TokenQuery>(q.trim_start_matches('?'))
+            .ok()
+            .map(|v| v.token);
+
+        Self {
+            token,
+            response: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ResetResponse(resp) => {
+                self.response = Some(resp);
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let inner = match (self.token.clone(), self.response.as_ref()) {
+            (_, Some(Ok(_))) => html! {
+                <>
+                    <h2>{ "Your password has been reset." }</h2>
+                    <Link<Route> to={ Route::Login }>{ "Log in" }</Link<Route>>
+                </>
+            },
+
+            (Some(token), resp) => html! {
+                <>
+                    <ResetForm cb={ ctx.link().clone() } { token } />
+
+                    {
+                        if let Some(Err(e)) = resp {
+                            html! {
+                                <div class="badge bg-danger">{ e.description.clone() }</div>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </>
+            },
+
+            (None, _) => html! {
+                <h2>{ "Invalid Query" }</h2>
+            },
+        };
+
+        html! {
+            <div class="login-container">
+                <div class="center-normal">
+                    <div class="center-container">
+                        { inner }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
+#[derive(Properties)]
+struct InnerProps {
+    cb: Scope<ResetPasswordPage>,
+    token: String,
+}
+
+impl PartialEq for InnerProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.token == other.token
+    }
+}
+
+#[function_component(ResetForm)]
+fn reset_form(props: &InnerProps) -> Html {
+    let reset_pass = use_state(String::new);
+
+    let on_change_reset_pass = {
+        let value = reset_pass.setter();
+        Callback::from(move |e: Event| {
+            value.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let submit_reset = {
+        let token = props.token.clone();
+
+        props.cb.callback_future(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let body = PasswordResetConfirmBody {
+                token: token.clone(),
+                password: reset_pass.to_string(),
+            };
+
+            async move {
+                let resp = request::confirm_password_reset(&body).await;
+
+                Msg::ResetResponse(resp.ok())
+            }
+        })
+    };
+
+    html! {
+        <>
+            <h2>{ "Reset Password" }</h2>
+            <form class="mb-2" onsubmit={ submit_reset }>
+                <label for="password">{ "New Password" }</label>
+                <input class="form-control" type="password" name="password" id="password" onchange={ on_change_reset_pass } />
+
+                <input class="btn btn-primary" type="submit" value="Reset Password" />
+            </form>
+        </>
+    }
+}
@@ -0,0 +1,221 @@
+use common::api::{ApiErrorResponse, WrappingResponse};
+use common_local::api::{
+    LoginStatus, TwoFactorCodeBody, TwoFactorRecoveryCodesResponse, TwoFactorSetupResponse,
+};
+use gloo_utils::window;
+use serde::Deserialize;
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::HtmlInputElement;
+use yew::{html::Scope, prelude::*};
+
+use crate::request;
+
+pub enum Msg {
+    SetupResponse(WrappingResponse<TwoFactorSetupResponse>),
+    EnableResponse(std::result::Result<TwoFactorRecoveryCodesResponse, ApiErrorResponse>),
+    VerifyResponse(std::result::Result<LoginStatus, ApiErrorResponse>),
+}
+
+/// Finishes a login which is pending two-factor. Admins may have to set it up first.
+pub struct TwoFactorPage {
+    is_setup: bool,
+
+    setup_resp: Option<WrappingResponse<TwoFactorSetupResponse>>,
+    /// Shown once two-factor was enabled.
+    recovery_codes: Option<Vec<String>>,
+
+    error: Option<ApiErrorResponse>,
+}
+
+impl Component for TwoFactorPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let q = window().location().search().unwrap_or_default();
+
+        let is_setup = serde_qs::from_str::<TwoFactorQuery>(q.trim_start_matches('?'))
+            .map(|v| v.setup)
+            .unwrap_or_default();
+
+        if is_setup {
+            ctx.link()
+                .send_future(async { Msg::SetupResponse(request::setup_two_factor().await) });
+        }
+
+        Self {
+            is_setup,
+            setup_resp: None,
+            recovery_codes: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetupResponse(resp) => {
+                self.setup_resp = Some(resp);
+            }
+
+            Msg::EnableResponse(resp) => match resp {
+                Ok(resp) => {
+                    self.error = None;
+                    self.recovery_codes = Some(resp.recovery_codes);
+                }
+
+                Err(e) => self.error = Some(e),
+            },
+
+            Msg::VerifyResponse(resp) => match resp {
+                // Reload so the member is fetched again.
+                Ok(_) => window().location().set_href("/").unwrap_throw(),
+                Err(e) => self.error = Some(e),
+            },
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let inner = if let Some(codes) = self.recovery_codes.as_ref() {
+            html! {
+                <>
+                    <h2>{ "Two-Factor Enabled" }</h2>
+                    <div>{ "Store these recovery codes somewhere safe. Each can be used once instead of a code. They won't be shown again." }</div>
+                    <ul>
+                        { for codes.iter().map(|code| html! { <li><code>{ code.clone() }</code></li> }) }
+                    </ul>
+                    <a class="btn btn-primary" href="/">{ "Continue" }</a>
+                </>
+            }
+        } else if self.is_setup {
+            match self.setup_resp.as_ref() {
+                Some(WrappingResponse::Resp(setup)) => html! {
+                    <>
+                        <h2>{ "Set Up Two-Factor" }</h2>
+                        <div>{ "Two-factor authentication is required for your account. Scan this with your authenticator app." }</div>
+                        { render_setup(setup) }
+                        <CodeForm cb={ ctx.link().clone() } is_setup=true />
+                    </>
+                },
+
+                Some(WrappingResponse::Error(e)) => html! {
+                    <h2>{ e.description.clone() }</h2>
+                },
+
+                None => html! {
+                    <h2>{ "Loading..." }</h2>
+                },
+            }
+        } else {
+            html! {
+                <>
+                    <h2>{ "Two-Factor" }</h2>
+                    <div>{ "Enter the code from your authenticator app or one of your recovery codes." }</div>
+                    <CodeForm cb={ ctx.link().clone() } is_setup=false />
+                </>
+            }
+        };
+
+        html! {
+            <div class="login-container">
+                <div class="center-normal">
+                    <div class="center-container">
+                        { inner }
+
+                        {
+                            if let Some(e) = self.error.as_ref() {
+                                html! {
+                                    <div class="badge bg-danger">{ e.description.clone() }</div>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+/// Also used when setting up two-factor from the options page.
+pub fn render_setup(setup: &TwoFactorSetupResponse) -> Html {
+    html! {
+        <div class="mb-2">
+            {
+                if let Some(qr_code) = setup.qr_code.as_ref() {
+                    html! {
+                        <img src={ format!("data:image/png;base64,{qr_code}") } alt="Two-Factor QR Code" />
+                    }
+                } else {
+                    html! {}
+                }
+            }
+
+            <div>
+                <span>{ "Or enter this key: " }</span>
+                <code>{ setup.secret.clone() }</code>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Deserialize)]
+struct TwoFactorQuery {
+    #[serde(default)]
+    setup: bool,
+}
+
+#[derive(Properties)]
+struct InnerProps {
+    cb: Scope<TwoFactorPage>,
+    is_setup: bool,
+}
+
+impl PartialEq for InnerProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_setup == other.is_setup
+    }
+}
+
+#[function_component(CodeForm)]
+fn code_form(props: &InnerProps) -> Html {
+    let code = use_state(String::new);
+
+    let on_change_code = {
+        let value = code.setter();
+        Callback::from(move |e: Event| {
+            value.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let submit_code = {
+        let is_setup = props.is_setup;
+
+        props.cb.callback_future(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let body = TwoFactorCodeBody {
+                code: code.to_string(),
+            };
+
+            async move {
+                if is_setup {
+                    Msg::EnableResponse(request::enable_two_factor(&body).await.ok())
+                } else {
+                    Msg::VerifyResponse(request::verify_two_factor(&body).await.ok())
+                }
+            }
+        })
+    };
+
+    html! {
+        <form class="mb-2" onsubmit={ submit_code }>
+            <label for="code">{ "Code" }</label>
+            <input class="form-control" type="text" name="code" id="code" autocomplete="one-time-code" onchange={ on_change_code } />
+
+            <input class="btn btn-primary" type="submit" value="Verify" />
+        </form>
+    }
+}
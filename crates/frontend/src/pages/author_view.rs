@@ -9,14 +9,22 @@ use common::{
 };
 use common_local::{
     api::{self, BookListQuery, GetPersonResponse, GetPostersResponse},
+    edit::EditType,
     item::edit::PersonEdit,
+    ws::WebsocketTopic,
     TagType,
 };
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlInputElement, HtmlTextAreaElement};
 use yew::{html::Scope, prelude::*};
 
-use crate::{components::LoginBarrier, get_member_self, pages::home::MediaItem, request};
+use crate::{
+    components::{EditHistoryPanel, HistoryOf, LoginBarrier, WatchButton},
+    get_member_self,
+    pages::home::MediaItem,
+    request,
+    ws::{self, WsSubscription},
+};
 
 #[derive(Clone)]
 pub enum Msg {
@@ -29,9 +37,13 @@ pub enum Msg {
 
     // Events
     ToggleEdit,
+    ToggleHistory,
     SaveEdits,
     UpdateEditing(ChangingType, String),
 
+    /// An accepted edit changed the person.
+    PersonUpdated,
+
     Ignore,
 }
 
@@ -49,6 +61,9 @@ pub struct AuthorView {
 
     editing_item: PersonEdit,
     is_editing: bool,
+    is_viewing_history: bool,
+
+    _subscription: WsSubscription,
 }
 
 impl Component for AuthorView {
@@ -76,6 +91,9 @@ impl Component for AuthorView {
             media_popup: None,
             editing_item: PersonEdit::default(),
             is_editing: false,
+            is_viewing_history: false,
+
+            _subscription: subscribe_to_person(ctx),
         }
     }
 
@@ -83,6 +101,16 @@ impl Component for AuthorView {
         match msg {
             Msg::Ignore => return false,
 
+            Msg::PersonUpdated => {
+                let person_id = ctx.props().id;
+
+                ctx.link().send_future(async move {
+                    Msg::RetrieveMediaView(Box::new(request::get_person(person_id).await))
+                });
+
+                return false;
+            }
+
             Msg::BooksListResults(resp) => {
                 self.cached_books = Some(resp);
             }
@@ -117,6 +145,10 @@ impl Component for AuthorView {
                 }
             }
 
+            Msg::ToggleHistory => {
+                self.is_viewing_history = !self.is_viewing_history;
+            }
+
             Msg::SaveEdits => {
                 let person = &self
                     .media
@@ -214,10 +246,20 @@ impl Component for AuthorView {
                                     <div class="sidebar-item">
                                         <button class="btn btn-secondary" onclick={ctx.link().callback(|_| Msg::ToggleEdit)}>{ "Start Editing" }</button>
                                     </div>
+
+                                    <div class="sidebar-item">
+                                        <WatchButton type_of={ EditType::Person } model_id={ *person.id } />
+                                    </div>
                                 </LoginBarrier>
                             }
                         }
                     }
+
+                        <div class="sidebar-item">
+                            <button class="btn btn-secondary" onclick={ctx.link().callback(|_| Msg::ToggleHistory)}>
+                                { if self.is_viewing_history { "Hide History" } else { "View History" } }
+                            </button>
+                        </div>
                     </div>
 
                     // TODO: We use px-0 b/c view-container pads
@@ -434,6 +476,19 @@ impl Component for AuthorView {
                                         </div>
                                     </div>
                                 </section>
+
+                                {
+                                    if self.is_viewing_history {
+                                        html! {
+                                            <section>
+                                                <h2>{ "History" }</h2>
+                                                <EditHistoryPanel model={ HistoryOf::Person(person.id) } />
+                                            </section>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
                             </div>
                         </div>
                     </div>
@@ -446,7 +501,11 @@ impl Component for AuthorView {
         }
     }
 
-    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if old_props.id != ctx.props().id {
+            self._subscription = subscribe_to_person(ctx);
+        }
+
         if let Some(member) = get_member_self() {
             if member.localsettings.get_page_view_default().is_editing() {
                 self.is_editing = true;
@@ -473,6 +532,13 @@ impl Component for AuthorView {
     }
 }
 
+fn subscribe_to_person(ctx: &Context<AuthorView>) -> WsSubscription {
+    ws::subscribe(
+        WebsocketTopic::Person(ctx.props().id),
+        ctx.link().callback(|_| Msg::PersonUpdated),
+    )
+}
+
 impl AuthorView {
     fn is_editing(&self) -> bool {
         self.is_editing
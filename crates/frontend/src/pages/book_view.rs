@@ -14,7 +14,9 @@ use common::{
 };
 use common_local::{
     api::{GetPostersQuery, GetPostersResponse, GetTagsResponse, MediaViewResponse},
+    edit::EditType,
     item::edit::BookEdit,
+    ws::WebsocketTopic,
     Person, SearchType, TagFE, TagType,
 };
 
@@ -22,6 +24,7 @@ use js_sys::Date;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use yew::{html::Scope, prelude::*};
+use yew_router::prelude::RouterScopeExt;
 
 use crate::{
     components::{
@@ -29,9 +32,12 @@ use crate::{
             search::SearchSelectedValue,
             search_person::SearchSelectedValue as PersonSearchSelectedValue, SearchBy,
         },
-        LoginBarrier, PopupEditMetadata, PopupSearch, PopupSearchPerson,
+        EditHistoryPanel, HistoryOf, LoginBarrier, PopupEditMetadata, PopupSearch,
+        PopupSearchPerson, WatchButton,
     },
     get_member_self, request,
+    ws::{self, WsSubscription},
+    Route,
 };
 
 #[derive(Clone)]
@@ -51,14 +57,19 @@ pub enum Msg {
 
     // Events
     ToggleEdit,
+    ToggleHistory,
     SaveEdits,
     UpdateEditing(ChangingType, String),
 
     OnDelete(WrappingResponse<bool>),
+    OnMerge(BookId, WrappingResponse<String>),
 
     ShowPopup(DisplayOverlay),
     ClosePopup,
 
+    /// An accepted edit changed the book.
+    BookUpdated,
+
     Ignore,
 }
 
@@ -76,11 +87,14 @@ pub struct BookView {
     /// If we're currently editing. This'll be set.
     editing_item: BookEdit,
     is_editing: bool,
+    is_viewing_history: bool,
 
     // Multiselect Values
     cached_tags: Vec<CachedTag>,
 
     search_poster_metadata: bool,
+
+    _subscription: WsSubscription,
 }
 
 impl Component for BookView {
@@ -97,10 +111,13 @@ impl Component for BookView {
             media_popup: None,
             editing_item: BookEdit::default(),
             is_editing: false,
+            is_viewing_history: false,
 
             cached_tags: Vec::new(),
 
             search_poster_metadata: false,
+
+            _subscription: subscribe_to_book(ctx),
         }
     }
 
@@ -108,6 +125,16 @@ impl Component for BookView {
         match msg {
             Msg::Ignore => return false,
 
+            Msg::BookUpdated => {
+                let book_id = ctx.props().id;
+
+                ctx.link().send_future(async move {
+                    Msg::RetrieveMediaView(Box::new(request::get_media_view(book_id).await))
+                });
+
+                return false;
+            }
+
             // Multiselect
             Msg::MultiselectToggle(inserted, tag_id) => {
                 if let Some(curr_book) = self.media.as_ref().and_then(|v| v.as_ok().ok()) {
@@ -137,12 +164,18 @@ impl Component for BookView {
                         let tag_resp = request::new_tag(item.name.clone(), type_of).await;
 
                         match tag_resp.ok() {
-                            Ok(tag_resp) => {
+                            Ok(Some(tag_resp)) => {
                                 item.register.emit(tag_resp.id);
 
                                 Msg::MultiCreateResponse(tag_resp)
                             }
 
+                            Ok(None) => {
+                                log::info!("Tag creation has been submitted for review.");
+
+                                Msg::Ignore
+                            }
+
                             Err(err) => {
                                 log::error!("{err}");
 
@@ -236,6 +269,10 @@ impl Component for BookView {
                 self.is_editing = !self.is_editing;
             }
 
+            Msg::ToggleHistory => {
+                self.is_viewing_history = !self.is_viewing_history;
+            }
+
             Msg::SaveEdits => {
                 if let Some(curr_book) = self.media.as_ref().and_then(|v| v.as_ok().ok()) {
                     let edit = self.editing_item.clone();
@@ -313,9 +350,13 @@ impl Component for BookView {
             Msg::OnDelete(resp) => {
                 match resp {
                     WrappingResponse::Resp(okay) => {
+                        // TODO: Deletion View.
                         if okay {
-                            // TODO: Deletion View.
                             self.media = Some(WrappingResponse::error("Deleted..."));
+                        } else {
+                            self.media = Some(WrappingResponse::error(
+                                "Deletion has been submitted for review.",
+                            ));
                         }
                     }
 
@@ -323,6 +364,28 @@ impl Component for BookView {
                 }
             }
 
+            Msg::OnMerge(into_book_id, resp) => match resp {
+                WrappingResponse::Resp(_) => {
+                    // Admin merges are applied instantly.
+                    if get_member_self()
+                        .map(|v| v.permissions.is_admin())
+                        .unwrap_or_default()
+                    {
+                        if let Some(nav) = ctx.link().navigator() {
+                            nav.push(&Route::ViewMeta {
+                                meta_id: into_book_id,
+                            });
+                        }
+                    } else {
+                        self.media = Some(WrappingResponse::error(
+                            "Merge has been submitted for review.",
+                        ));
+                    }
+                }
+
+                WrappingResponse::Error(e) => self.media = Some(WrappingResponse::Error(e)),
+            },
+
             // Popup
             Msg::ClosePopup => {
                 self.media_popup = None;
@@ -358,6 +421,17 @@ impl Component for BookView {
             Msg::RetrieveMediaView(value) => {
                 self.media = Some(*value);
 
+                // If the book was merged into another one we'll point the url to the remaining book.
+                if let Some(book) = self.media.as_ref().and_then(|v| v.as_ok().ok()) {
+                    if book.metadata.id != ctx.props().id {
+                        if let Some(nav) = ctx.link().navigator() {
+                            nav.replace(&Route::ViewMeta {
+                                meta_id: book.metadata.id,
+                            });
+                        }
+                    }
+                }
+
                 if self.is_editing && self.cached_posters.is_none() {
                     ctx.link().send_message(Msg::ReloadPosters);
                 }
@@ -391,7 +465,11 @@ impl Component for BookView {
         }
     }
 
-    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if old_props.id != ctx.props().id {
+            self._subscription = subscribe_to_book(ctx);
+        }
+
         if let Some(member) = get_member_self() {
             if member.localsettings.get_page_view_default().is_editing() {
                 self.is_editing = true;
@@ -402,6 +480,13 @@ impl Component for BookView {
     }
 }
 
+fn subscribe_to_book(ctx: &Context<BookView>) -> WsSubscription {
+    ws::subscribe(
+        WebsocketTopic::Book(ctx.props().id),
+        ctx.link().callback(|_| Msg::BookUpdated),
+    )
+}
+
 impl BookView {
     fn render_editing(&self, ctx: &Context<Self>) -> Html {
         if let Some(resp) = self.media.as_ref() {
@@ -856,7 +941,17 @@ impl BookView {
                             <div class="sidebar-item">
                                 <button class="btn btn-secondary" onclick={ctx.link().callback(|_| Msg::ToggleEdit)}>{"Start Editing"}</button>
                             </div>
+
+                            <div class="sidebar-item">
+                                <WatchButton type_of={ EditType::Book } model_id={ *book_id } />
+                            </div>
                         </LoginBarrier>
+
+                        <div class="sidebar-item">
+                            <button class="btn btn-secondary" onclick={ctx.link().callback(|_| Msg::ToggleHistory)}>
+                                { if self.is_viewing_history { "Hide History" } else { "View History" } }
+                            </button>
+                        </div>
                     </div>
 
                     // TODO: We use px-0 b/c view-container pads
@@ -972,6 +1067,19 @@ impl BookView {
                                 }
                                 </div>
                             </section>
+
+                            {
+                                if self.is_viewing_history {
+                                    html! {
+                                        <section class="row">
+                                            <h2>{ "History" }</h2>
+                                            <EditHistoryPanel model={ HistoryOf::Book(book_id) } />
+                                        </section>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
                         </div>
 
                     </div>
@@ -1008,6 +1116,20 @@ impl BookView {
                                 <div class="dropdown-item" yew-close-popup="" onclick={
                                     Self::on_click_prevdef_stopprop(ctx.link(), Msg::ShowPopup(DisplayOverlay::SearchForBook { input_value: None }))
                                 }>{ "Search New Metadata" }</div>
+                                <div class="dropdown-item" yew-close-popup="" onclick={ ctx.link().callback_future(move |_| async move {
+                                    let into_book_id = gloo_utils::window()
+                                        .prompt_with_message("ID of the Book to merge this one into")
+                                        .ok()
+                                        .flatten()
+                                        .and_then(|v| v.trim().parse::<usize>().ok())
+                                        .map(BookId::from);
+
+                                    if let Some(into_book_id) = into_book_id {
+                                        Msg::OnMerge(into_book_id, request::merge_book(book_id, into_book_id).await)
+                                    } else {
+                                        Msg::Ignore
+                                    }
+                                }) }>{ "Merge Into Book" }</div>
                                 <div class="dropdown-item" yew-close-popup="" onclick={ ctx.link().callback_future(move |_| async move {
                                     Msg::OnDelete(request::delete_book(book_id).await)
                                 }) }>{ "Delete" }</div>
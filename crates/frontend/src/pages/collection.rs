@@ -1,6 +1,7 @@
 use common::api::WrappingResponse;
 use common_local::{
     api::{self, UpdateCollectionModel},
+    edit::EditType,
     SearchType,
 };
 use yew::prelude::*;
@@ -8,7 +9,7 @@ use yew::prelude::*;
 use crate::{
     components::{
         popup::{search::SearchSelectedValue, SearchBy},
-        LoginBarrier, PopupSearch,
+        LoginBarrier, PopupSearch, WatchButton,
     },
     pages::home::MediaItem,
     request,
@@ -79,6 +80,10 @@ impl Component for CollectionView {
                             <div class="sidebar-item">
                                 <button class="btn btn-secondary">{ "Start Editing" }</button>
                             </div>
+
+                            <div class="sidebar-item">
+                                <WatchButton type_of={ EditType::Collection } model_id={ *value.id } />
+                            </div>
                         </LoginBarrier>
                     </div>
 
@@ -1,12 +1,23 @@
 use std::fmt;
 
-use chrono::Utc;
+use chrono::{NaiveDate, TimeZone, Utc};
 use common::api::WrappingResponse;
-use common_local::{api, edit::*, item::edit::*};
+use common_local::{
+    api,
+    edit::*,
+    item::edit::*,
+    ws::{WebsocketNotification, WebsocketTopic},
+    EditId,
+};
 use wasm_bindgen::UnwrapThrowExt;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::{html::Scope, prelude::*};
 
-use crate::{components::LoginBarrier, get_member_self, request};
+use crate::{
+    components::{EditCommentsPanel, EditDiffView, LoginBarrier},
+    get_member_self, request,
+    ws::{self, WsSubscription},
+};
 
 #[derive(Properties, PartialEq, Eq)]
 pub struct Property {}
@@ -16,14 +27,29 @@ pub enum Msg {
     // Requests
     RequestEdits,
 
+    UpdateFilter(Box<api::EditListQuery>),
+
     // Results
     EditListResults(WrappingResponse<api::GetEditListResponse>),
 
     EditItemUpdate(Box<WrappingResponse<api::PostEditResponse>>),
+
+    ToggleDiscussion(EditId),
+
+    /// Admin only. Asks for a reason before changing the status.
+    Override(EditId, EditStatus),
+
+    Notification(WebsocketNotification),
 }
 
 pub struct EditListPage {
     items_resp: Option<WrappingResponse<api::GetEditListResponse>>,
+
+    filter: api::EditListQuery,
+
+    open_discussions: Vec<EditId>,
+
+    _subscription: WsSubscription,
 }
 
 impl Component for EditListPage {
@@ -33,17 +59,36 @@ impl Component for EditListPage {
     fn create(ctx: &Context<Self>) -> Self {
         ctx.link().send_message(Msg::RequestEdits);
 
-        Self { items_resp: None }
+        Self {
+            items_resp: None,
+            filter: api::EditListQuery::default(),
+            open_discussions: Vec::new(),
+
+            _subscription: ws::subscribe(
+                WebsocketTopic::Edits,
+                ctx.link().callback(Msg::Notification),
+            ),
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::RequestEdits => {
+                let filter = self.filter.clone();
+
                 ctx.link().send_future(async move {
-                    Msg::EditListResults(request::get_edit_list(None, None).await)
+                    Msg::EditListResults(request::get_edit_list(filter).await)
                 });
             }
 
+            Msg::UpdateFilter(filter) => {
+                self.filter = *filter;
+
+                ctx.link().send_message(Msg::RequestEdits);
+
+                return false;
+            }
+
             Msg::EditListResults(mut resp) => {
                 // Default old BookEdit (generate_person_rows checks for both new/old have Some)
                 if let WrappingResponse::Resp(resp) = &mut resp {
@@ -58,14 +103,72 @@ impl Component for EditListPage {
                                 v.old = Some(Default::default())
                             }
                         }
-                        EditData::Tag => todo!(),
-                        EditData::Collection => todo!(),
+                        EditData::Tag(v) => {
+                            if v.old.is_none() {
+                                v.old = Some(Default::default())
+                            }
+                        }
+                        EditData::Collection(v) => {
+                            if v.old.is_none() {
+                                v.old = Some(Default::default())
+                            }
+                        }
+                        EditData::BookMerge(_) | EditData::PersonMerge(_) => (),
                     });
                 }
 
                 self.items_resp = Some(resp);
             }
 
+            Msg::Notification(value) => match value {
+                WebsocketNotification::EditVoted { id, vote_count } => {
+                    let item = match self.items_resp.as_mut() {
+                        Some(WrappingResponse::Resp(resp)) => {
+                            resp.items.iter_mut().find(|v| v.id == id)
+                        }
+                        _ => None,
+                    };
+
+                    match item {
+                        Some(item) => item.vote_count = vote_count.max(0) as usize,
+                        None => return false,
+                    }
+                }
+
+                _ => {
+                    ctx.link().send_message(Msg::RequestEdits);
+
+                    return false;
+                }
+            },
+
+            Msg::Override(edit_id, status) => {
+                let reason = gloo_utils::window()
+                    .prompt_with_message("Reason")
+                    .ok()
+                    .flatten()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+
+                if let Some(reason) = reason {
+                    ctx.link().send_future(async move {
+                        Msg::EditItemUpdate(Box::new(
+                            request::override_edit_item(edit_id, status, reason).await,
+                        ))
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::ToggleDiscussion(edit_id) => {
+                if let Some(index) = self.open_discussions.iter().position(|v| *v == edit_id) {
+                    self.open_discussions.remove(index);
+                } else {
+                    self.open_discussions.push(edit_id);
+                }
+            }
+
             Msg::EditItemUpdate(item) => {
                 let mut item = item.ok().unwrap_throw();
 
@@ -123,7 +226,19 @@ impl Component for EditListPage {
             html! {
                 <div class="view-container edit-list-view-container">
                     <div class="container">
-                        { for resp.items.iter().map(|item| self.render_item(item, ctx.link())) }
+                        { self.render_filters(ctx.link()) }
+
+                        {
+                            if resp.items.is_empty() {
+                                html! {
+                                    <span class="text-muted">{ "No edits found." }</span>
+                                }
+                            } else {
+                                html! {
+                                    for resp.items.iter().map(|item| self.render_item(item, ctx.link()))
+                                }
+                            }
+                        }
                     </div>
                 </div>
             }
@@ -136,6 +251,217 @@ impl Component for EditListPage {
 }
 
 impl EditListPage {
+    /// Creates a callback which applies the changed input value to a copy of the current filter.
+    fn filter_callback<E: 'static>(
+        &self,
+        scope: &Scope<Self>,
+        get_value: fn(E) -> String,
+        update: fn(&mut api::EditListQuery, String),
+    ) -> Callback<E> {
+        let filter = self.filter.clone();
+
+        scope.callback(move |e: E| {
+            let mut filter = filter.clone();
+
+            update(&mut filter, get_value(e));
+
+            // Always start back at the first page.
+            filter.offset = None;
+
+            Msg::UpdateFilter(Box::new(filter))
+        })
+    }
+
+    /// Creates a callback which narrows the current filter down by a clicked value.
+    fn filter_by_callback(
+        &self,
+        scope: &Scope<Self>,
+        update: impl Fn(&mut api::EditListQuery) + 'static,
+    ) -> Callback<MouseEvent> {
+        let filter = self.filter.clone();
+
+        scope.callback(move |_| {
+            let mut filter = filter.clone();
+
+            update(&mut filter);
+
+            filter.offset = None;
+
+            Msg::UpdateFilter(Box::new(filter))
+        })
+    }
+
+    fn render_filters(&self, scope: &Scope<Self>) -> Html {
+        fn select_value(e: Event) -> String {
+            e.target_unchecked_into::<HtmlSelectElement>().value()
+        }
+
+        fn input_value(e: Event) -> String {
+            e.target_unchecked_into::<HtmlInputElement>().value()
+        }
+
+        fn parse_date(value: &str) -> Option<i64> {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+
+            Some(date.and_hms_opt(0, 0, 0)?.timestamp())
+        }
+
+        fn format_date(secs: Option<i64>) -> String {
+            secs.map(|v| Utc.timestamp(v, 0).format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        }
+
+        let filter = &self.filter;
+
+        let is_logged_in = get_member_self().is_some();
+
+        html! {
+            <div class="row justify-content-center mb-2">
+                <div class="col-12 col-lg-9 edit-list-filters">
+                    <div class="row g-2">
+                        <div class="col">
+                            <select class="form-select form-select-sm" title="Type" onchange={ self.filter_callback(scope, select_value, |f, v| {
+                                f.type_of = v.parse::<u8>().ok().and_then(|v| EditType::try_from(v).ok());
+                            }) }>
+                                <option value="" selected={ filter.type_of.is_none() }>{ "All Types" }</option>
+                                {
+                                    for [EditType::Book, EditType::Person, EditType::Tag, EditType::Collection]
+                                        .into_iter()
+                                        .map(|v| html! {
+                                            <option value={ (v as u8).to_string() } selected={ filter.type_of == Some(v) }>{ v.get_name() }</option>
+                                        })
+                                }
+                            </select>
+                        </div>
+
+                        <div class="col">
+                            <select class="form-select form-select-sm" title="Operation" onchange={ self.filter_callback(scope, select_value, |f, v| {
+                                f.operation = v.parse::<u8>().ok().and_then(|v| EditOperation::try_from(v).ok());
+                            }) }>
+                                <option value="" selected={ filter.operation.is_none() }>{ "All Operations" }</option>
+                                {
+                                    for [EditOperation::Create, EditOperation::Delete, EditOperation::Modify, EditOperation::Merge]
+                                        .into_iter()
+                                        .map(|v| html! {
+                                            <option value={ (v as u8).to_string() } selected={ filter.operation == Some(v) }>{ v.get_name() }</option>
+                                        })
+                                }
+                            </select>
+                        </div>
+
+                        <div class="col">
+                            <select class="form-select form-select-sm" title="Status" onchange={ self.filter_callback(scope, select_value, |f, v| {
+                                f.status = v.parse::<u8>().ok().and_then(|v| EditStatus::try_from(v).ok());
+                            }) }>
+                                <option value="" selected={ filter.status.is_none() }>{ "All Statuses" }</option>
+                                {
+                                    for [
+                                        EditStatus::Pending,
+                                        EditStatus::Accepted,
+                                        EditStatus::Rejected,
+                                        EditStatus::Failed,
+                                        EditStatus::Cancelled,
+                                        EditStatus::ForceAccepted,
+                                        EditStatus::ForceRejected,
+                                    ]
+                                        .into_iter()
+                                        .map(|v| html! {
+                                            <option value={ (v as u8).to_string() } selected={ filter.status == Some(v) }>{ v.get_name() }</option>
+                                        })
+                                }
+                            </select>
+                        </div>
+
+                        <div class="col">
+                            <input
+                                class="form-control form-control-sm"
+                                type="number"
+                                placeholder="Member ID"
+                                value={ filter.member_id.map(|v| v.to_string()).unwrap_or_default() }
+                                onchange={ self.filter_callback(scope, input_value, |f, v| {
+                                    f.member_id = v.trim().parse::<usize>().ok().map(Into::into);
+                                }) }
+                            />
+                        </div>
+
+                        <div class="col">
+                            <input
+                                class="form-control form-control-sm"
+                                type="number"
+                                placeholder="Model ID"
+                                value={ filter.model_id.map(|v| v.to_string()).unwrap_or_default() }
+                                onchange={ self.filter_callback(scope, input_value, |f, v| {
+                                    f.model_id = v.trim().parse().ok();
+                                }) }
+                            />
+                        </div>
+                    </div>
+
+                    <div class="row g-2 mt-0 align-items-center">
+                        <div class="col">
+                            <input
+                                class="form-control form-control-sm"
+                                type="date"
+                                title="Created After"
+                                value={ format_date(filter.created_after) }
+                                onchange={ self.filter_callback(scope, input_value, |f, v| {
+                                    f.created_after = parse_date(&v);
+                                }) }
+                            />
+                        </div>
+
+                        <div class="col">
+                            <input
+                                class="form-control form-control-sm"
+                                type="date"
+                                title="Created Before"
+                                value={ format_date(filter.created_before) }
+                                onchange={ self.filter_callback(scope, input_value, |f, v| {
+                                    f.created_before = parse_date(&v);
+                                }) }
+                            />
+                        </div>
+
+                        <div class="col">
+                            <select class="form-select form-select-sm" title="Sort By" onchange={ self.filter_callback(scope, select_value, |f, v| {
+                                f.sort = match v.as_str() {
+                                    "expires" => Some(api::EditListSort::Expires),
+                                    "votes" => Some(api::EditListSort::Votes),
+                                    _ => None,
+                                };
+                            }) }>
+                                <option value="created" selected={ filter.sort.is_none() }>{ "Newest" }</option>
+                                <option value="expires" selected={ filter.sort == Some(api::EditListSort::Expires) }>{ "Expiring Soonest" }</option>
+                                <option value="votes" selected={ filter.sort == Some(api::EditListSort::Votes) }>{ "Most Votes" }</option>
+                            </select>
+                        </div>
+
+                        {
+                            if is_logged_in {
+                                html! {
+                                    <div class="col form-check ms-2">
+                                        <input
+                                            class="form-check-input"
+                                            type="checkbox"
+                                            id="edit-filter-not-voted"
+                                            checked={ filter.not_voted }
+                                            onchange={ self.filter_callback(scope, |e: Event| e.target_unchecked_into::<HtmlInputElement>().checked().to_string(), |f, v| {
+                                                f.not_voted = v == "true";
+                                            }) }
+                                        />
+                                        <label class="form-check-label" for="edit-filter-not-voted">{ "Not voted by me" }</label>
+                                    </div>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
     fn render_item(&self, item: &SharedEditModel, scope: &Scope<Self>) -> Html {
         let id = item.id;
 
@@ -150,19 +476,68 @@ impl EditListPage {
 
         let my_vote = self.get_my_vote(item);
 
+        let is_discussion_open = self.open_discussions.contains(&id);
+
+        let has_conflicts = match &item.data {
+            EditData::Book(v) => v.conflicts.is_some(),
+            EditData::Person(v) => v.conflicts.is_some(),
+            EditData::Tag(v) => v.conflicts.is_some(),
+            EditData::Collection(v) => v.conflicts.is_some(),
+            EditData::BookMerge(_) | EditData::PersonMerge(_) => false,
+        };
+
+        let is_admin = get_member_self()
+            .map(|v| v.permissions.is_admin())
+            .unwrap_or_default();
+
+        let is_author = get_member_self()
+            .zip(item.member.as_ref())
+            .map(|(me, author)| me.id == author.id)
+            .unwrap_or_default();
+
         html! {
             <div class="row justify-content-center" key={ id.to_string() }>
                 <div class="col-12 col-lg-9 editing-item-card">
                     <div class="header">
                         // Left
                         <div class="aligned-left">
-                            <h5>{ item.operation.get_name() } { " " } { item.type_of.get_name() }</h5>
+                            <h5>
+                                { item.operation.get_name() } { " " } { item.type_of.get_name() }
+                                {
+                                    if let Some(model_id) = item.model_id {
+                                        let type_of = item.type_of;
+
+                                        html! {
+                                            <button
+                                                class="btn btn-sm btn-link"
+                                                title="Show every edit for this item"
+                                                onclick={ self.filter_by_callback(scope, move |f| {
+                                                    f.type_of = Some(type_of);
+                                                    f.model_id = Some(model_id);
+                                                }) }
+                                            >{ format!("#{model_id}") }</button>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                            </h5>
                             <div>
                                 <b>{ "Member: " }</b>
                                 {
                                     if let Some(member) = item.member.as_ref() {
+                                        let member_id = member.id;
+
                                         html! {
-                                            <span>{ member.name.clone() }</span>
+                                            <>
+                                                <span
+                                                    class="text-decoration-underline"
+                                                    role="button"
+                                                    title="Show every edit by this member"
+                                                    onclick={ self.filter_by_callback(scope, move |f| f.member_id = Some(member_id)) }
+                                                >{ member.name.clone() }</span>
+                                                <span class="badge bg-secondary ms-1" title="Reputation">{ member.reputation }</span>
+                                            </>
                                         }
                                     } else {
                                         html! {}
@@ -182,6 +557,30 @@ impl EditListPage {
                                 <span class={classes!("badge", status_color)}>{ item.status.get_name() }</span>
                             </div>
 
+                            {
+                                if let Some(batch_id) = item.batch_id {
+                                    html! {
+                                        <div>
+                                            <span class="badge bg-info" title="Voting on this edit also votes on the rest of the mass edit.">{ format!("Mass Edit #{batch_id}") }</span>
+                                        </div>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
+
+                            {
+                                if let Some(server_link_id) = item.server_link_id {
+                                    html! {
+                                        <div>
+                                            <span class="badge bg-secondary" title="Submitted by a linked server on behalf of the member.">{ format!("Linked Server #{server_link_id}") }</span>
+                                        </div>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
+
                             {
                                 // Closed
                                 if let Some(ended_at) = item.ended_at {
@@ -237,14 +636,61 @@ impl EditListPage {
 
                     <hr class="transparent" />
 
+                    {
+                        if has_conflicts {
+                            if item.status.is_pending() {
+                                html! {
+                                    <div class="alert alert-warning">
+                                        <span>{ "The current model has changed since this edit was made. Conflicting values won't be applied." }</span>
+                                        {
+                                            if is_author {
+                                                html! {
+                                                    <button
+                                                        class="btn btn-sm btn-warning ms-2"
+                                                        onclick={scope.callback_future(move |_| async move {
+                                                            Msg::EditItemUpdate(Box::new(request::rebase_edit_item(id).await))
+                                                        })}
+                                                    >{ "Rebase" }</button>
+                                                }
+                                            } else {
+                                                html! {}
+                                            }
+                                        }
+                                    </div>
+                                }
+                            } else {
+                                html! {
+                                    <div class="alert alert-warning">
+                                        { "Some values weren't applied since they conflicted with the model at the time." }
+                                    </div>
+                                }
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+
                     <div class="body">
                         {
                             match &item.data {
                                 EditData::Book(v) => Self::generate_book_rows(item.status, item.operation, v, scope),
                                 EditData::Person(v) => Self::generate_person_rows(item.status, item.operation, v, scope),
-                                _ => todo!(),
+                                EditData::BookMerge(v) => Self::generate_merge_rows(
+                                    *v.source_id,
+                                    *v.target_id,
+                                    v.preview.as_ref().map(|v| v.title.clone().or_else(|| v.clean_title.clone()).unwrap_or_default()),
+                                ),
+                                EditData::PersonMerge(v) => Self::generate_merge_rows(
+                                    *v.source_id,
+                                    *v.target_id,
+                                    v.preview.as_ref().map(|v| v.name.clone()),
+                                ),
+                                EditData::Tag(v) => Self::generate_tag_rows(item.status, item.operation, v, scope),
+                                EditData::Collection(v) => Self::generate_collection_rows(item.status, item.operation, v, scope),
                             }
                         }
+
+                        { Self::render_list_diff(item) }
                     </div>
 
                     <hr class="transparent" />
@@ -309,41 +755,58 @@ impl EditListPage {
                             </div>
                             <div class="aligned-right">
                             {
-                                if item.status.is_pending() {
+                                if !is_admin || item.status.is_accepted() {
+                                    html! {}
+                                } else if item.status.is_pending() {
                                     html! {
                                         <>
                                             <button
                                                 class="btn btn-sm btn-danger"
-                                                onclick={scope.callback_future(move |_| async move {
-                                                    let resp = request::update_edit_item(id, &UpdateEditModel {
-                                                        status: Some(EditStatus::ForceRejected),
-                                                        .. UpdateEditModel::default()
-                                                    }).await;
-
-                                                    Msg::EditItemUpdate(Box::new(resp))
-                                                })}
+                                                onclick={ scope.callback(move |_| Msg::Override(id, EditStatus::ForceRejected)) }
                                             >{ "Force Reject" }</button>
 
                                             <button
                                                 class="btn btn-sm btn-success ms-2"
-                                                onclick={scope.callback_future(move |_| async move {
-                                                    let resp = request::update_edit_item(id, &UpdateEditModel {
-                                                        status: Some(EditStatus::ForceAccepted),
-                                                        .. UpdateEditModel::default()
-                                                    }).await;
-
-                                                    Msg::EditItemUpdate(Box::new(resp))
-                                                })}
+                                                onclick={ scope.callback(move |_| Msg::Override(id, EditStatus::ForceAccepted)) }
                                             >{ "Force Accept" }</button>
                                         </>
                                     }
                                 } else {
-                                    html! {}
+                                    html! {
+                                        <>
+                                            <button
+                                                class="btn btn-sm btn-warning"
+                                                onclick={ scope.callback(move |_| Msg::Override(id, EditStatus::Pending)) }
+                                            >{ "Reopen" }</button>
+
+                                            <button
+                                                class="btn btn-sm btn-success ms-2"
+                                                title="Applied against the current model. Conflicting values are skipped."
+                                                onclick={ scope.callback(move |_| Msg::Override(id, EditStatus::ForceAccepted)) }
+                                            >{ "Force Accept" }</button>
+                                        </>
+                                    }
                                 }
                             }
                             </div>
                         </LoginBarrier>
                     </div>
+
+                    <div class="discussion">
+                        <button class="btn btn-sm btn-secondary" onclick={ scope.callback(move |_| Msg::ToggleDiscussion(id)) }>
+                            { if is_discussion_open { "Hide Discussion" } else { "Show Discussion" } }
+                        </button>
+
+                        {
+                            if is_discussion_open {
+                                html! {
+                                    <EditCommentsPanel edit_id={ id } />
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </div>
                 </div>
             </div>
         }
@@ -374,6 +837,97 @@ impl EditListPage {
         }
     }
 
+    fn generate_tag_rows(
+        status: EditStatus,
+        operation: EditOperation,
+        tag_edit_data: &TagEditData,
+        _scope: &Scope<Self>,
+    ) -> Html {
+        let current = tag_edit_data.current.as_ref();
+        let updated = tag_edit_data.updated.clone().unwrap_or_default();
+
+        let (new_data, old_data) = match (&tag_edit_data.new, &tag_edit_data.old) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return html! {},
+        };
+
+        if operation == EditOperation::Create {
+            return html! {
+                <div class="comparison-row">
+                    <div class="row-title"><span>{ "Creating" }</span></div>
+                    <div class="row-grow"><div class="badge text-bg-success text-wrap">{ new_data.name.clone().unwrap_or_default() }</div></div>
+                    <div class="row-grow"><div class="badge text-bg-secondary text-wrap">{ new_data.type_of.as_ref().map(|v| format!("{v:?}")).unwrap_or_default() }</div></div>
+                </div>
+            };
+        }
+
+        html! {
+            <>
+                { Self::display_row("Name", &new_data.name, &old_data.name, current.map(|v| &v.name), updated.name, status, operation) }
+                { Self::display_row(
+                    "Type",
+                    &new_data.type_of.as_ref().map(|v| format!("{v:?}")),
+                    &old_data.type_of.as_ref().map(|v| format!("{v:?}")),
+                    current.map(|v| format!("{:?}", v.type_of)).as_ref(),
+                    updated.type_of,
+                    status,
+                    operation,
+                ) }
+            </>
+        }
+    }
+
+    fn generate_collection_rows(
+        status: EditStatus,
+        operation: EditOperation,
+        coll_edit_data: &CollectionEditData,
+        _scope: &Scope<Self>,
+    ) -> Html {
+        let current = coll_edit_data.current.as_ref();
+        let updated = coll_edit_data.updated.clone().unwrap_or_default();
+
+        let (new_data, old_data) = match (&coll_edit_data.new, &coll_edit_data.old) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                { Self::display_row("Name", &new_data.name, &old_data.name, current.map(|v| &v.name), updated.name, status, operation) }
+                { Self::display_row("Description", &new_data.description, &old_data.description, current.and_then(|v| v.description.as_ref()), updated.description, status, operation) }
+            </>
+        }
+    }
+
+    fn generate_merge_rows(
+        source_id: usize,
+        target_id: usize,
+        preview_name: Option<String>,
+    ) -> Html {
+        html! {
+            <>
+                <div class="comparison-row">
+                    <div class="row-title"><span>{ "Merging" }</span></div>
+                    <div class="row-grow"><div class="badge text-bg-danger text-wrap">{ source_id }</div></div>
+                    <div class="row-grow"><div class="badge text-bg-success text-wrap">{ target_id }</div></div>
+                </div>
+
+                {
+                    if let Some(name) = preview_name {
+                        html! {
+                            <div class="comparison-row">
+                                <div class="row-title"><span>{ "Result" }</span></div>
+                                <div class="row-grow"><div class="badge text-bg-secondary text-wrap">{ name }</div></div>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </>
+        }
+    }
+
     fn generate_book_rows(
         status: EditStatus,
         operation: EditOperation,
@@ -383,6 +937,19 @@ impl EditListPage {
         let current = book_edit_data.current.as_ref();
         let updated = book_edit_data.updated.clone().unwrap_or_default();
 
+        if operation == EditOperation::Delete {
+            let title = current
+                .and_then(|v| v.title.clone().or_else(|| v.clean_title.clone()))
+                .unwrap_or_else(|| String::from("(Unknown Book)"));
+
+            return html! {
+                <div class="comparison-row">
+                    <div class="row-title"><span>{ "Removing" }</span></div>
+                    <div class="row-grow"><div class="badge text-bg-danger text-wrap">{ title }</div></div>
+                </div>
+            };
+        }
+
         let (new_data, old_data) = match (&book_edit_data.new, &book_edit_data.old) {
             (Some(a), Some(b)) => (a, b),
             _ => return html! {},
@@ -399,22 +966,28 @@ impl EditListPage {
                 { Self::display_row("Language", &new_data.language, &old_data.language, current.map(|v| &v.language), updated.language, status, operation) }
                 { Self::display_row("Display Person", &new_data.display_person_id, &old_data.display_person_id, current.and_then(|v| v.cached.author_id.as_ref()), updated.display_person_id, status, operation) }
 
-                { Self::display_row_array("Added ISBNs", &new_data.added_isbns, &old_data.added_isbns, None, updated.added_isbns, status, operation) }
-                { Self::display_row_array("Removed ISBNs", &new_data.removed_isbns, &old_data.removed_isbns, None, updated.removed_isbns, status, operation) }
-
-                { Self::display_row_array_map(
-                    "Updated People", &new_data.updated_people, &old_data.updated_people, None, updated.updated_people, status, operation,
-                    |v| format!("{} - {}", v.0, v.1.as_deref().unwrap_or("(Empty)"))
-                ) }
-                { Self::display_row_array("Added People", &new_data.added_people, &old_data.added_people, None, updated.added_people, status, operation) }
-                { Self::display_row_array("Removed People", &new_data.removed_people, &old_data.removed_people, None, updated.removed_people, status, operation) }
                 // { Self::display_row("Publisher", &new_data.publisher, &old_data.publisher, current.and_then(|v| v.publisher.as_ref())) }
 
-                // TODO: People, Tags, Images
+                // ISBNs, People, Tags and Images are resolved by the backend. See `Self::render_list_diff`.
             </>
         }
     }
 
+    /// Added and removed values from the backends' diff. Single values are displayed by the rows above.
+    fn render_list_diff(item: &SharedEditModel) -> Html {
+        let diff = item
+            .diff
+            .iter()
+            .flatten()
+            .filter(|v| !matches!(v.change, EditFieldChange::Value { .. }))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        html! {
+            <EditDiffView {diff} />
+        }
+    }
+
     fn get_my_vote(&self, item: &SharedEditModel) -> Option<bool> {
         let votes = item.votes.as_ref()?;
 
@@ -496,150 +1069,6 @@ impl EditListPage {
             }
         }
     }
-
-    fn display_row_array<V: Clone + Default + PartialEq + fmt::Display + fmt::Debug>(
-        title: &'static str,
-        new_data: &Option<Vec<V>>,
-        old_data: &Option<Vec<V>>,
-        current: Option<&Vec<V>>,
-        is_updated: bool,
-        status: EditStatus,
-        operation: EditOperation,
-    ) -> Html {
-        Self::display_row_array_map::<V, V, _>(
-            title,
-            new_data,
-            old_data,
-            current,
-            is_updated,
-            status,
-            operation,
-            |a| a.clone(),
-        )
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    fn display_row_array_map<A, V, F>(
-        title: &'static str,
-        new_data: &Option<Vec<A>>,
-        old_data: &Option<Vec<A>>,
-        current: Option<&Vec<A>>,
-        is_updated: bool,
-        status: EditStatus,
-        operation: EditOperation,
-        map: F,
-    ) -> Html
-    where
-        A: PartialEq,
-        V: Clone + Default + fmt::Display + fmt::Debug,
-        F: Fn(&A) -> V,
-    {
-        match operation {
-            EditOperation::Modify => {
-                if let Some((new_value, old_value)) = determine_new_old(new_data, old_data) {
-                    if let Some(val) = old_value {
-                        html! {
-                            for val.iter().zip(new_value).enumerate()
-                                .map(|(index, (old_val, new_val))| html! {
-                                    <div class="comparison-row">
-                                        {
-                                            if index == 0 {
-                                                html! {
-                                                    <div class="row-title"><span>{ title }</span></div>
-                                                }
-                                            } else {
-                                                html! {
-                                                    <div class="row-title"></div>
-                                                }
-                                            }
-                                        }
-
-                                        // Old Value
-                                        <div class="row-grow"><div class="badge text-bg-danger text-wrap">{ map(old_val) }</div></div>
-
-                                        // New Value
-                                        {
-                                            if status.is_accepted() && is_updated {
-                                                html! {
-                                                    <div class="row-grow">
-                                                        <div class="badge text-bg-success text-wrap" title={ "Updated Model with value." }>
-                                                            { map(new_val) }
-                                                        </div>
-                                                    </div>
-                                                }
-                                            } else if current.is_some() && current != old_value.as_ref() {
-                                                html! {
-                                                    <div class="row-grow">
-                                                        <div class="badge text-bg-warning text-wrap" title={ "Current Model has a different value than wanted. It'll not be used if approved." }>
-                                                            { map(new_val) }
-                                                        </div>
-                                                    </div>
-                                                }
-                                            } else {
-                                                html! {
-                                                    <div class="row-grow">
-                                                        <div class="badge text-bg-success text-wrap">{ map(new_val) }</div>
-                                                    </div>
-                                                }
-                                            }
-                                        }
-                                    </div>
-                                })
-                        }
-                    } else {
-                        html! {
-                            <div class="comparison-row">
-                                <div class="row-title"><span>{ title }</span></div>
-
-                                // Old Value
-                                <div class="row-grow"><div class="badge text-bg-danger text-wrap">{ "(Empty)" }</div></div>
-
-                                // New Value
-                                <div class="row-grow d-flex flex-column">
-                                    {
-                                        for new_value.iter().map(|new_val| {
-                                            if status.is_accepted() && is_updated {
-                                                html! {
-                                                    <div
-                                                        class="badge text-bg-success text-wrap w-fit-content mb-1"
-                                                        title={ "Updated Model with value." }
-                                                    >
-                                                        { map(new_val) }
-                                                    </div>
-                                                }
-                                            } else if current.is_some() && current != old_value.as_ref() {
-                                                html! {
-                                                    <div
-                                                        class="badge text-bg-warning text-wrap w-fit-content mb-1"
-                                                        title={ "Current Model has a different value than wanted. It'll not be used if approved." }
-                                                    >
-                                                        { map(new_val) }
-                                                    </div>
-                                                }
-                                            } else {
-                                                html! {
-                                                    <div class="badge text-bg-success text-wrap w-fit-content mb-1">{ map(new_val) }</div>
-                                                }
-                                            }
-                                        })
-                                    }
-                                </div>
-                            </div>
-                        }
-                    }
-                } else {
-                    html! {}
-                }
-            }
-
-            // EditOperation::Create => html! {},
-            // EditOperation::Delete => html! {},
-            // EditOperation::Merge => html! {},
-            _ => {
-                html! { <div class="comparison-row"><div class="row-title"><span>{ "Unimplemented Operation" }</span></div></div> }
-            }
-        }
-    }
 }
 
 fn determine_new_old<'a, V>(
@@ -1,6 +1,9 @@
 mod auth;
 
-pub use auth::{authorize::AuthorizePage, login::LoginPage, logout::LogoutPage};
+pub use auth::{
+    authorize::AuthorizePage, grant::GrantPage, login::LoginPage, logout::LogoutPage,
+    reset_password::ResetPasswordPage, two_factor::TwoFactorPage,
+};
 
 pub mod admin;
 pub mod author_view;
@@ -10,6 +13,7 @@ pub mod home;
 pub mod list_authors;
 pub mod list_collections;
 pub mod list_edits;
+pub mod notifications;
 pub mod options;
 
 pub use author_view::AuthorView;
@@ -19,4 +23,5 @@ pub use home::HomePage;
 pub use list_authors::AuthorListPage;
 pub use list_collections::ListCollectionsPage;
 pub use list_edits::EditListPage;
+pub use notifications::NotificationsPage;
 pub use options::OptionsPage;
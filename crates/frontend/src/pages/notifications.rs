@@ -0,0 +1,175 @@
+use common::api::WrappingResponse;
+use common_local::{api, notification::SharedNotification, NotificationId};
+use yew::{html::Scope, prelude::*};
+use yew_router::prelude::Link;
+
+use crate::{request, Route};
+
+const PAGE_SIZE: usize = 25;
+
+pub enum Msg {
+    // Requests
+    RequestNotifications,
+
+    ChangePage(usize),
+    ToggleUnreadOnly,
+
+    /// Marks every notification if None.
+    Mark(Option<NotificationId>, bool),
+
+    // Results
+    NotificationsResults(WrappingResponse<api::GetNotificationsResponse>),
+    MarkResult(WrappingResponse<String>),
+}
+
+pub struct NotificationsPage {
+    items_resp: Option<WrappingResponse<api::GetNotificationsResponse>>,
+
+    query: api::NotificationListQuery,
+}
+
+impl Component for NotificationsPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::RequestNotifications);
+
+        Self {
+            items_resp: None,
+            query: api::NotificationListQuery {
+                offset: Some(0),
+                limit: Some(PAGE_SIZE),
+                unread_only: false,
+            },
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RequestNotifications => {
+                let query = self.query.clone();
+
+                ctx.link().send_future(async move {
+                    Msg::NotificationsResults(request::get_notifications(query).await)
+                });
+
+                return false;
+            }
+
+            Msg::ChangePage(page) => {
+                self.query.offset = Some(page * PAGE_SIZE);
+
+                ctx.link().send_message(Msg::RequestNotifications);
+
+                return false;
+            }
+
+            Msg::ToggleUnreadOnly => {
+                self.query.unread_only = !self.query.unread_only;
+                self.query.offset = Some(0);
+
+                ctx.link().send_message(Msg::RequestNotifications);
+            }
+
+            Msg::Mark(id, is_read) => {
+                ctx.link().send_future(async move {
+                    Msg::MarkResult(request::mark_notifications(id.map(|v| vec![v]), is_read).await)
+                });
+
+                return false;
+            }
+
+            Msg::MarkResult(resp) => {
+                if let Err(e) = resp.ok() {
+                    log::error!("{e}");
+                }
+
+                ctx.link().send_message(Msg::RequestNotifications);
+
+                return false;
+            }
+
+            Msg::NotificationsResults(resp) => {
+                self.items_resp = Some(resp);
+            }
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(resp) = self.items_resp.as_ref() {
+            let resp = crate::continue_or_html_err!(resp);
+
+            let page = resp.offset / PAGE_SIZE;
+            let page_count = (resp.total + PAGE_SIZE - 1) / PAGE_SIZE;
+
+            html! {
+                <div class="view-container searches-list-view-container">
+                    <div class="list-items">
+                        <div class="search-item-card">
+                            <h3>{ "Notifications" } { " (" } { resp.unread } { " unread)" }</h3>
+
+                            <button onclick={ ctx.link().callback(|_| Msg::ToggleUnreadOnly) }>
+                                { if self.query.unread_only { "Show All" } else { "Show Unread Only" } }
+                            </button>
+
+                            <button onclick={ ctx.link().callback(|_| Msg::Mark(None, true)) }>{ "Mark All As Read" }</button>
+
+                            <hr />
+
+                            <button
+                                disabled={ page == 0 }
+                                onclick={ ctx.link().callback(move |_| Msg::ChangePage(page.saturating_sub(1))) }
+                            >{ "Previous Page" }</button>
+
+                            <button
+                                disabled={ page + 1 >= page_count }
+                                onclick={ ctx.link().callback(move |_| Msg::ChangePage(page + 1)) }
+                            >{ "Next Page" }</button>
+
+                            <span>{ " Page: " } { page + 1 } { "/" } { page_count.max(1) }</span>
+                        </div>
+
+                        { for resp.items.iter().map(|item| Self::render_item(item, ctx.link())) }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <h1>{ "Loading..." }</h1>
+            }
+        }
+    }
+}
+
+impl NotificationsPage {
+    fn render_item(item: &SharedNotification, scope: &Scope<Self>) -> Html {
+        let id = item.id;
+        let is_read = item.is_read;
+
+        html! {
+            <div class="search-item-card">
+                <div class="body">
+                    <h4>
+                        { if is_read { "" } else { "● " } }
+                        { item.type_of.get_description() }
+                    </h4>
+
+                    <Link<Route> to={ Route::EditList }>{ "Edit #" } { item.edit_id }</Link<Route>>
+                </div>
+
+                <div class="footer">
+                    <div>{ item.created_at.format("%a, %e %b %y %r %Z") }</div>
+                </div>
+
+                <div class="tools">
+                    <button onclick={ scope.callback(move |_| Msg::Mark(Some(id), !is_read)) }>
+                        { if is_read { "Mark Unread" } else { "Mark Read" } }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+}
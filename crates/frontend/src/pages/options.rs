@@ -1,32 +1,144 @@
+use chrono::{Duration, Utc};
 use common::api::WrappingResponse;
 use common_local::{
     api,
-    item::member::{MemberSettings, PageView},
+    api_token::ApiTokenScopes,
+    item::member::{DigestFrequency, MemberSettings, PageView},
     update::OptionsUpdate,
+    ws::WebsocketTopic,
+    ApiTokenId, ServerLinkGrantId, ServerLinkId,
 };
+use gloo_utils::window;
 use wasm_bindgen::UnwrapThrowExt;
 use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
-use crate::{get_member_self, request};
+use crate::{
+    get_member_self,
+    pages::auth::two_factor::render_setup,
+    request,
+    ws::{self, WsSubscription},
+    MEMBER_SELF,
+};
+
+const API_TOKEN_SCOPES: [ApiTokenScopes; 4] = [
+    ApiTokenScopes::READ,
+    ApiTokenScopes::EDIT,
+    ApiTokenScopes::VOTE,
+    ApiTokenScopes::ADMIN,
+];
 
 pub enum Msg {
     // Request Results
     SettingsResults(Box<WrappingResponse<api::GetSettingsResponse>>),
+    TasksResults(WrappingResponse<api::GetTasksResponse>),
+    ApiTokensResults(WrappingResponse<api::GetApiTokensResponse>),
+    ApiTokenCreated(WrappingResponse<api::NewApiTokenResponse>),
+    /// Contains the message to show on success.
+    AccountResult(WrappingResponse<String>, &'static str),
+    TwoFactorResults(WrappingResponse<api::TwoFactorStatusResponse>),
+    TwoFactorSetupResults(WrappingResponse<api::TwoFactorSetupResponse>),
+    TwoFactorRecoveryCodes(WrappingResponse<api::TwoFactorRecoveryCodesResponse>),
+    TwoFactorDisabled(WrappingResponse<String>),
+    SessionsResults(WrappingResponse<api::GetSessionsResponse>),
+    ServerLinksResults(WrappingResponse<api::GetServerLinksResponse>),
+    ServerLinkRotated(WrappingResponse<api::RotateServerLinkResponse>),
+    ServerLinkGrantsResults(WrappingResponse<api::GetServerLinkGrantsResponse>),
 
     UpdateSettings,
+    UpdateTasks,
+    UpdateApiTokens,
+    UpdateTwoFactor,
+    UpdateSessions,
+    UpdateServerLinks,
+    UpdateServerLinkGrants,
+
+    ChangeName,
+    ChangeEmail,
+    ChangePassword,
+    ResendVerification,
+
+    SetupTwoFactor,
+    EnableTwoFactor,
+    DisableTwoFactor,
+    RegenerateRecoveryCodes,
+
+    RevokeSession(usize),
+
+    RenameServerLink(ServerLinkId),
+    RotateServerLink(ServerLinkId),
+    RevokeServerLink(ServerLinkId),
+
+    RevokeServerLinkGrant(ServerLinkGrantId),
+
+    RunTask(String),
+    CancelTask(String),
+
+    ToggleNewApiTokenScope(ApiTokenScopes),
+    CreateApiToken,
+    RevokeApiToken(ApiTokenId),
 }
 
 pub struct OptionsPage {
     resp: Option<WrappingResponse<api::GetSettingsResponse>>,
+    tasks_resp: Option<WrappingResponse<api::GetTasksResponse>>,
+    api_tokens_resp: Option<WrappingResponse<api::GetApiTokensResponse>>,
+
+    new_api_token_scopes: ApiTokenScopes,
+    /// Shown once after creating a token.
+    new_api_token_secret: Option<String>,
+
+    account_message: Option<String>,
+
+    two_factor_resp: Option<WrappingResponse<api::TwoFactorStatusResponse>>,
+    two_factor_setup: Option<api::TwoFactorSetupResponse>,
+    /// Shown once after enabling two-factor or regenerating them.
+    two_factor_recovery_codes: Option<Vec<String>>,
+    two_factor_message: Option<String>,
+
+    sessions_resp: Option<WrappingResponse<api::GetSessionsResponse>>,
+
+    server_links_resp: Option<WrappingResponse<api::GetServerLinksResponse>>,
+    /// Shown once after rotating the IDs of a server.
+    rotated_server_link: Option<api::RotateServerLinkResponse>,
+
+    server_link_grants_resp: Option<WrappingResponse<api::GetServerLinkGrantsResponse>>,
+
+    _subscription: WsSubscription,
 }
 
 impl Component for OptionsPage {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self { resp: None }
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            resp: None,
+            tasks_resp: None,
+            api_tokens_resp: None,
+
+            new_api_token_scopes: ApiTokenScopes::READ,
+            new_api_token_secret: None,
+
+            account_message: None,
+
+            two_factor_resp: None,
+            two_factor_setup: None,
+            two_factor_recovery_codes: None,
+            two_factor_message: None,
+
+            sessions_resp: None,
+
+            server_links_resp: None,
+            rotated_server_link: None,
+
+            server_link_grants_resp: None,
+
+            _subscription: ws::subscribe(
+                WebsocketTopic::Tasks,
+                ctx.link().callback(|_| Msg::UpdateTasks),
+            ),
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -35,11 +147,442 @@ impl Component for OptionsPage {
                 self.resp = Some(*resp);
             }
 
+            Msg::TasksResults(resp) => {
+                self.tasks_resp = Some(resp);
+            }
+
+            Msg::ApiTokensResults(resp) => {
+                self.api_tokens_resp = Some(resp);
+            }
+
+            Msg::ApiTokenCreated(resp) => match resp.ok() {
+                Ok(resp) => {
+                    self.new_api_token_secret = Some(resp.secret);
+
+                    ctx.link().send_message(Msg::UpdateApiTokens);
+                }
+
+                Err(e) => {
+                    log::error!("{e}");
+
+                    return false;
+                }
+            },
+
+            Msg::AccountResult(resp, message) => {
+                self.account_message = Some(match resp.ok() {
+                    Ok(_) => message.to_string(),
+                    Err(e) => e.description,
+                });
+            }
+
+            Msg::TwoFactorResults(resp) => {
+                self.two_factor_resp = Some(resp);
+            }
+
+            Msg::TwoFactorSetupResults(resp) => match resp.ok() {
+                Ok(resp) => {
+                    self.two_factor_message = None;
+                    self.two_factor_setup = Some(resp);
+                }
+
+                Err(e) => self.two_factor_message = Some(e.description),
+            },
+
+            Msg::TwoFactorRecoveryCodes(resp) => match resp.ok() {
+                Ok(resp) => {
+                    self.two_factor_message = None;
+                    self.two_factor_setup = None;
+                    self.two_factor_recovery_codes = Some(resp.recovery_codes);
+
+                    ctx.link().send_message(Msg::UpdateTwoFactor);
+                }
+
+                Err(e) => self.two_factor_message = Some(e.description),
+            },
+
+            Msg::TwoFactorDisabled(resp) => {
+                match resp.ok() {
+                    Ok(_) => {
+                        self.two_factor_message = None;
+                        self.two_factor_recovery_codes = None;
+                    }
+
+                    Err(e) => self.two_factor_message = Some(e.description),
+                }
+
+                ctx.link().send_message(Msg::UpdateTwoFactor);
+            }
+
+            Msg::SessionsResults(resp) => {
+                self.sessions_resp = Some(resp);
+            }
+
+            Msg::RevokeSession(id) => {
+                ctx.link().send_future(async move {
+                    if let Err(e) = request::revoke_account_session(id).await.ok() {
+                        log::error!("{e}");
+                    }
+
+                    Msg::UpdateSessions
+                });
+            }
+
+            Msg::ServerLinksResults(resp) => {
+                self.server_links_resp = Some(resp);
+            }
+
+            Msg::ServerLinkGrantsResults(resp) => {
+                self.server_link_grants_resp = Some(resp);
+            }
+
+            Msg::ServerLinkRotated(resp) => match resp.ok() {
+                Ok(resp) => {
+                    self.rotated_server_link = Some(resp);
+
+                    ctx.link().send_message(Msg::UpdateServerLinks);
+                }
+
+                Err(e) => {
+                    log::error!("{e}");
+
+                    return false;
+                }
+            },
+
+            Msg::RenameServerLink(id) => {
+                let server_owner_name = match self.server_links_resp.as_ref() {
+                    Some(WrappingResponse::Resp(resp)) => resp
+                        .items
+                        .iter()
+                        .find(|v| v.id == id)
+                        .and_then(|v| v.server_owner_name.clone()),
+                    _ => None,
+                };
+
+                // Cancelling keeps the current name. An empty one removes it.
+                if let Some(server_name) =
+                    window().prompt_with_message("Server Name").ok().flatten()
+                {
+                    ctx.link().send_future(async move {
+                        let resp = request::update_server_link(
+                            id,
+                            &api::UpdateServerLinkBody {
+                                server_owner_name,
+                                server_name: Some(server_name),
+                            },
+                        )
+                        .await;
+
+                        if let Err(e) = resp.ok() {
+                            log::error!("{e}");
+                        }
+
+                        Msg::UpdateServerLinks
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RotateServerLink(id) => {
+                let confirmed = window()
+                    .confirm_with_message(
+                        "Generate new IDs for this server? It won't be able to search until it's updated with them.",
+                    )
+                    .unwrap_or_default();
+
+                if confirmed {
+                    ctx.link().send_future(async move {
+                        Msg::ServerLinkRotated(request::rotate_server_link(id).await)
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RevokeServerLink(id) => {
+                let confirmed = window()
+                    .confirm_with_message(
+                        "Revoke this server? It will no longer be able to search.",
+                    )
+                    .unwrap_or_default();
+
+                if confirmed {
+                    ctx.link().send_future(async move {
+                        if let Err(e) = request::revoke_server_link(id).await.ok() {
+                            log::error!("{e}");
+                        }
+
+                        Msg::UpdateServerLinks
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RevokeServerLinkGrant(id) => {
+                let confirmed = window()
+                    .confirm_with_message(
+                        "Revoke this server's access? It will no longer be able to act on your behalf.",
+                    )
+                    .unwrap_or_default();
+
+                if confirmed {
+                    ctx.link().send_future(async move {
+                        if let Err(e) = request::revoke_server_link_grant(id).await.ok() {
+                            log::error!("{e}");
+                        }
+
+                        Msg::UpdateServerLinkGrants
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::SetupTwoFactor => {
+                ctx.link().send_future(async {
+                    Msg::TwoFactorSetupResults(request::setup_two_factor().await)
+                });
+
+                return false;
+            }
+
+            Msg::EnableTwoFactor => {
+                if let Some(code) = prompt_password("Code from your authenticator app") {
+                    ctx.link().send_future(async move {
+                        Msg::TwoFactorRecoveryCodes(
+                            request::enable_two_factor(&api::TwoFactorCodeBody { code }).await,
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::DisableTwoFactor => {
+                if let Some(code) =
+                    prompt_password("Code from your authenticator app or a recovery code")
+                {
+                    ctx.link().send_future(async move {
+                        Msg::TwoFactorDisabled(
+                            request::disable_two_factor(&api::TwoFactorCodeBody { code }).await,
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RegenerateRecoveryCodes => {
+                if let Some(code) =
+                    prompt_password("Code from your authenticator app or a recovery code")
+                {
+                    ctx.link().send_future(async move {
+                        Msg::TwoFactorRecoveryCodes(
+                            request::regenerate_two_factor_recovery(&api::TwoFactorCodeBody {
+                                code,
+                            })
+                            .await,
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::ChangeName => {
+                let name = window()
+                    .prompt_with_message("New Name")
+                    .ok()
+                    .flatten()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+
+                if let Some(name) = name {
+                    ctx.link().send_future(async move {
+                        let resp = request::change_account_name(&api::ChangeNameBody {
+                            name: name.clone(),
+                        })
+                        .await;
+
+                        if let WrappingResponse::Resp(_) = resp {
+                            if let Some(member) = MEMBER_SELF.lock().unwrap().as_mut() {
+                                member.name = name;
+                            }
+                        }
+
+                        Msg::AccountResult(resp, "Name updated.")
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::ChangeEmail => {
+                let email = window()
+                    .prompt_with_message("New Email Address")
+                    .ok()
+                    .flatten()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+
+                if let Some(email) = email {
+                    let password =
+                        prompt_password("Current Password. Leave empty if you don't have one.");
+
+                    ctx.link().send_future(async move {
+                        Msg::AccountResult(
+                            request::change_account_email(&api::ChangeEmailBody {
+                                email,
+                                password,
+                            })
+                            .await,
+                            "A verification link has been sent to your new email address.",
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::ChangePassword => {
+                let current_password =
+                    prompt_password("Current Password. Leave empty if you don't have one.");
+
+                if let Some(new_password) = prompt_password("New Password") {
+                    ctx.link().send_future(async move {
+                        Msg::AccountResult(
+                            request::change_account_password(&api::ChangePasswordBody {
+                                current_password,
+                                new_password,
+                            })
+                            .await,
+                            "Password updated.",
+                        )
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::ResendVerification => {
+                ctx.link().send_future(async {
+                    Msg::AccountResult(
+                        request::resend_account_verification().await,
+                        "A verification link has been sent to your email address.",
+                    )
+                });
+
+                return false;
+            }
+
             Msg::UpdateSettings => {
                 ctx.link().send_future(async {
                     Msg::SettingsResults(Box::new(request::get_settings().await))
                 });
             }
+
+            Msg::UpdateTasks => {
+                ctx.link()
+                    .send_future(async { Msg::TasksResults(request::get_tasks().await) });
+            }
+
+            Msg::UpdateTwoFactor => {
+                ctx.link().send_future(async {
+                    Msg::TwoFactorResults(request::get_two_factor_status().await)
+                });
+            }
+
+            Msg::UpdateSessions => {
+                ctx.link().send_future(async {
+                    Msg::SessionsResults(request::get_account_sessions().await)
+                });
+            }
+
+            Msg::UpdateServerLinks => {
+                ctx.link().send_future(async {
+                    Msg::ServerLinksResults(request::get_account_server_links().await)
+                });
+            }
+
+            Msg::UpdateServerLinkGrants => {
+                ctx.link().send_future(async {
+                    Msg::ServerLinkGrantsResults(request::get_server_link_grants().await)
+                });
+            }
+
+            Msg::UpdateApiTokens => {
+                ctx.link()
+                    .send_future(async { Msg::ApiTokensResults(request::get_api_tokens().await) });
+            }
+
+            Msg::RunTask(name) => {
+                ctx.link().send_future(async move {
+                    if let Err(e) = request::run_task(&name).await.ok() {
+                        log::error!("{e}");
+                    }
+
+                    Msg::UpdateTasks
+                });
+            }
+
+            Msg::CancelTask(name) => {
+                ctx.link().send_future(async move {
+                    if let Err(e) = request::cancel_task(&name).await.ok() {
+                        log::error!("{e}");
+                    }
+
+                    Msg::UpdateTasks
+                });
+            }
+
+            Msg::ToggleNewApiTokenScope(scope) => {
+                self.new_api_token_scopes.toggle(scope);
+            }
+
+            Msg::CreateApiToken => {
+                let name = window()
+                    .prompt_with_message("Token Name")
+                    .ok()
+                    .flatten()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+
+                if let Some(name) = name {
+                    let expires_at = window()
+                        .prompt_with_message(
+                            "Expires in how many days? Leave empty to never expire.",
+                        )
+                        .ok()
+                        .flatten()
+                        .and_then(|v| v.trim().parse::<i64>().ok())
+                        .map(|days| Utc::now() + Duration::days(days));
+
+                    let body = api::NewApiTokenBody {
+                        name,
+                        scopes: self.new_api_token_scopes,
+                        expires_at,
+                    };
+
+                    ctx.link().send_future(async move {
+                        Msg::ApiTokenCreated(request::create_api_token(&body).await)
+                    });
+                }
+
+                return false;
+            }
+
+            Msg::RevokeApiToken(id) => {
+                ctx.link().send_future(async move {
+                    if let Err(e) = request::revoke_api_token(id).await.ok() {
+                        log::error!("{e}");
+                    }
+
+                    Msg::UpdateApiTokens
+                });
+            }
         }
 
         true
@@ -82,6 +625,16 @@ impl Component for OptionsPage {
                             </select>
                         </div>
 
+                        { self.render_account(ctx) }
+
+                        { self.render_two_factor(ctx) }
+
+                        { self.render_sessions(ctx) }
+
+                        { self.render_server_links(ctx) }
+
+                        { self.render_server_link_grants(ctx) }
+
                         <h3>{ "My Settings" }</h3>
 
                         <div class="mb-2 shrink-width-to-content">
@@ -90,16 +643,17 @@ impl Component for OptionsPage {
                                 class="form-select"
                                 id="page-view-type"
                                 onchange={
-                                    ctx.link().callback_future(|e: Event| {
+                                    let settings = member.localsettings.clone();
+
+                                    ctx.link().callback_future(move |e: Event| {
                                         let index = e.target_unchecked_into::<HtmlSelectElement>().selected_index();
 
-                                        async move {
-                                            request::update_settings(OptionsUpdate {
-                                                member: Some(MemberSettings {
-                                                    page_view: Some(if index == 0 { PageView::Viewing } else { PageView::Editing }),
-                                                }),
+                                        let settings = settings.clone();
 
-                                                .. Default::default()
+                                        async move {
+                                            update_member_settings(MemberSettings {
+                                                page_view: Some(if index == 0 { PageView::Viewing } else { PageView::Editing }),
+                                                .. settings
                                             }).await;
 
                                             Msg::UpdateSettings
@@ -112,17 +666,49 @@ impl Component for OptionsPage {
                             </select>
                         </div>
 
+                        <div class="mb-2 shrink-width-to-content">
+                            <label for="email-digest">{ "Email Digest of Notifications" }</label>
+                            <select
+                                class="form-select"
+                                id="email-digest"
+                                onchange={
+                                    let settings = member.localsettings.clone();
+
+                                    ctx.link().callback_future(move |e: Event| {
+                                        let index = e.target_unchecked_into::<HtmlSelectElement>().selected_index();
+
+                                        let settings = settings.clone();
+
+                                        async move {
+                                            update_member_settings(MemberSettings {
+                                                email_digest: match index {
+                                                    1 => Some(DigestFrequency::Daily),
+                                                    2 => Some(DigestFrequency::Weekly),
+                                                    _ => None,
+                                                },
+                                                .. settings
+                                            }).await;
+
+                                            Msg::UpdateSettings
+                                        }
+                                    })
+                                }
+                            >
+                                <option selected={ member.localsettings.email_digest.is_none() }>{ "Never" }</option>
+                                <option selected={ member.localsettings.email_digest == Some(DigestFrequency::Daily) }>{ DigestFrequency::Daily.get_name() }</option>
+                                <option selected={ member.localsettings.email_digest == Some(DigestFrequency::Weekly) }>{ DigestFrequency::Weekly.get_name() }</option>
+                            </select>
+                        </div>
+
+                        { self.render_api_tokens(ctx) }
+
+                        { self.render_tasks(ctx) }
+
                         <br />
 
                         <div>
                             <a class="btn btn-danger" href="/auth/logout">{ "Logout" }</a>
                         </div>
-
-
-                        // <button class="button" onclick={ ctx.link().callback_future(|_| async {
-                        //     request::run_task().await;
-                        //     Msg::Ignore
-                        // }) }>{ "Run Library Scan + Metadata Updater" }</button>
                     </div>
                 </div>
             }
@@ -136,6 +722,478 @@ impl Component for OptionsPage {
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
         if first_render {
             ctx.link().send_message(Msg::UpdateSettings);
+            ctx.link().send_message(Msg::UpdateApiTokens);
+            ctx.link().send_message(Msg::UpdateTwoFactor);
+            ctx.link().send_message(Msg::UpdateSessions);
+            ctx.link().send_message(Msg::UpdateServerLinks);
+            ctx.link().send_message(Msg::UpdateServerLinkGrants);
+
+            if get_member_self()
+                .map(|v| v.permissions.is_admin())
+                .unwrap_or_default()
+            {
+                ctx.link().send_message(Msg::UpdateTasks);
+            }
+        }
+    }
+}
+
+/// Also updates the cached member so later changes don't overwrite this one.
+async fn update_member_settings(settings: MemberSettings) {
+    request::update_settings(OptionsUpdate {
+        member: Some(settings.clone()),
+        ..Default::default()
+    })
+    .await;
+
+    if let Some(member) = MEMBER_SELF.lock().unwrap().as_mut() {
+        member.localsettings = settings;
+    }
+}
+
+/// Empty values are treated as not entered.
+fn prompt_password(message: &str) -> Option<String> {
+    window()
+        .prompt_with_message(message)
+        .ok()
+        .flatten()
+        .filter(|v| !v.is_empty())
+}
+
+impl OptionsPage {
+    fn render_account(&self, ctx: &Context<Self>) -> Html {
+        let member = get_member_self().unwrap_throw();
+
+        html! {
+            <>
+                <h3>{ "Account" }</h3>
+
+                <div class="mb-2">
+                    <b>{ "Name: " }</b>
+                    <span>{ member.name.clone() }</span>
+                    <button class="btn btn-sm btn-secondary" onclick={ ctx.link().callback(|_| Msg::ChangeName) }>{ "Change" }</button>
+                </div>
+
+                <div class="mb-2">
+                    <b>{ "Email: " }</b>
+                    <span>{ member.email.clone().unwrap_or_default() }</span>
+                    <button class="btn btn-sm btn-secondary" onclick={ ctx.link().callback(|_| Msg::ChangeEmail) }>{ "Change" }</button>
+
+                    {
+                        if member.email.is_some() && !member.email_verified {
+                            html! {
+                                <>
+                                    <span class="badge bg-warning">{ "Unverified" }</span>
+                                    <button class="btn btn-sm btn-secondary" onclick={ ctx.link().callback(|_| Msg::ResendVerification) }>{ "Resend Verification" }</button>
+                                </>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+
+                <div class="mb-2">
+                    <button class="btn btn-sm btn-secondary" onclick={ ctx.link().callback(|_| Msg::ChangePassword) }>{ "Change Password" }</button>
+                </div>
+
+                {
+                    if let Some(message) = self.account_message.as_ref() {
+                        html! {
+                            <div class="mb-2">{ message.clone() }</div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </>
+        }
+    }
+
+    fn render_two_factor(&self, ctx: &Context<Self>) -> Html {
+        let resp = match self.two_factor_resp.as_ref() {
+            Some(WrappingResponse::Resp(v)) => v,
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                <h3>{ "Two-Factor Authentication" }</h3>
+
+                {
+                    if resp.enabled {
+                        html! {
+                            <div class="mb-2">
+                                <span class="badge bg-success">{ "Enabled" }</span>
+                                <span>{ format!(" {} recovery codes remaining ", resp.recovery_codes_remaining) }</span>
+                                <button class="btn btn-sm btn-secondary" onclick={ ctx.link().callback(|_| Msg::RegenerateRecoveryCodes) }>{ "New Recovery Codes" }</button>
+                                <button class="btn btn-sm btn-danger" onclick={ ctx.link().callback(|_| Msg::DisableTwoFactor) }>{ "Disable" }</button>
+                            </div>
+                        }
+                    } else if let Some(setup) = self.two_factor_setup.as_ref() {
+                        html! {
+                            <>
+                                <div>{ "Scan this with your authenticator app then enter a code from it." }</div>
+                                { render_setup(setup) }
+                                <button class="btn btn-sm btn-primary" onclick={ ctx.link().callback(|_| Msg::EnableTwoFactor) }>{ "Enable" }</button>
+                            </>
+                        }
+                    } else {
+                        html! {
+                            <div class="mb-2">
+                                <button class="btn btn-sm btn-primary" onclick={ ctx.link().callback(|_| Msg::SetupTwoFactor) }>{ "Set Up" }</button>
+                            </div>
+                        }
+                    }
+                }
+
+                {
+                    if let Some(codes) = self.two_factor_recovery_codes.as_ref() {
+                        html! {
+                            <div class="mb-2">
+                                <span>{ "Store these recovery codes somewhere safe. They won't be shown again:" }</span>
+                                <ul>
+                                    { for codes.iter().map(|code| html! { <li><code>{ code.clone() }</code></li> }) }
+                                </ul>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                {
+                    if let Some(message) = self.two_factor_message.as_ref() {
+                        html! {
+                            <div class="mb-2">{ message.clone() }</div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </>
+        }
+    }
+
+    fn render_sessions(&self, ctx: &Context<Self>) -> Html {
+        let resp = match self.sessions_resp.as_ref() {
+            Some(WrappingResponse::Resp(v)) => v,
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                <h3>{ "Sessions" }</h3>
+
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Device" }</th>
+                            <th>{ "IP Address" }</th>
+                            <th>{ "Logged In" }</th>
+                            <th>{ "Last Seen" }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for resp.items.iter().map(|session| {
+                                let id = session.id;
+
+                                html! {
+                                    <tr>
+                                        <td>{ session.user_agent.clone().unwrap_or_else(|| String::from("Unknown")) }</td>
+                                        <td>{ session.ip_address.clone().unwrap_or_default() }</td>
+                                        <td>{ session.created_at.format("%b %e, %Y %T").to_string() }</td>
+                                        <td>{ session.last_seen_at.format("%b %e, %Y %T").to_string() }</td>
+                                        <td>
+                                            {
+                                                if session.is_current {
+                                                    html! {
+                                                        <span class="badge bg-success">{ "This Device" }</span>
+                                                    }
+                                                } else {
+                                                    html! {
+                                                        <button class="btn btn-sm btn-danger" onclick={ ctx.link().callback(move |_| Msg::RevokeSession(id)) }>{ "Revoke" }</button>
+                                                    }
+                                                }
+                                            }
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+
+    fn render_server_links(&self, ctx: &Context<Self>) -> Html {
+        let resp = match self.server_links_resp.as_ref() {
+            Some(WrappingResponse::Resp(v)) => v,
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                <h3>{ "Linked Servers" }</h3>
+
+                {
+                    if let Some(rotated) = self.rotated_server_link.as_ref() {
+                        html! {
+                            <div class="mb-2">
+                                <div>{ "Update your server with its new IDs. The Server ID won't be shown again." }</div>
+                                <div>{ "Server ID: " } <code>{ rotated.server_id.clone() }</code></div>
+                                <div>{ "Public ID: " } <code>{ rotated.link.public_id.clone() }</code></div>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Name" }</th>
+                            <th>{ "Status" }</th>
+                            <th>{ "Linked" }</th>
+                            <th>{ "Last Seen" }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for resp.items.iter().map(|link| {
+                                let id = link.id;
+
+                                html! {
+                                    <tr>
+                                        <td>{ link.server_name.clone().unwrap_or_else(|| String::from("Unnamed")) }</td>
+                                        <td>
+                                            {
+                                                if link.verified {
+                                                    html! { <span class="badge bg-success">{ "Verified" }</span> }
+                                                } else {
+                                                    html! { <span class="badge bg-warning">{ "Unverified" }</span> }
+                                                }
+                                            }
+                                        </td>
+                                        <td>{ link.created_at.format("%b %e, %Y %T").to_string() }</td>
+                                        <td>{ link.last_seen_at.map(|v| v.format("%b %e, %Y %T").to_string()).unwrap_or_else(|| String::from("Never")) }</td>
+                                        <td>
+                                            <button class="btn btn-sm btn-secondary" onclick={ ctx.link().callback(move |_| Msg::RenameServerLink(id)) }>{ "Rename" }</button>
+                                            <button class="btn btn-sm btn-warning" onclick={ ctx.link().callback(move |_| Msg::RotateServerLink(id)) }>{ "Rotate IDs" }</button>
+                                            <button class="btn btn-sm btn-danger" onclick={ ctx.link().callback(move |_| Msg::RevokeServerLink(id)) }>{ "Revoke" }</button>
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+
+    fn render_server_link_grants(&self, ctx: &Context<Self>) -> Html {
+        let resp = match self.server_link_grants_resp.as_ref() {
+            Some(WrappingResponse::Resp(v)) if !v.items.is_empty() => v,
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                <h3>{ "Authorized Servers" }</h3>
+
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Server" }</th>
+                            <th>{ "Scopes" }</th>
+                            <th>{ "Granted" }</th>
+                            <th>{ "Last Used" }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for resp.items.iter().map(|grant| {
+                                let id = grant.id;
+
+                                let scopes = API_TOKEN_SCOPES
+                                    .into_iter()
+                                    .filter(|v| grant.scopes.contains(*v))
+                                    .map(|v| v.get_name())
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+
+                                html! {
+                                    <tr>
+                                        <td>
+                                            { grant.server_name.clone().unwrap_or_else(|| String::from("Unnamed")) }
+                                            { " by " }
+                                            { grant.server_owner_name.clone().unwrap_or_else(|| String::from("(Unknown)")) }
+                                        </td>
+                                        <td>{ scopes }</td>
+                                        <td>{ grant.updated_at.format("%b %e, %Y %T").to_string() }</td>
+                                        <td>{ grant.last_used_at.map(|v| v.format("%b %e, %Y %T").to_string()).unwrap_or_else(|| String::from("Never")) }</td>
+                                        <td>
+                                            <button class="btn btn-sm btn-danger" onclick={ ctx.link().callback(move |_| Msg::RevokeServerLinkGrant(id)) }>{ "Revoke" }</button>
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+
+    fn render_api_tokens(&self, ctx: &Context<Self>) -> Html {
+        let resp = match self.api_tokens_resp.as_ref() {
+            Some(WrappingResponse::Resp(v)) => v,
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                <h3>{ "API Tokens" }</h3>
+
+                {
+                    if let Some(secret) = self.new_api_token_secret.as_ref() {
+                        html! {
+                            <div class="mb-2">
+                                <span>{ "Copy your new token now. It won't be shown again: " }</span>
+                                <code>{ secret.clone() }</code>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <div class="mb-2">
+                    {
+                        for API_TOKEN_SCOPES.into_iter().map(|scope| html! {
+                            <label>
+                                <input
+                                    type="checkbox"
+                                    checked={ self.new_api_token_scopes.contains(scope) }
+                                    onclick={ ctx.link().callback(move |_| Msg::ToggleNewApiTokenScope(scope)) }
+                                />
+                                { scope.get_name() }
+                            </label>
+                        })
+                    }
+
+                    <button class="btn btn-sm btn-primary" onclick={ ctx.link().callback(|_| Msg::CreateApiToken) }>{ "Create Token" }</button>
+                </div>
+
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Name" }</th>
+                            <th>{ "Scopes" }</th>
+                            <th>{ "Last Used" }</th>
+                            <th>{ "Expires" }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for resp.items.iter().map(|token| {
+                                let id = token.id;
+
+                                let scopes = API_TOKEN_SCOPES
+                                    .into_iter()
+                                    .filter(|v| token.scopes.contains(*v))
+                                    .map(|v| v.get_name())
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+
+                                html! {
+                                    <tr>
+                                        <td>{ token.name.clone() }</td>
+                                        <td>{ scopes }</td>
+                                        <td>{ token.last_used_at.map(|v| v.format("%b %e, %Y %T").to_string()).unwrap_or_else(|| String::from("Never")) }</td>
+                                        <td>{ token.expires_at.map(|v| v.format("%b %e, %Y %T").to_string()).unwrap_or_else(|| String::from("Never")) }</td>
+                                        <td>
+                                            <button class="btn btn-sm btn-danger" onclick={ ctx.link().callback(move |_| Msg::RevokeApiToken(id)) }>{ "Revoke" }</button>
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+
+    fn render_tasks(&self, ctx: &Context<Self>) -> Html {
+        let resp = match self.tasks_resp.as_ref() {
+            Some(WrappingResponse::Resp(v)) => v,
+            _ => return html! {},
+        };
+
+        html! {
+            <>
+                <h3>{ "Tasks" }</h3>
+
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Name" }</th>
+                            <th>{ "Schedule" }</th>
+                            <th>{ "Last Run" }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for resp.items.iter().map(|task| {
+                                let name = task.name.clone();
+
+                                html! {
+                                    <tr>
+                                        <td title={ task.description.clone() }>{ task.name.clone() }</td>
+                                        <td>{ task.schedule.clone().unwrap_or_else(|| String::from("Manual")) }</td>
+                                        <td>
+                                            {
+                                                if let Some(run) = task.last_run.as_ref() {
+                                                    html! {
+                                                        <span title={ run.error.clone().unwrap_or_default() }>
+                                                            { run.status.get_name() } { " - " } { run.started_at.format("%b %e, %Y %T").to_string() }
+                                                        </span>
+                                                    }
+                                                } else {
+                                                    html! { { "Never" } }
+                                                }
+                                            }
+                                        </td>
+                                        <td>
+                                            {
+                                                if task.is_running {
+                                                    html! {
+                                                        <button class="btn btn-sm btn-danger" onclick={ ctx.link().callback(move |_| Msg::CancelTask(name.clone())) }>{ "Cancel" }</button>
+                                                    }
+                                                } else {
+                                                    html! {
+                                                        <button class="btn btn-sm btn-primary" onclick={ ctx.link().callback(move |_| Msg::RunTask(name.clone())) }>{ "Run Now" }</button>
+                                                    }
+                                                }
+                                            }
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+            </>
         }
     }
 }
@@ -2,7 +2,7 @@
 
 use common::{
     api::{ApiErrorResponse, DeletionResponse, QueryListResponse, WrappingResponse},
-    BookId, Either, ImageId, ImageIdType, PersonId, Source, TagId,
+    BookId, Either, ImageId, ImageIdType, MemberId, PersonId, Source, TagId,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -14,7 +14,8 @@ use common_local::{
     api::*,
     item::edit::{BookEdit, UpdateEditModel},
     update::OptionsUpdate,
-    CollectionId, DisplayMetaItem, EditId, Member, SearchGroup, SearchGroupId, SearchType, TagType,
+    ApiTokenId, CollectionId, DisplayMetaItem, EditCommentId, EditId, Member, NotificationId,
+    SearchGroup, SearchGroupId, SearchType, ServerLinkGrantId, ServerLinkId, TagType,
 };
 
 // Collection
@@ -51,7 +52,7 @@ pub async fn get_collection_list(
 pub async fn update_collection(
     id: CollectionId,
     value: &UpdateCollectionModel,
-) -> WrappingResponse<String> {
+) -> WrappingResponse<bool> {
     fetch("POST", &format!("/api/v1/collection/{}", id), Some(value))
         .await
         .unwrap_or_else(def)
@@ -87,11 +88,53 @@ pub async fn get_collection_books(path: &str) -> WrappingResponse<GetBookListRes
 
 // Edits
 
-pub async fn get_edit_list(
+pub async fn get_edit_list(query: EditListQuery) -> WrappingResponse<GetEditListResponse> {
+    let url = format!("/api/v1/edits?{}", serde_qs::to_string(&query).unwrap());
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn update_edit_item(
+    id: EditId,
+    value: &UpdateEditModel,
+) -> WrappingResponse<PostEditResponse> {
+    fetch("POST", &format!("/api/v1/edit/{}", id), Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn rebase_edit_item(id: EditId) -> WrappingResponse<PostEditResponse> {
+    fetch(
+        "POST",
+        &format!("/api/v1/edit/{id}/rebase"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn override_edit_item(
+    id: EditId,
+    status: EditStatus,
+    reason: String,
+) -> WrappingResponse<PostEditResponse> {
+    fetch(
+        "POST",
+        &format!("/api/v1/edit/{id}/override"),
+        Some(&OverrideEditBody { status, reason }),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn get_edit_comments(
+    id: EditId,
     offset: Option<usize>,
     limit: Option<usize>,
-) -> WrappingResponse<GetEditListResponse> {
-    let mut url = String::from("/api/v1/edits?");
+) -> WrappingResponse<GetEditCommentListResponse> {
+    let mut url = format!("/api/v1/edit/{id}/comments?");
 
     if let Some(value) = offset {
         url += "offset=";
@@ -110,13 +153,41 @@ pub async fn get_edit_list(
         .unwrap_or_else(def)
 }
 
-pub async fn update_edit_item(
+pub async fn new_edit_comment(
     id: EditId,
-    value: &UpdateEditModel,
-) -> WrappingResponse<PostEditResponse> {
-    fetch("POST", &format!("/api/v1/edit/{}", id), Some(value))
-        .await
-        .unwrap_or_else(def)
+    text: String,
+    parent_id: Option<EditCommentId>,
+) -> WrappingResponse<NewEditCommentResponse> {
+    fetch(
+        "POST",
+        &format!("/api/v1/edit/{id}/comments"),
+        Some(&NewEditCommentBody { text, parent_id }),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn delete_edit_comment(
+    id: EditId,
+    comment_id: EditCommentId,
+) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/edit/{id}/comments/{comment_id}"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn revert_edit_item(id: EditId) -> WrappingResponse<bool> {
+    fetch(
+        "POST",
+        &format!("/api/v1/edit/{id}/revert"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
 }
 
 // Tags
@@ -240,6 +311,52 @@ pub async fn get_member_list(
         .unwrap_or_else(def)
 }
 
+pub async fn update_member(id: MemberId, value: &UpdateMemberBody) -> WrappingResponse<Member> {
+    fetch("POST", &format!("/api/v1/member/{}", *id), Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn suspend_member(id: MemberId, value: &SuspendMemberBody) -> WrappingResponse<Member> {
+    fetch(
+        "POST",
+        &format!("/api/v1/member/{}/suspend", *id),
+        Some(value),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn unsuspend_member(id: MemberId) -> WrappingResponse<Member> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/member/{}/suspend", *id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn delete_member(id: MemberId, anonymize: bool) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/member/{}?anonymize={anonymize}", *id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn revoke_member_sessions(id: MemberId) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/member/{}/sessions", *id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
 // Metadata
 
 pub async fn update_book(id: BookId, value: &BookEdit) -> WrappingResponse<String> {
@@ -248,6 +365,16 @@ pub async fn update_book(id: BookId, value: &BookEdit) -> WrappingResponse<Strin
         .unwrap_or_else(def)
 }
 
+pub async fn merge_book(id: BookId, into_id: BookId) -> WrappingResponse<String> {
+    fetch(
+        "POST",
+        &format!("/api/v1/book/{}/merge/{}", id, into_id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
 pub async fn delete_book(id: BookId) -> WrappingResponse<bool> {
     fetch(
         "DELETE",
@@ -258,6 +385,49 @@ pub async fn delete_book(id: BookId) -> WrappingResponse<bool> {
     .unwrap_or_else(def)
 }
 
+pub async fn get_deleted_books(
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> WrappingResponse<QueryListResponse<DisplayMetaItem>> {
+    let mut url = String::from("/api/v1/books/deleted?");
+
+    if let Some(value) = offset {
+        url += "offset=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    if let Some(value) = limit {
+        url += "limit=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn restore_book(id: BookId) -> WrappingResponse<bool> {
+    fetch(
+        "POST",
+        &format!("/api/v1/book/{}/restore", id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn purge_book(id: BookId) -> WrappingResponse<bool> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/book/{}/purge", id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
 pub async fn get_media_view(book_id: BookId) -> WrappingResponse<MediaViewResponse> {
     fetch(
         "GET",
@@ -346,6 +516,30 @@ pub async fn get_person(id: PersonId) -> WrappingResponse<GetPersonResponse> {
     .unwrap_or_else(def)
 }
 
+pub async fn get_person_history(
+    id: PersonId,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> WrappingResponse<GetEditListResponse> {
+    let mut url = format!("/api/v1/person/{id}/history?");
+
+    if let Some(value) = offset {
+        url += "offset=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    if let Some(value) = limit {
+        url += "limit=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
 pub async fn new_person(value: Source) -> WrappingResponse<String> {
     fetch("POST", "/api/v1/person", Some(&value))
         .await
@@ -376,6 +570,30 @@ pub async fn get_book_info(id: BookId) -> WrappingResponse<GetBookIdResponse> {
         .unwrap_or_else(def)
 }
 
+pub async fn get_book_history(
+    id: BookId,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> WrappingResponse<GetEditListResponse> {
+    let mut url = format!("/api/v1/book/{id}/history?");
+
+    if let Some(value) = offset {
+        url += "offset=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    if let Some(value) = limit {
+        url += "limit=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
 // Searches
 
 pub async fn get_search_list(
@@ -424,23 +642,269 @@ pub async fn update_settings(value: OptionsUpdate) -> WrappingResponse<String> {
         .unwrap_or_else(def)
 }
 
-pub async fn run_task() {
-    // TODO: Use common::api::RunTaskBody
-    let _: Option<String> = fetch(
+// Tasks
+
+pub async fn get_tasks() -> WrappingResponse<GetTasksResponse> {
+    fetch("GET", "/api/v1/tasks", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn run_task(name: &str) -> WrappingResponse<RunTaskResponse> {
+    fetch(
         "POST",
-        "/api/v1/task",
-        Some(&serde_json::json!({
-            "run_search": true,
-            "run_metadata": true
-        })),
+        &format!("/api/v1/task/{name}/run"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn cancel_task(name: &str) -> WrappingResponse<bool> {
+    fetch(
+        "POST",
+        &format!("/api/v1/task/{name}/cancel"),
+        Option::<&()>::None,
     )
     .await
-    .ok();
+    .unwrap_or_else(def)
+}
+
+// Watches
+
+pub async fn get_watches() -> WrappingResponse<GetWatchesResponse> {
+    fetch("GET", "/api/v1/watches", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn update_watch(value: WatchBody, is_watching: bool) -> WrappingResponse<String> {
+    fetch(
+        if is_watching { "POST" } else { "DELETE" },
+        "/api/v1/watch",
+        Some(&value),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+// Notifications
+
+pub async fn get_notifications(
+    query: NotificationListQuery,
+) -> WrappingResponse<GetNotificationsResponse> {
+    let url = format!(
+        "/api/v1/notifications?{}",
+        serde_qs::to_string(&query).unwrap()
+    );
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn mark_notifications(
+    ids: Option<Vec<NotificationId>>,
+    is_read: bool,
+) -> WrappingResponse<String> {
+    fetch(
+        "POST",
+        "/api/v1/notifications/read",
+        Some(&MarkNotificationsBody { ids, is_read }),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+// API Tokens
+
+pub async fn get_api_tokens() -> WrappingResponse<GetApiTokensResponse> {
+    fetch("GET", "/api/v1/tokens", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn create_api_token(value: &NewApiTokenBody) -> WrappingResponse<NewApiTokenResponse> {
+    fetch("POST", "/api/v1/token", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn revoke_api_token(id: ApiTokenId) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/token/{}", *id),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+// Audit Log
+
+pub async fn get_audit_log(query: AuditLogQuery) -> WrappingResponse<GetAuditLogResponse> {
+    let url = format!(
+        "/api/v1/admin/audit?{}",
+        serde_qs::to_string(&query).unwrap()
+    );
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+// Account
+
+pub async fn change_account_name(value: &ChangeNameBody) -> WrappingResponse<String> {
+    fetch("POST", "/api/v1/account/name", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn change_account_email(value: &ChangeEmailBody) -> WrappingResponse<String> {
+    fetch("POST", "/api/v1/account/email", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn change_account_password(value: &ChangePasswordBody) -> WrappingResponse<String> {
+    fetch("POST", "/api/v1/account/password", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn resend_account_verification() -> WrappingResponse<String> {
+    fetch("POST", "/api/v1/account/verify", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn get_account_sessions() -> WrappingResponse<GetSessionsResponse> {
+    fetch("GET", "/api/v1/account/sessions", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn revoke_account_session(id: usize) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/account/session/{id}"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+// Server Links
+
+pub async fn get_account_server_links() -> WrappingResponse<GetServerLinksResponse> {
+    fetch("GET", "/api/v1/account/servers", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn get_all_server_links(
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> WrappingResponse<GetAllServerLinksResponse> {
+    let mut url = String::from("/api/v1/admin/servers?");
+
+    if let Some(value) = offset {
+        url += "offset=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    if let Some(value) = limit {
+        url += "limit=";
+        url += &value.to_string();
+        url += "&";
+    }
+
+    fetch("GET", &url, Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn update_server_link(
+    id: ServerLinkId,
+    value: &UpdateServerLinkBody,
+) -> WrappingResponse<SharedServerLink> {
+    fetch("POST", &format!("/api/v1/server/{id}"), Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn rotate_server_link(id: ServerLinkId) -> WrappingResponse<RotateServerLinkResponse> {
+    fetch(
+        "POST",
+        &format!("/api/v1/server/{id}/rotate"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn update_server_link_limits(
+    id: ServerLinkId,
+    body: &UpdateServerLinkLimitsBody,
+) -> WrappingResponse<String> {
+    fetch(
+        "POST",
+        &format!("/api/v1/admin/server/{id}/limits"),
+        Some(body),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn get_server_link_grants() -> WrappingResponse<GetServerLinkGrantsResponse> {
+    fetch("GET", "/api/v1/account/grants", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn get_grant_server(public_id: &str) -> WrappingResponse<GetGrantServerResponse> {
+    fetch(
+        "GET",
+        &format!("/api/v1/grant/server/{}", urlencoding::encode(public_id)),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn create_server_link_grant(
+    value: &NewServerLinkGrantBody,
+) -> WrappingResponse<SharedServerLinkGrant> {
+    fetch("POST", "/api/v1/account/grant", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn revoke_server_link_grant(id: ServerLinkGrantId) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/account/grant/{id}"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn revoke_server_link(id: ServerLinkId) -> WrappingResponse<String> {
+    fetch(
+        "DELETE",
+        &format!("/api/v1/server/{id}"),
+        Option::<&()>::None,
+    )
+    .await
+    .unwrap_or_else(def)
 }
 
 // Login In
 
-pub async fn login_with_password(email: String, password: String) -> WrappingResponse<String> {
+pub async fn login_with_password(email: String, password: String) -> WrappingResponse<LoginStatus> {
     fetch(
         "POST",
         "/auth/password",
@@ -465,6 +929,78 @@ pub async fn login_without_password(email: String) -> WrappingResponse<String> {
     .unwrap_or_else(def)
 }
 
+pub async fn signup_with_password(
+    name: String,
+    email: String,
+    password: String,
+) -> WrappingResponse<String> {
+    fetch(
+        "POST",
+        "/auth/password/signup",
+        Some(&json!({
+            "name": name,
+            "email": email,
+            "password": password,
+        })),
+    )
+    .await
+    .unwrap_or_else(def)
+}
+
+pub async fn request_password_reset(value: &PasswordResetBody) -> WrappingResponse<String> {
+    fetch("POST", "/auth/password/reset", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn confirm_password_reset(value: &PasswordResetConfirmBody) -> WrappingResponse<String> {
+    fetch("POST", "/auth/password/reset/confirm", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+// Two-Factor
+
+pub async fn get_two_factor_status() -> WrappingResponse<TwoFactorStatusResponse> {
+    fetch("GET", "/auth/2fa", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn setup_two_factor() -> WrappingResponse<TwoFactorSetupResponse> {
+    fetch("POST", "/auth/2fa/setup", Option::<&()>::None)
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn enable_two_factor(
+    value: &TwoFactorCodeBody,
+) -> WrappingResponse<TwoFactorRecoveryCodesResponse> {
+    fetch("POST", "/auth/2fa/enable", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn verify_two_factor(value: &TwoFactorCodeBody) -> WrappingResponse<LoginStatus> {
+    fetch("POST", "/auth/2fa/verify", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn disable_two_factor(value: &TwoFactorCodeBody) -> WrappingResponse<String> {
+    fetch("POST", "/auth/2fa/disable", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
+pub async fn regenerate_two_factor_recovery(
+    value: &TwoFactorCodeBody,
+) -> WrappingResponse<TwoFactorRecoveryCodesResponse> {
+    fetch("POST", "/auth/2fa/recovery", Some(value))
+        .await
+        .unwrap_or_else(def)
+}
+
 async fn fetch<V: for<'a> Deserialize<'a>>(
     method: &str,
     url: &str,
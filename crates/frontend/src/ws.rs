@@ -0,0 +1,185 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use common_local::ws::{
+    WebsocketNotification, WebsocketRequest, WebsocketResponse, WebsocketTopic,
+};
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use gloo_timers::callback::Timeout;
+use reqwasm::websocket::{futures::WebSocket, Message};
+use wasm_bindgen_futures::spawn_local;
+use yew::Callback;
+
+/// How long we wait before trying to reconnect after the connection was lost.
+const RECONNECT_DELAY: u32 = 5_000;
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::default();
+}
+
+#[derive(Default)]
+struct State {
+    sender: Option<mpsc::UnboundedSender<WebsocketRequest>>,
+
+    next_id: usize,
+    listeners: HashMap<usize, (WebsocketTopic, Callback<WebsocketNotification>)>,
+}
+
+impl State {
+    fn has_topic(&self, topic: WebsocketTopic) -> bool {
+        self.listeners.values().any(|(t, _)| *t == topic)
+    }
+
+    fn send(&self, value: WebsocketRequest) {
+        if let Some(sender) = self.sender.as_ref() {
+            let _ = sender.unbounded_send(value);
+        }
+    }
+}
+
+/// Unsubscribes the callback once dropped.
+pub struct WsSubscription(usize);
+
+impl Drop for WsSubscription {
+    fn drop(&mut self) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+
+            if let Some((topic, _)) = state.listeners.remove(&self.0) {
+                if !state.has_topic(topic) {
+                    state.send(WebsocketRequest::Unsubscribe(topic));
+                }
+            }
+        });
+    }
+}
+
+/// Calls the callback for every notification of the topic until the subscription is dropped.
+pub fn subscribe(
+    topic: WebsocketTopic,
+    callback: Callback<WebsocketNotification>,
+) -> WsSubscription {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        if !state.has_topic(topic) {
+            state.send(WebsocketRequest::Subscribe(topic));
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+
+        state.listeners.insert(id, (topic, callback));
+
+        WsSubscription(id)
+    })
+}
+
+/// Opens the connection. Reconnects and resubscribes if it's lost.
+pub fn connect() {
+    let location = gloo_utils::window().location();
+
+    let protocol = if location.protocol().ok().as_deref() == Some("https:") {
+        "wss"
+    } else {
+        "ws"
+    };
+
+    let url = format!(
+        "{protocol}://{}/api/v1/ws",
+        location.host().unwrap_or_default()
+    );
+
+    let socket = match WebSocket::open(&url) {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("{e:?}");
+            reconnect();
+            return;
+        }
+    };
+
+    let (mut write, mut read) = socket.split();
+    let (sender, mut receiver) = mpsc::unbounded::<WebsocketRequest>();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let topics = state
+            .listeners
+            .values()
+            .map(|v| v.0)
+            .collect::<HashSet<_>>();
+
+        for topic in topics {
+            let _ = sender.unbounded_send(WebsocketRequest::Subscribe(topic));
+        }
+
+        state.sender = Some(sender);
+    });
+
+    spawn_local(async move {
+        while let Some(value) = receiver.next().await {
+            let value = match serde_json::to_string(&value) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("{e}");
+                    continue;
+                }
+            };
+
+            if write.send(Message::Text(value)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    spawn_local(async move {
+        while let Some(message) = read.next().await {
+            let text = match message {
+                Ok(Message::Text(v)) => v,
+                Ok(Message::Bytes(_)) => continue,
+                Err(_) => break,
+            };
+
+            match serde_json::from_str::<WebsocketResponse>(&text) {
+                Ok(WebsocketResponse::Ping) => {
+                    STATE.with(|state| state.borrow().send(WebsocketRequest::Pong))
+                }
+
+                Ok(WebsocketResponse::Pong) => (),
+
+                Ok(WebsocketResponse::Notification(value)) => {
+                    let topic = value.topic();
+
+                    // Cloned out so callbacks are able to (un)subscribe.
+                    let callbacks = STATE.with(|state| {
+                        state
+                            .borrow()
+                            .listeners
+                            .values()
+                            .filter(|(t, _)| *t == topic)
+                            .map(|(_, cb)| cb.clone())
+                            .collect::<Vec<_>>()
+                    });
+
+                    for callback in callbacks {
+                        callback.emit(value.clone());
+                    }
+                }
+
+                Err(e) => log::error!("{e}"),
+            }
+        }
+
+        STATE.with(|state| state.borrow_mut().sender = None);
+
+        reconnect();
+    });
+}
+
+fn reconnect() {
+    Timeout::new(RECONNECT_DELAY, connect).forget();
+}